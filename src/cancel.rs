@@ -0,0 +1,87 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio::sync::Notify;
+
+use crate::SyError;
+
+#[derive(Debug, Default)]
+struct Inner {
+    flag : AtomicBool,
+    notify : Notify
+}
+
+/// A cloneable token to cancel long-running operations (moves, homing, probing, program execution)
+/// 
+/// Any operation can be made cancellable by wrapping its future with `guard()`, the future is dropped 
+/// as soon as the token is cancelled. The token of a robot (see `Robot::cancel_token()`) aborts all of its drives, 
+/// homing and the program runners
+/// 
+/// ```rust
+/// use sybot::cancel::CancelToken;
+/// 
+/// let token = CancelToken::new();
+/// token.cancel();
+/// 
+/// assert!(token.check().is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    inner : Arc<Inner>
+}
+
+impl CancelToken {
+    /// Creates a new token, that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels all the operations guarded by this token (or any of its clones)
+    pub fn cancel(&self) {
+        self.inner.flag.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Resets the token, so it can be used for new operations
+    pub fn reset(&self) {
+        self.inner.flag.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns wheiter the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.flag.load(Ordering::SeqCst)
+    }
+
+    /// Returns an error if the token has been cancelled, used by synchronous loops to check between steps
+    pub fn check(&self) -> Result<(), crate::Error> {
+        if self.is_cancelled() {
+            Err(SyError::safety("The operation has been cancelled!").into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Waits until the token is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+
+            if self.is_cancelled() {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+
+    /// Runs the given future until it finishes or the token is cancelled, in which case an error is returned
+    pub async fn guard<T, F : Future<Output = Result<T, crate::Error>>>(&self, fut : F) -> Result<T, crate::Error> {
+        tokio::select! {
+            biased;
+
+            _ = self.cancelled() => Err(SyError::safety("The operation has been cancelled!").into()),
+            res = fut => res
+        }
+    }
+}
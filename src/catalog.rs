@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+
+use crate::ErrorKind;
+
+// Prompts
+    /// Operator prompt: The tool `{tool}` has to be mounted
+    pub const PROMPT_TOOL_CHANGE : u16 = 1000;
+    /// Operator prompt: The program paused at line `{line}` and waits to be resumed
+    pub const PROMPT_PAUSED : u16 = 1001;
+    /// Operator prompt: The robot has to be homed before continuing
+    pub const PROMPT_HOMING : u16 = 1002;
+//
+
+// Errors
+    /// Error: The robot has to be homed before absolute movements
+    pub const ERR_NOT_HOMED : u16 = 2000;
+    /// Error: The movement requires the masked axis `{axis}`, moving it by `{delta}`
+    pub const ERR_MASKED_AXIS : u16 = 2001;
+    /// Error: The `Phi` values `{phis}` are outside of the limits of the components
+    pub const ERR_INVALID_PHIS : u16 = 2002;
+    /// Error: The emergency stop has been triggered with the reason `{reason}`
+    pub const ERR_ESTOP : u16 = 2003;
+    /// Error: The limit switch `{input}` has triggered
+    pub const ERR_LIMIT_SWITCH : u16 = 2004;
+//
+
+/// A language independent message, identified by a stable code and rendered by a `Catalog`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    /// The code of the message, error codes (see `ErrorKind::code`), specific error codes (`ERR_*`) or prompt codes 
+    /// (`PROMPT_*`)
+    pub code : u16,
+    /// Named parameters interpolated into the templates
+    #[serde(default)]
+    pub params : Vec<(String, String)>
+}
+
+impl Message {
+    /// Creates a new message without parameters
+    pub fn new(code : u16) -> Self {
+        Self { code, params: Vec::new() }
+    }
+
+    /// Adds a named parameter
+    pub fn param<K : Into<String>, V : ToString>(mut self, key : K, value : V) -> Self {
+        self.params.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Returns the value of the parameter with the given name
+    pub fn get(&self, key : &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Templates of the messages in one language, parameters are inserted at `{name}` placeholders
+///
+/// Catalogs are stored as JSON, e.g. `{ "lang": "de", "messages": { "1000": "Werkzeug {tool} einsetzen" } }`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Catalog {
+    /// The language of the catalog (e.g. `en`, `de`)
+    pub lang : String,
+    /// The templates by code
+    pub messages : HashMap<u16, String>
+}
+
+impl Catalog {
+    /// Creates a new empty catalog
+    pub fn new<S : Into<String>>(lang : S) -> Self {
+        Self { lang: lang.into(), messages: HashMap::new() }
+    }
+
+    /// The built-in english catalog, covering the error kinds and prompts of the crate
+    pub fn english() -> Self {
+        Self::new("en")
+            .with(ErrorKind::Kinematics.code(), "Position out of range: {msg}")
+            .with(ErrorKind::Component.code(), "Component failure: {msg}")
+            .with(ErrorKind::Config.code(), "Invalid configuration: {msg}")
+            .with(ErrorKind::Interpreter.code(), "Invalid program: {msg}")
+            .with(ErrorKind::Remote.code(), "Communication failure: {msg}")
+            .with(ErrorKind::Safety.code(), "Safety stop: {msg}")
+            .with(ErrorKind::Access.code(), "Access denied: {msg}")
+            .with(ErrorKind::Other.code(), "{msg}")
+            .with(PROMPT_TOOL_CHANGE, "Please mount the tool {tool}")
+            .with(PROMPT_PAUSED, "The program paused at line {line}, resume to continue")
+            .with(PROMPT_HOMING, "The robot has to be homed before continuing")
+            .with(ERR_NOT_HOMED, "Safety stop: The robot has to be homed before absolute movements")
+            .with(ERR_MASKED_AXIS, "Safety stop: The movement requires the masked axis {axis} (Delta: {delta})")
+            .with(ERR_INVALID_PHIS, "Position out of range: The joint values {phis} exceed the limits of the components")
+            .with(ERR_ESTOP, "Emergency stop: {reason}")
+            .with(ERR_LIMIT_SWITCH, "Safety stop: The limit switch {input} has triggered")
+    }
+
+    /// Loads a catalog out of a JSON, TOML or YAML file, see `config::load_conf()`
+    pub fn load<P : AsRef<Path>>(path : P) -> Result<Self, crate::Error> {
+        crate::config::load_conf(path)
+    }
+
+    /// Adds or replaces the template of a code
+    pub fn with<S : Into<String>>(mut self, code : u16, template : S) -> Self {
+        self.messages.insert(code, template.into());
+        self
+    }
+
+    /// Renders the message, returns `None` if the catalog has no template for its code. Unknown placeholders are
+    /// kept as they are
+    pub fn get(&self, msg : &Message) -> Option<String> {
+        let mut text = self.messages.get(&msg.code)?.clone();
+
+        for (key, value) in &msg.params {
+            text = text.replace(&format!("{{{}}}", key), value);
+        }
+
+        Some(text)
+    }
+
+    /// Renders the message, falling back to the code and parameters if the catalog has no template for it
+    pub fn render(&self, msg : &Message) -> String {
+        self.get(msg).unwrap_or_else(|| {
+            let params : Vec<String> = msg.params.iter().map(|(k, v)| format!("{}: {}", k, v)).collect();
+            format!("[E{}] {}", msg.code, params.join(", "))
+        })
+    }
+
+    /// Renders any error of the crate, see `ErrorKind::of`. Specific messages (see `SyError::detail`) fall back to 
+    /// the template of the error kind if the catalog has none for them
+    pub fn render_error(&self, err : &crate::Error) -> String {
+        match err {
+            crate::Error::Sy(err) => {
+                let msg = err.message();
+                let kind = Message { code: err.code(), params: msg.params.clone() };
+                self.get(&msg).or_else(|| self.get(&kind)).unwrap_or_else(|| self.render(&msg))
+            },
+            err => self.render(&Message::new(err.code()).param("msg", err))
+        }
+    }
+}
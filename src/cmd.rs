@@ -0,0 +1,169 @@
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{Descriptor, Robot, SyError};
+use crate::cancel::CancelToken;
+use crate::events::StateEvent;
+use crate::rcs::Position;
+
+// Submodules
+    mod auth;
+    pub use auth::{AuthSender, AuthTable, ControlLock, Role, Session};
+// 
+
+/// Commands that can be sent to the motion loop of a robot
+#[derive(Clone, Debug)]
+pub enum Command<const C : usize> {
+    /// Relative joint movement, see `Robot::move_j`
+    MoveJ { deltas : [Delta; C], speed_f : Factor },
+    /// Absolute joint movement, see `Robot::move_abs_j`
+    MoveAbsJ { phis : [Phi; C], speed_f : Factor },
+    /// Movement to a position, see `Robot::move_p`
+    MoveP { pos : Position, speed_f : Factor },
+    /// Relative movement of a single axis, stopping at the soft limits, see `Robot::jog_j`
+    Jog { axis : usize, delta : Delta, speed_f : Factor }
+}
+
+/// Reply to a command, errors are converted into `SyError`s to be sendable between tasks (see `SyError::from_error()`)
+pub type Reply<T> = Result<T, SyError>;
+
+enum Priority<const C : usize> {
+    Stop(oneshot::Sender<Reply<()>>),
+    Query(oneshot::Sender<Reply<[Phi; C]>>)
+}
+
+/// Creates a new command channel with the given `buffer` size for motion commands
+/// 
+/// Stopping the robot cancels the `cancel` token given, pass the token of the robot (see `Robot::cancel_token()`) so
+/// the movements started by other tasks (e.g. programs) are cancelled as well
+pub fn channel<const C : usize>(buffer : usize, cancel : CancelToken) -> (CommandSender<C>, CommandReceiver<C>) {
+    let (tx, rx) = mpsc::channel(buffer);
+    let (prio_tx, prio_rx) = mpsc::unbounded_channel();
+
+    ( CommandSender { tx, prio_tx, cancel: cancel.clone() }, CommandReceiver { rx, prio_rx, cancel } )
+}
+
+/// The producer side of a command channel, can be cloned and shared between servers, interpreters and remotes
+#[derive(Clone)]
+pub struct CommandSender<const C : usize> {
+    tx : mpsc::Sender<(Command<C>, oneshot::Sender<Reply<()>>)>,
+    prio_tx : mpsc::UnboundedSender<Priority<C>>,
+    cancel : CancelToken
+}
+
+impl<const C : usize> CommandSender<C> {
+    /// Sends a command to the motion loop and waits until it has been executed
+    pub async fn send(&self, cmd : Command<C>) -> Result<(), crate::Error> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx.send((cmd, reply_tx)).await.map_err(|_| SyError::remote("The motion loop has been closed!"))?;
+        Ok(reply_rx.await.map_err(|_| SyError::remote("The command has been dropped by the motion loop!"))??)
+    }
+
+    /// Stops the robot, the current motion is cancelled and all the commands queued are discarded. Handled with priority
+    pub async fn stop(&self) -> Result<(), crate::Error> {
+        self.cancel.cancel();
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.prio_tx.send(Priority::Stop(reply_tx)).map_err(|_| SyError::remote("The motion loop has been closed!"))?;
+        Ok(reply_rx.await.map_err(|_| SyError::remote("The command has been dropped by the motion loop!"))??)
+    }
+
+    /// Queries the current `Phi` values of the robot. Handled with priority
+    pub async fn query_phis(&self) -> Result<[Phi; C], crate::Error> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.prio_tx.send(Priority::Query(reply_tx)).map_err(|_| SyError::remote("The motion loop has been closed!"))?;
+        Ok(reply_rx.await.map_err(|_| SyError::remote("The command has been dropped by the motion loop!"))??)
+    }
+}
+
+/// The consumer side of a command channel, owned by the motion loop
+pub struct CommandReceiver<const C : usize> {
+    rx : mpsc::Receiver<(Command<C>, oneshot::Sender<Reply<()>>)>,
+    prio_rx : mpsc::UnboundedReceiver<Priority<C>>,
+    cancel : CancelToken
+}
+
+impl<const C : usize> CommandReceiver<C> {
+    async fn execute<R, G, T, D>(rob : &mut R, desc : &mut D, cmd : Command<C>) -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        match cmd {
+            Command::MoveJ { deltas, speed_f } => rob.move_j(deltas, speed_f).await,
+            Command::MoveAbsJ { phis, speed_f } => rob.move_abs_j(phis, speed_f).await,
+            Command::MoveP { pos, speed_f } => rob.move_p(desc, pos, speed_f).await,
+            Command::Jog { axis, delta, speed_f } => rob.jog_j(axis, delta, speed_f).await
+        }
+    }
+
+    fn handle_priority<R, G, T>(&mut self, rob : &mut R, prio : Priority<C>) 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        match prio {
+            Priority::Stop(reply) => {
+                // Discard all the commands queued
+                while let Ok((_, cmd_reply)) = self.rx.try_recv() {
+                    cmd_reply.send(Err(SyError::safety("The robot has been stopped!"))).ok();
+                }
+
+                self.cancel.reset();
+
+                reply.send(Ok(())).ok();
+            },
+            Priority::Query(reply) => {
+                reply.send(Ok(rob.phis())).ok();
+            }
+        }
+    }
+
+    /// Runs the motion loop, consuming the commands until all the senders have been dropped
+    /// 
+    /// Priority commands (stop and query) are always handled before the queued motion commands
+    pub async fn run<R, G, T, D>(mut self, rob : &mut R, desc : &mut D) 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        loop {
+            tokio::select! {
+                biased;
+
+                Some(prio) = self.prio_rx.recv() => {
+                    self.handle_priority(rob, prio);
+                },
+                next = self.rx.recv() => {
+                    let Some((cmd, reply)) = next else {
+                        return;
+                    };
+
+                    let mut res = self.cancel.guard(Self::execute(rob, desc, cmd)).await
+                        .map_err(|err| SyError::from_error(&err));
+
+                    if res.is_err() && self.cancel.is_cancelled() {
+                        // The drives are dropped wherever they have been cancelled, the components are halted and the 
+                        // positions are re-synced before the next command is executed
+                        if let Err(sync_err) = rob.halt().and_then(|_| rob.update()) {
+                            res = res.map_err(|err| SyError::safety(format!("{} (Halting failed: {})", err, sync_err)));
+                        }
+                    }
+
+                    if let Err(err) = &res {
+                        rob.events().publish(StateEvent::Error(err.to_string()));
+                    }
+
+                    reply.send(res).ok();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use syunit::*;
+
+use crate::SyError;
+use crate::cmd::{Command, CommandSender};
+
+/// The role of a client, defines the commands it is allowed to send
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Telemetry and queries only
+    ReadOnly,
+    /// Motion commands are allowed while holding the control lock
+    Operator
+}
+
+/// An authenticated client
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Session {
+    /// Unique id of the session
+    pub id : u64,
+    /// The role of the client
+    pub role : Role
+}
+
+/// Table of the access tokens accepted, mapping each token to a role
+#[derive(Default)]
+pub struct AuthTable {
+    tokens : HashMap<String, Role>,
+    next_id : AtomicU64
+}
+
+impl AuthTable {
+    /// Creates a new empty table, no client can authenticate
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a token with the given role
+    pub fn token<S : Into<String>>(mut self, token : S, role : Role) -> Self {
+        self.tokens.insert(token.into(), role);
+        self
+    }
+
+    /// Removes a token, sessions created with it stay valid until dropped
+    pub fn revoke(&mut self, token : &str) {
+        self.tokens.remove(token);
+    }
+
+    /// Authenticates a client by its token, returning a new session
+    pub fn authenticate(&self, token : &str) -> Result<Session, crate::Error> {
+        let role = *self.tokens.get(token).ok_or_else(|| SyError::access("Invalid access token!"))?;
+
+        Ok(Session {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            role
+        })
+    }
+}
+
+/// Exclusive control lock, only the session holding the lock is allowed to command motion
+#[derive(Clone, Default)]
+pub struct ControlLock {
+    holder : Arc<Mutex<Option<u64>>>
+}
+
+impl ControlLock {
+    /// Creates a new free lock
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Acquires the lock for the given session, fails if the session is read-only or another session holds the lock
+    pub fn acquire(&self, session : &Session) -> Result<(), crate::Error> {
+        if session.role != Role::Operator {
+            return Err(SyError::access("Read-only clients cannot acquire control!").into());
+        }
+
+        let mut holder = self.holder.lock().unwrap();
+
+        match *holder {
+            Some(id) if id != session.id => Err(SyError::access(format!("Control is held by session {}!", id)).into()),
+            _ => {
+                *holder = Some(session.id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Releases the lock if it is held by the given session
+    pub fn release(&self, session : &Session) {
+        let mut holder = self.holder.lock().unwrap();
+
+        if *holder == Some(session.id) {
+            *holder = None;
+        }
+    }
+
+    /// Returns the id of the session holding the lock
+    pub fn holder(&self) -> Option<u64> {
+        *self.holder.lock().unwrap()
+    }
+
+    /// Returns wheiter the given session holds the lock
+    pub fn is_held_by(&self, session : &Session) -> bool {
+        self.holder() == Some(session.id)
+    }
+}
+
+/// A `CommandSender` bound to a client session, checking the permissions of the client for every command. Servers
+/// should create one for each connection after authentication
+pub struct AuthSender<const C : usize> {
+    sender : CommandSender<C>,
+    session : Session,
+    lock : ControlLock
+}
+
+impl<const C : usize> AuthSender<C> {
+    /// Creates a new sender for the given session
+    pub fn new(sender : CommandSender<C>, session : Session, lock : ControlLock) -> Self {
+        Self { sender, session, lock }
+    }
+
+    /// Returns the session of the sender
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Acquires the control lock, see `ControlLock::acquire`
+    pub fn acquire(&self) -> Result<(), crate::Error> {
+        self.lock.acquire(&self.session)
+    }
+
+    /// Releases the control lock
+    pub fn release(&self) {
+        self.lock.release(&self.session)
+    }
+
+    /// Sends a motion command, requires the operator role and the control lock
+    pub async fn send(&self, cmd : Command<C>) -> Result<(), crate::Error> {
+        if !self.lock.is_held_by(&self.session) {
+            return Err(SyError::access("Motion commands require the control lock!").into());
+        }
+
+        self.sender.send(cmd).await
+    }
+
+    /// Stops the robot, allowed for every operator regardless of the control lock
+    pub async fn stop(&self) -> Result<(), crate::Error> {
+        if self.session.role != Role::Operator {
+            return Err(SyError::access("Read-only clients cannot stop the robot!").into());
+        }
+
+        self.sender.stop().await
+    }
+
+    /// Queries the current `Phi` values of the robot, allowed for every client
+    pub async fn query_phis(&self) -> Result<[Phi; C], crate::Error> {
+        self.sender.query_phis().await
+    }
+}
+
+impl<const C : usize> Drop for AuthSender<C> {
+    fn drop(&mut self) {
+        // Disconnecting clients must not block the robot
+        self.lock.release(&self.session);
+    }
+}
@@ -1,41 +1,247 @@
+use serde::{Serialize, Deserialize};
 use syunit::*;
 
+use crate::SyError;
+
+// Submodules
+    mod export;
+    pub use export::{ModelSegment, ModelTool, RobotModel};
+
+    mod format;
+    pub use format::{ConfFormat, from_value, load_conf};
+
+    mod scaffold;
+    pub use scaffold::{CompKind, CompScaffold, Scaffold};
+
+    /// Import of robot models from URDF files
+    #[cfg(feature = "urdf")]
+    pub mod urdf;
+// 
+
+// Angle units
+    /// The unit of angles given in configurations and interpreter arguments, all angles are converted to radians on load
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum AngleUnit {
+        /// Radians, the internal unit of `Phi` and `Gamma` values
+        #[default]
+        Radians,
+        /// Degrees
+        Degrees
+    }
+
+    impl AngleUnit {
+        /// Converts the given value in this unit into radians
+        pub fn to_rad(&self, value : f32) -> f32 {
+            match self {
+                Self::Radians => value,
+                Self::Degrees => value.to_radians()
+            }
+        }
+
+        /// Converts the given value in radians into this unit
+        pub fn from_rad(&self, value : f32) -> f32 {
+            match self {
+                Self::Radians => value,
+                Self::Degrees => value.to_degrees()
+            }
+        }
+
+        /// Parses an angle argument (e.g. the `A` word of a GCode line) given in this unit, returning radians
+        pub fn parse(&self, arg : &str) -> Result<f32, crate::Error> {
+            let value : f32 = arg.trim().parse()
+                .map_err(|_| SyError::config(format!("Invalid angle argument! (Argument: '{}')", arg)))?;
+            Ok(self.to_rad(value))
+        }
+    }
+// 
+
 // Angle Configuration
     /// Angle configuration (phi to gamma conversion), allowing 
     /// - `offset`: The `Phi` value has an offset compared to the `Gamma` value
     /// - `counter`: The `Phi` value is working as a counter angle to `Gamma` (The `Gamma` value will be negated)
-    #[derive(Debug, Default, Clone, Copy)]
+    /// - `ratio`: Gear ratio between the `Gamma` and the `Phi` value (`phi = gamma * ratio`), must not be zero
+    #[derive(Debug, Clone, Copy)]
     pub struct AngleConfig {
         /// Offset of the value
         pub offset : Delta,
         /// Wheiter or not the angle is a counterpart (negative addition)
-        pub counter : bool
+        pub counter : bool,
+        /// Gear ratio of the value
+        pub ratio : f32
+    }
+
+    impl Default for AngleConfig {
+        fn default() -> Self {
+            Self::EMPTY
+        }
+    }
+
+    /// Raw `AngleConfig` as stored in configuration files, the `offset` is given in `unit`
+    #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+    struct AngleConfigDes {
+        #[serde(default)]
+        offset : f32,
+        #[serde(default)]
+        counter : bool,
+        #[serde(default = "default_ratio")]
+        ratio : f32,
+        #[serde(default)]
+        unit : AngleUnit
+    }
+
+    fn default_ratio() -> f32 {
+        1.0
+    }
+
+    impl Serialize for AngleConfig {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer {
+            AngleConfigDes { offset: self.offset.0, counter: self.counter, ratio: self.ratio, unit: AngleUnit::Radians }.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for AngleConfig {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de> {
+            let des = AngleConfigDes::deserialize(deserializer)?;
+
+            if des.ratio == 0.0 {
+                return Err(serde::de::Error::custom("The ratio of an angle configuration must not be zero!"));
+            }
+
+            Ok(Self { offset: Delta(des.unit.to_rad(des.offset)), counter: des.counter, ratio: des.ratio })
+        }
     }
 
     impl AngleConfig {
         /// An empty `AngleConfig`, with all values set to `0`/´false`
-        pub const EMPTY : Self = Self { offset: Delta::ZERO, counter: false };
+        pub const EMPTY : Self = Self { offset: Delta::ZERO, counter: false, ratio: 1.0 };
+
+        /// Creates a new angle configuration without a gear ratio
+        pub fn new(offset : Delta, counter : bool) -> Self {
+            Self { offset, counter, ratio: 1.0 }
+        }
 
         /// Convert the given gamma angle to a phi angle
         pub fn phi_from_gamma(&self, gamma : Gamma) -> Phi {
-            (if self.counter { 
-                -gamma
+            Phi(if self.counter { 
+                -gamma.0
             } else { 
-                gamma
-            } + self.offset).force_to_phi()
+                gamma.0
+            } * self.ratio + self.offset.0)
         }
         
         /// Convert the given phi angle to a gamma angle
         pub fn gamma_from_phi(&self, phi : Phi) -> Gamma {
-            if self.counter { 
-                -phi.force_to_gamma() + self.offset
+            Gamma(if self.counter { 
+                self.offset.0 - phi.0
             } else { 
-                phi.force_to_gamma() - self.offset
-            }
+                phi.0 - self.offset.0
+            } / self.ratio)
         }
     }
 //
 
+// Axis coupling
+    /// Mechanical coupling between two axes (e.g. belt-differential wrists), the `target` axis has to move by 
+    /// `factor * phi` of the `source` axis additionally to keep its own `Phi` value
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    pub struct AxisCoupling {
+        /// Index of the axis causing the coupled movement
+        pub source : usize,
+        /// Index of the axis being moved by the coupling
+        pub target : usize,
+        /// Factor of the coupling
+        pub factor : f32
+    }
+
+    impl AxisCoupling {
+        /// Checks the couplings for valid indices and for being ordered, so the source of a coupling is never the 
+        /// target of a later coupling
+        pub fn validate(couplings : &[AxisCoupling], axes : usize) -> Result<(), crate::Error> {
+            for (i, coupling) in couplings.iter().enumerate() {
+                if (coupling.source >= axes) | (coupling.target >= axes) | (coupling.source == coupling.target) {
+                    return Err(SyError::config(format!("Invalid axis coupling {}! (Source: {}, Target: {}, Axes: {})", 
+                        i, coupling.source, coupling.target, axes)).into());
+                }
+
+                if couplings[i + 1 ..].iter().any(|c| c.target == coupling.source) {
+                    return Err(SyError::config(
+                        format!("The source of axis coupling {} is coupled by a later coupling, reorder the couplings!", i)).into());
+                }
+            }
+
+            Ok(())
+        }
+    }
+// 
+
+// Kinematic configurations
+    /// Position of the elbow of an arm
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum Elbow {
+        #[default]
+        Up,
+        Down
+    }
+
+    /// Orientation of the wrist of an arm, flipped wrists reach the same pose with the wrist turned by half a rotation
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum Wrist {
+        #[default]
+        NoFlip,
+        Flip
+    }
+
+    /// Side of the base an arm reaches the target from
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum Base {
+        #[default]
+        Front,
+        Back
+    }
+
+    /// The configuration of an IK solution, as arms can reach most positions in multiple ways. Descriptors that do not
+    /// distinguish a part of the configuration use the default value for it
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct KinConf {
+        /// Position of the elbow
+        pub elbow : Elbow,
+        /// Orientation of the wrist
+        pub wrist : Wrist,
+        /// Side of the base
+        pub base : Base
+    }
+
+    /// Selection of one of multiple IK solutions
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum ConfSelect {
+        /// The solution with the minimal joint travel from the current pose
+        #[default]
+        MinTravel,
+        /// The solution with the given configuration, falls back to the minimal joint travel if it is unavailable
+        Prefer(KinConf)
+    }
+
+    impl ConfSelect {
+        /// Selects one of the solutions, `current` are the `Phi` values of the current pose
+        pub fn select<const C : usize>(&self, solutions : &[(KinConf, [Phi; C])], current : &[Phi; C]) -> Option<(KinConf, [Phi; C])> {
+            if let Self::Prefer(conf) = self {
+                if let Some(sol) = solutions.iter().find(|(c, _)| c == conf) {
+                    return Some(*sol);
+                }
+            }
+
+            let travel = |phis : &[Phi; C]| phis.iter().zip(current).map(|(p, c)| (p.0 - c.0).abs()).sum::<f32>();
+
+            solutions.iter().copied().min_by(|(_, a), (_, b)| travel(a).total_cmp(&travel(b)))
+        }
+    }
+// 
+
 // AxisConf
     /// Defines the way a robot should act when there is more than one possible way of accessing an object
     pub trait AxisConfig {
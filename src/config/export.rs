@@ -0,0 +1,208 @@
+use core::fmt::Write;
+
+use glam::{EulerRot, Quat, Vec3};
+use serde::{Serialize, Deserialize};
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Robot};
+use crate::desc::{Kinematic, Movement, Rot};
+use crate::rcs::Point;
+
+/// Factor converting the millimeters used by the library into the lengths of URDF files (meters)
+const MM_TO_M : f32 = 0.001;
+
+/// A segment of an exported `RobotModel`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelSegment {
+    /// Wheiter the segment rotates (`true`) or moves linear (`false`)
+    pub rotary : bool,
+    /// Position of the segment relative to the previous one at `Phi` zero
+    pub origin : [f32; 3],
+    /// Orientation of the segment relative to the previous one at `Phi` zero, as roll, pitch and yaw angles
+    pub rpy : [f32; 3],
+    /// Axis of the movement, scaled with the distance moved per `Phi` for linear segments
+    pub axis : [f32; 3],
+    /// The current `Phi` value of the segment
+    pub phi : f32,
+    /// Minimum `Phi` value, the tighter one of the component and soft limits of the robot
+    pub min : Option<f32>,
+    /// Maximum `Phi` value, the tighter one of the component and soft limits of the robot
+    pub max : Option<f32>,
+    /// Maximum `Phi` velocity, taken from the maximum velocities of the components (see `Robot::omega_max()`)
+    #[serde(default)]
+    pub vel : Option<f32>
+}
+
+/// The tool mounted when a `RobotModel` has been exported
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelTool {
+    /// Id of the tool
+    pub id : usize,
+    /// Vector of the tool
+    pub vec : [f32; 3]
+}
+
+/// Snapshot of the kinematic model a robot is actually running with, exported for visualization tools and other stacks
+/// either as JSON (`export_json()`) or as URDF (`export_urdf()`)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RobotModel {
+    /// Name of the robot, taken from its identity
+    pub name : String,
+    /// The segments of the kinematic
+    pub segments : Vec<ModelSegment>,
+    /// Position of the TCP relative to the last segment
+    pub tcp : [f32; 3],
+    /// The tool currently mounted
+    pub tool : Option<ModelTool>
+}
+
+impl RobotModel {
+    /// Name of robots without identity
+    pub const DEFAULT_NAME : &'static str = "sybot";
+
+    /// Takes a snapshot of the model of the given robot and descriptor
+    pub fn from_robot<R, G, T, D, const C : usize>(rob : &R, desc : &D) -> Self
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        let limits = rob.vars().soft_limits.as_ref();
+        let comp_limits = rob.limits();
+        let omega_max = rob.omega_max();
+        let gammas = rob.gammas();
+
+        // Converts a `Gamma` value of the component `i` into its `Phi` value, the other components at their current position
+        let phi_of = |i : usize, gamma : f32| {
+            let mut gammas = gammas;
+            gammas[i] = Gamma(gamma);
+            rob.phis_from_gammas(gammas)[i].0
+        };
+
+        let segments = desc.kinematic().segments().iter().enumerate().map(|(i, seg)| {
+            let point_0 = seg.point_0().borrow();
+            let (yaw, pitch, roll) = Quat::from_mat3(point_0.ori()).to_euler(EulerRot::ZYX);
+
+            let (rotary, axis) = match seg.movement() {
+                Movement::Rotation(Rot::X) => (true, Vec3::X),
+                Movement::Rotation(Rot::Y) => (true, Vec3::Y),
+                Movement::Rotation(Rot::Z) => (true, Vec3::Z),
+                Movement::Linear(vec) => (false, *vec)
+            };
+
+            let limit = limits.map(|l| l.axes[i]);
+
+            // Mirrored components swap their limits
+            let (mut min, mut max) = match comp_limits {
+                Some((comp_min, comp_max)) => {
+                    let a = comp_min[i].map(|g| phi_of(i, g.0));
+                    let b = comp_max[i].map(|g| phi_of(i, g.0));
+
+                    match (a, b) {
+                        (Some(a), Some(b)) => (Some(a.min(b)), Some(a.max(b))),
+                        _ if phi_of(i, 1.0) >= phi_of(i, 0.0) => (a, b),
+                        _ => (b, a)
+                    }
+                },
+                None => (None, None)
+            };
+
+            if let Some(soft) = limit.and_then(|l| l.min) {
+                min = Some(min.map_or(soft.0, |m| m.max(soft.0)));
+            }
+
+            if let Some(soft) = limit.and_then(|l| l.max) {
+                max = Some(max.map_or(soft.0, |m| m.min(soft.0)));
+            }
+
+            ModelSegment {
+                rotary,
+                origin: point_0.pos().to_array(),
+                rpy: [ roll, pitch, yaw ],
+                axis: axis.to_array(),
+                phi: seg.phi().0,
+                min,
+                max,
+                vel: omega_max.map(|omega| (phi_of(i, gammas[i].0 + omega[i].0) - phi_of(i, gammas[i].0)).abs())
+            }
+        }).collect();
+
+        Self {
+            name: rob.identity().map(|id| id.name.clone()).unwrap_or_else(|| Self::DEFAULT_NAME.to_owned()),
+            segments,
+            tcp: desc.tcp().pos().to_array(),
+            tool: rob.get_tool_id().zip(rob.get_tool()).map(|(id, tool)| ModelTool { id, vec: tool.vec().to_array() })
+        }
+    }
+
+    /// Serializes the model as JSON
+    pub fn export_json(&self) -> Result<String, crate::Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Serializes the model as URDF, every segment becomes a revolute, continuous or prismatic joint with a link named
+    /// `link_<n>`, the TCP is added as fixed joint. Lengths are converted into meters, the limits and velocities of 
+    /// linear segments are scaled with the length of their axis. Limits and velocities unknown are not written
+    pub fn export_urdf(&self) -> String {
+        let mut urdf = String::new();
+        let fmt = |v : [f32; 3], scale : f32| format!("{} {} {}", v[0] * scale, v[1] * scale, v[2] * scale);
+
+        // Writing into a `String` cannot fail
+        let _ = writeln!(urdf, "<?xml version=\"1.0\"?>");
+        let _ = writeln!(urdf, "<robot name=\"{}\">", escape_xml(&self.name));
+        let _ = writeln!(urdf, "  <link name=\"base\"/>");
+
+        let mut parent = "base".to_owned();
+
+        for (i, seg) in self.segments.iter().enumerate() {
+            let link = format!("link_{}", i);
+            let axis = Vec3::from(seg.axis);
+
+            // Linear `Phi` values are multiples of the axis vector, rotary segments without limits are continuous
+            let (kind, scale) = match (seg.rotary, seg.min.or(seg.max)) {
+                (true, None) => ("continuous", 1.0),
+                (true, Some(_)) => ("revolute", 1.0),
+                (false, _) => ("prismatic", axis.length() * MM_TO_M)
+            };
+
+            let _ = writeln!(urdf, "  <link name=\"{}\"/>", link);
+            let _ = writeln!(urdf, "  <joint name=\"joint_{}\" type=\"{}\">", i, kind);
+            let _ = writeln!(urdf, "    <parent link=\"{}\"/>", parent);
+            let _ = writeln!(urdf, "    <child link=\"{}\"/>", link);
+            let _ = writeln!(urdf, "    <origin xyz=\"{}\" rpy=\"{}\"/>", fmt(seg.origin, MM_TO_M), fmt(seg.rpy, 1.0));
+            let _ = writeln!(urdf, "    <axis xyz=\"{}\"/>", fmt(axis.normalize_or_zero().to_array(), 1.0));
+
+            if (kind != "continuous") | seg.vel.is_some() {
+                let attr = |name : &str, value : Option<f32>| value.map(|v| format!(" {}=\"{}\"", name, v * scale)).unwrap_or_default();
+
+                let _ = writeln!(urdf, "    <limit{}{} effort=\"0\"{}/>", attr("lower", seg.min), attr("upper", seg.max), 
+                    attr("velocity", seg.vel));
+            }
+
+            let _ = writeln!(urdf, "  </joint>");
+
+            parent = link;
+        }
+
+        let _ = writeln!(urdf, "  <link name=\"tcp\"/>");
+        let _ = writeln!(urdf, "  <joint name=\"tcp_joint\" type=\"fixed\">");
+        let _ = writeln!(urdf, "    <parent link=\"{}\"/>", parent);
+        let _ = writeln!(urdf, "    <child link=\"tcp\"/>");
+        let _ = writeln!(urdf, "    <origin xyz=\"{}\"/>", fmt(self.tcp, MM_TO_M));
+        let _ = writeln!(urdf, "  </joint>");
+        let _ = writeln!(urdf, "</robot>");
+
+        urdf
+    }
+}
+
+/// Escapes the characters of a text that are not allowed inside XML attributes
+fn escape_xml(text : &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
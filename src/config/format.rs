@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::SyError;
+
+/// The file formats configurations can be written in, all formats are interchangeable
+///
+/// JSON is always available, TOML and YAML require the `toml` and `yaml` features
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConfFormat {
+    /// JSON (`.json`)
+    #[default]
+    Json,
+    /// TOML (`.toml`)
+    Toml,
+    /// YAML (`.yaml` or `.yml`)
+    Yaml
+}
+
+impl ConfFormat {
+    /// Selects the format by the extension of the path, e.g. `arm.conf.toml`
+    pub fn from_path<P : AsRef<Path>>(path : P) -> Result<Self, crate::Error> {
+        let path = path.as_ref();
+
+        match path.extension().and_then(|ext| ext.to_str()).map(str::to_lowercase).as_deref() {
+            Some("json") => Ok(Self::Json),
+            Some("toml") => Ok(Self::Toml),
+            Some("yaml" | "yml") => Ok(Self::Yaml),
+            _ => Err(SyError::config(format!("Unknown configuration format of file '{}'! (Supported: json, toml, yaml)",
+                path.display())).into())
+        }
+    }
+
+    /// Parses the content into a generic value, syntax errors contain the position given by the parser
+    pub fn parse_value(&self, content : &str) -> Result<Value, crate::Error> {
+        let value = match self {
+            Self::Json => serde_json::from_str(content).map_err(|err| err.to_string()),
+
+            #[cfg(feature = "toml")]
+            Self::Toml => toml::from_str(content).map_err(|err| err.to_string()),
+            #[cfg(not(feature = "toml"))]
+            Self::Toml => Err("TOML configurations require the `toml` feature".to_owned()),
+
+            #[cfg(feature = "yaml")]
+            Self::Yaml => serde_yaml::from_str(content).map_err(|err| err.to_string()),
+            #[cfg(not(feature = "yaml"))]
+            Self::Yaml => Err("YAML configurations require the `yaml` feature".to_owned())
+        };
+
+        value.map_err(|err| SyError::config(format!("Invalid {:?} configuration! ({})", self, err)).into())
+    }
+
+    /// Parses the content into the given type, errors reference the path of the offending key
+    /// (e.g. `comps[2].limit.vel`)
+    pub fn parse<T : DeserializeOwned>(&self, content : &str) -> Result<T, crate::Error> {
+        from_value(self.parse_value(content)?)
+    }
+}
+
+/// Converts a generic value into the given type, errors reference the path of the offending key
+pub fn from_value<T : DeserializeOwned>(value : Value) -> Result<T, crate::Error> {
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        let path = err.path().to_string();
+        SyError::config(format!("Invalid value at '{}': {}", path, err.into_inner())).into()
+    })
+}
+
+/// Loads a configuration file, the format is selected by the extension of the path (see `ConfFormat::from_path()`)
+pub fn load_conf<T : DeserializeOwned, P : AsRef<Path>>(path : P) -> Result<T, crate::Error> {
+    let format = ConfFormat::from_path(&path)?;
+    format.parse(&std::fs::read_to_string(path)?)
+}
@@ -0,0 +1,200 @@
+use std::path::Path;
+
+use glam::Vec3;
+use serde_json::{json, Value};
+
+use crate::robs::BackendConfig;
+
+/// Version of the configuration format generated
+pub const CONF_VERSION : &str = "0.0.1";
+
+/// The type of component used for an axis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompKind {
+    /// A rotary joint driven by a gear (`syact::act::gear_bearing::GearJoint`)
+    GearJoint,
+    /// A linear axis driven by a spindle or belt (`syact::act::LinearAxis`)
+    LinearAxis
+}
+
+impl CompKind {
+    /// Returns the type name of the component, as used in the `type_name` field
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::GearJoint => "syact::act::gear_bearing::GearJoint",
+            Self::LinearAxis => "syact::act::LinearAxis"
+        }
+    }
+
+    /// Returns the default movement axis of the component for the given axis index
+    pub fn default_axis(&self, index : usize) -> Vec3 {
+        match (self, index) {
+            (Self::GearJoint, 0) => Vec3::Z,
+            (Self::GearJoint, _) => Vec3::X,
+            (Self::LinearAxis, 0) => Vec3::X,
+            (Self::LinearAxis, 1) => Vec3::Y,
+            (Self::LinearAxis, _) => Vec3::Z
+        }
+    }
+}
+
+/// Scaffolding of a single component
+#[derive(Clone, Debug)]
+pub struct CompScaffold {
+    /// Name of the component
+    pub name : String,
+    /// Type of the component
+    pub kind : CompKind,
+    /// Dimension (vector to the next segment) of the component
+    pub dim : Vec3,
+    /// Movement axis of the component
+    pub axis : Vec3,
+    /// Mass moved by the component (kilograms)
+    pub mass : f32,
+    /// Maximum velocity of the component
+    pub vel : f32,
+    /// Minimum limit of the component
+    pub min : Option<f32>,
+    /// Maximum limit of the component
+    pub max : Option<f32>,
+    /// The backend driving the component
+    pub backend : BackendConfig
+}
+
+/// A programmatic package scaffolder, emitting a valid configuration with defaults for each component
+/// 
+/// ```rust
+/// use sybot::config::{Scaffold, CompKind};
+/// 
+/// let scaffold = Scaffold::new("MyArm", &[ CompKind::GearJoint, CompKind::GearJoint, CompKind::GearJoint ])
+///     .dims(&[ [ 0.0, 0.0, 15.0 ], [ 0.0, 285.0, 0.0 ], [ 0.0, 285.0, 0.0 ] ]);
+/// 
+/// assert_eq!(scaffold.to_json()["comps"].as_array().unwrap().len(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scaffold {
+    /// Name of the robot
+    pub name : String,
+    /// Author of the package
+    pub author : String,
+    /// Anchor of the robot
+    pub anchor : Vec3,
+    /// The components of the robot
+    pub comps : Vec<CompScaffold>
+}
+
+impl Scaffold {
+    /// Creates a new scaffold with the given name and one component of each given kind
+    pub fn new<S : Into<String>>(name : S, kinds : &[CompKind]) -> Self {
+        Self {
+            name: name.into(),
+            author: String::new(),
+            anchor: Vec3::ZERO,
+            comps: kinds.iter().enumerate().map(|(i, kind)| CompScaffold {
+                name: format!("Axis_{}", i),
+                kind: *kind,
+                dim: Vec3::ZERO,
+                axis: kind.default_axis(i),
+                mass: 0.0,
+                vel: 1.0,
+                min: None,
+                max: None,
+                backend: BackendConfig::default()
+            }).collect()
+        }
+    }
+
+    // Builder
+        /// Sets the author of the package
+        pub fn author<S : Into<String>>(mut self, author : S) -> Self {
+            self.author = author.into();
+            self
+        }
+
+        /// Sets the anchor of the robot
+        pub fn anchor(mut self, anchor : [f32; 3]) -> Self {
+            self.anchor = Vec3::from(anchor);
+            self
+        }
+
+        /// Sets the dimensions of the components, additional dimensions are ignored
+        pub fn dims(mut self, dims : &[[f32; 3]]) -> Self {
+            for (comp, dim) in self.comps.iter_mut().zip(dims) {
+                comp.dim = Vec3::from(*dim);
+            }
+            self
+        }
+    //
+
+    /// Returns the configuration as JSON-Object
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "conf_version": CONF_VERSION,
+            "author": self.author,
+
+            "anchor": self.anchor.to_array(),
+            "dims": self.comps.iter().map(|c| c.dim.to_array()).collect::<Vec<_>>(),
+            "axes": self.comps.iter().map(|c| c.axis.to_array()).collect::<Vec<_>>(),
+
+            "comps": self.comps.iter().map(|c| json!({
+                "name": c.name,
+                "type_name": c.kind.type_name(),
+                "obj": {
+                    "device": {
+                        "consts": "GEN",
+                        "pin_dir": null,
+                        "pin_step": null
+                    },
+                    "ratio": 1.0
+                },
+                "sim": {
+                    "mass": c.mass,
+                    "fric": 0.0
+                },
+                "meas": null,
+                "limit": {
+                    "vel": c.vel,
+                    "min": c.min,
+                    "max": c.max
+                },
+                "backend": c.backend
+            })).collect::<Vec<_>>()
+        })
+    }
+
+    /// Returns a markdown documentation of the generated configuration, explaining each field that has to be filled in
+    pub fn to_doc(&self) -> String {
+        let mut doc = format!("# {} - Configuration\n\nGenerated package, fill in the fields below in `{}.conf.json`\n\n", self.name, self.name);
+
+        doc.push_str("- `anchor`: Position of the robots base in the world\n");
+        doc.push_str("- `dims`: Vector from each component to the next one\n");
+        doc.push_str("- `axes`: Movement axis of each component\n");
+        doc.push_str("- `comps`: The components of the robot\n");
+        doc.push_str("  - `obj.device.consts`: Stepper motor constants name, e.g. `MOT_17HE15_1504S`\n");
+        doc.push_str("  - `obj.device.pin_dir` / `obj.device.pin_step`: GPIO pins of the driver (required)\n");
+        doc.push_str("  - `obj.ratio`: Gear or spindle ratio of the component\n");
+        doc.push_str("  - `sim`: Simulated mass and friction\n");
+        doc.push_str("  - `meas`: Measurement switch (`pin`, `set_val`, `dist`), `null` if none\n");
+        doc.push_str("  - `limit`: Maximum velocity and the optional `min` / `max` limits\n");
+        doc.push_str("  - `backend`: `kind` (`gpio`, `step_stream` or `position`), `port` and `channel` of external controllers\n\n");
+
+        doc.push_str("## Components\n\n");
+        for comp in &self.comps {
+            doc.push_str(&format!("- `{}`: {}\n", comp.name, comp.kind.type_name()));
+        }
+
+        doc
+    }
+
+    /// Writes the package into the given directory (`<name>.conf.json` and `<name>.conf.md`)
+    pub fn write<P : AsRef<Path>>(&self, dir : P) -> Result<(), crate::Error> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        std::fs::write(dir.join(format!("{}.conf.json", self.name)), serde_json::to_string_pretty(&self.to_json())?)?;
+        std::fs::write(dir.join(format!("{}.conf.md", self.name)), self.to_doc())?;
+
+        Ok(())
+    }
+}
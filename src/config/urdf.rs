@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use glam::{EulerRot, Mat3, Vec3};
+use roxmltree::{Document, Node};
+
+use crate::SyError;
+use crate::config::{CompKind, CompScaffold, Scaffold};
+
+/// Factor converting the lengths of URDF files (meters) into the millimeters used by the library
+pub const URDF_LENGTH_SCALE : f32 = 1000.0;
+
+#[derive(Clone, Debug)]
+struct UrdfJoint {
+    name : String,
+    kind : Option<CompKind>,
+    parent : String,
+    child : String,
+    xyz : Vec3,
+    rot : Mat3,
+    axis : Vec3,
+    lower : Option<f32>,
+    upper : Option<f32>,
+    vel : Option<f32>
+}
+
+fn child<'a, 'i>(node : &Node<'a, 'i>, tag : &str) -> Option<Node<'a, 'i>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+fn req_attr<'a>(node : &Node<'a, '_>, attr : &str) -> Result<&'a str, crate::Error> {
+    node.attribute(attr).ok_or_else(|| SyError::config(format!("The URDF element '{}' is missing the attribute '{}'!",
+        node.tag_name().name(), attr)).into())
+}
+
+fn parse_f32(value : &str) -> Result<f32, crate::Error> {
+    value.trim().parse::<f32>().map_err(|_| SyError::config(format!("Invalid number '{}' in URDF!", value)).into())
+}
+
+fn parse_vec3(value : &str) -> Result<Vec3, crate::Error> {
+    let values = value.split_whitespace().map(parse_f32).collect::<Result<Vec<f32>, _>>()?;
+
+    if values.len() != 3 {
+        return Err(SyError::config(format!("Expected three values in URDF vector '{}'!", value)).into());
+    }
+
+    Ok(Vec3::new(values[0], values[1], values[2]))
+}
+
+fn parse_joint(node : &Node) -> Result<UrdfJoint, crate::Error> {
+    let name = req_attr(node, "name")?.to_owned();
+
+    let kind = match req_attr(node, "type")? {
+        "revolute" | "continuous" => Some(CompKind::GearJoint),
+        "prismatic" => Some(CompKind::LinearAxis),
+        "fixed" => None,
+        other => return Err(SyError::config(format!("The joint type '{}' of joint '{}' is not supported!", other, name)).into())
+    };
+
+    let link = |tag : &str| -> Result<String, crate::Error> {
+        let elem = child(node, tag)
+            .ok_or_else(|| SyError::config(format!("The joint '{}' has no {} link!", name, tag)))?;
+        Ok(req_attr(&elem, "link")?.to_owned())
+    };
+
+    let parent = link("parent")?;
+    let child_link = link("child")?;
+
+    let (xyz, rot) = match child(node, "origin") {
+        Some(origin) => {
+            let xyz = origin.attribute("xyz").map(parse_vec3).transpose()?.unwrap_or(Vec3::ZERO);
+            let rpy = origin.attribute("rpy").map(parse_vec3).transpose()?.unwrap_or(Vec3::ZERO);
+            (xyz * URDF_LENGTH_SCALE, Mat3::from_euler(EulerRot::ZYX, rpy.z, rpy.y, rpy.x))
+        },
+        None => (Vec3::ZERO, Mat3::IDENTITY)
+    };
+
+    // URDF defaults to the X-axis
+    let axis = child(node, "axis").and_then(|a| a.attribute("xyz")).map(parse_vec3).transpose()?
+        .unwrap_or(Vec3::X).normalize_or_zero();
+
+    // Prismatic limits are lengths, revolute limits are angles
+    let scale = if kind == Some(CompKind::LinearAxis) { URDF_LENGTH_SCALE } else { 1.0 };
+    let limit = child(node, "limit");
+    let limit_attr = |attr : &str| -> Result<Option<f32>, crate::Error> {
+        Ok(limit.and_then(|l| l.attribute(attr)).map(parse_f32).transpose()?.map(|v| v * scale))
+    };
+
+    // Continuous joints have no position limits
+    let (lower, upper) = if req_attr(node, "type")? == "continuous" {
+        (None, None)
+    } else {
+        (limit_attr("lower")?, limit_attr("upper")?)
+    };
+
+    Ok(UrdfJoint {
+        vel: limit_attr("velocity")?,
+        name, kind, parent, child: child_link, xyz, rot, axis, lower, upper
+    })
+}
+
+/// Parses a URDF model into a `Scaffold`, so existing robot models can be used without writing the JSON configuration
+/// by hand
+///
+/// - Revolute and continuous joints become `GearJoint`s, prismatic joints become `LinearAxis` components
+/// - Fixed joints are merged into the surrounding components
+/// - Lengths are converted from meters into millimeters, the link lengths and axes are given for the zero position
+/// - The mass of a component is the mass of all the links it moves until the next joint
+///
+/// Only serial chains are supported, models with branches are rejected
+pub fn parse_urdf(xml : &str) -> Result<Scaffold, crate::Error> {
+    let doc = Document::parse(xml).map_err(|err| SyError::config(format!("Invalid URDF! ({})", err)))?;
+    let robot = doc.root_element();
+
+    if !robot.has_tag_name("robot") {
+        return Err(SyError::config("The root element of an URDF file must be 'robot'!").into());
+    }
+
+    let joints = robot.children().filter(|n| n.has_tag_name("joint"))
+        .map(|n| parse_joint(&n)).collect::<Result<Vec<_>, _>>()?;
+
+    let mass_of = |link : &str| -> Result<f32, crate::Error> {
+        let node = robot.children().find(|n| n.has_tag_name("link") && (n.attribute("name") == Some(link)));
+        let mass = node.as_ref().and_then(|n| child(n, "inertial")).and_then(|i| child(&i, "mass"));
+
+        match mass {
+            Some(mass) => parse_f32(req_attr(&mass, "value")?),
+            None => Ok(0.0)
+        }
+    };
+
+    // The base link is the only link that is not the child of any joint
+    let mut link = match joints.iter().find(|j| !joints.iter().any(|o| o.child == j.parent)) {
+        Some(joint) => joint.parent.clone(),
+        None => return Err(SyError::config("The URDF model contains no joints!").into())
+    };
+
+    let mut scaffold = Scaffold::new(req_attr(&robot, "name")?, &[ ]);
+
+    // Global pose of the current link at the zero position
+    let mut pos = Vec3::ZERO;
+    let mut rot = Mat3::IDENTITY;
+
+    for _ in 0 .. joints.len() {
+        let mut next = joints.iter().filter(|j| j.parent == link);
+
+        let Some(joint) = next.next() else {
+            break;
+        };
+
+        if next.next().is_some() {
+            return Err(SyError::config(format!("The link '{}' has multiple child joints, only serial chains are supported!",
+                link)).into());
+        }
+
+        pos += rot * joint.xyz;
+        rot *= joint.rot;
+
+        let mass = mass_of(&joint.child)?;
+
+        match joint.kind {
+            Some(kind) => {
+                match scaffold.comps.last_mut() {
+                    Some(prev) => prev.dim = pos - prev.dim,
+                    None => scaffold.anchor = pos
+                }
+
+                // The dimension stores the global position of the joint until the next joint is found
+                scaffold.comps.push(CompScaffold {
+                    name: joint.name.clone(),
+                    kind,
+                    dim: pos,
+                    axis: rot * joint.axis,
+                    mass,
+                    vel: joint.vel.unwrap_or(1.0),
+                    min: joint.lower,
+                    max: joint.upper
+                });
+            },
+            None => if let Some(prev) = scaffold.comps.last_mut() {
+                prev.mass += mass;
+            }
+        }
+
+        link = joint.child.clone();
+    }
+
+    // The last component reaches until the end of the chain (e.g. a fixed flange)
+    if let Some(last) = scaffold.comps.last_mut() {
+        last.dim = pos - last.dim;
+    }
+
+    Ok(scaffold)
+}
+
+/// Loads the URDF file at the given path and parses it into a `Scaffold`, see `parse_urdf()`
+pub fn load_urdf<P : AsRef<Path>>(path : P) -> Result<Scaffold, crate::Error> {
+    let xml = std::fs::read_to_string(path)?;
+    parse_urdf(&xml)
+}
@@ -1,10 +1,15 @@
 use syact::math::movements::DefinedActuator;
 use syact::{SyncActuatorGroup, SyncActuator};
+use glam::{EulerRot, Mat3, Vec3};
 use syunit::*;
 
-use crate::Robot;
-use crate::config::AxisConfig;
-use crate::rcs::{PointRef, Position, WorldObj};
+use crate::{Robot, SyError};
+use crate::config::{AxisConfig, ConfSelect, KinConf};
+use crate::rcs::{Point, PointRef, Position, WorldObj};
+use crate::rcs::math::{ori_deviation, swing_twist, OriDof, OrientationError};
+
+/// The maximum deviation of an orientation accepted by `Descriptor::check_ori()` in radians
+pub const ORI_TOLERANCE : f32 = 1e-3;
 
 // ####################
 // #    SUBMODULES    #
@@ -15,8 +20,22 @@ use crate::rcs::{PointRef, Position, WorldObj};
     mod elem;
     pub use elem::{KinElement, Movement, Rot};
 
+    mod envelope;
+    pub use envelope::Envelope;
+
+    mod ik;
+    pub use ik::{jacobian, IkSolver};
+
     mod kin;
     pub use kin::{Kinematic, SerialKinematic};
+
+    mod macros;
+
+    mod singular;
+    pub use singular::{manipulability, SingularityGuard};
+
+    mod workspace;
+    pub use workspace::{SpeedMap, SpeedMapParams, SpeedSample};
 // 
 
 /// # `Descriptor` trait
@@ -41,8 +60,133 @@ pub trait Descriptor<const C : usize> {
     // Calculation
         /// Returns the `Phi` values required to reach a certain position
         fn phis_for_pos(&self, pos : Position) -> Result<[Phi; C], crate::Error>;
+
+        /// Returns all the solutions of the IK for a certain position with their configurations. Descriptors with 
+        /// multiple solutions (e.g. elbow up and down) should override this method, the default returns the solution of 
+        /// `phis_for_pos` only
+        fn phis_for_pos_all(&self, pos : Position) -> Result<Vec<(KinConf, [Phi; C])>, crate::Error> {
+            Ok(vec![ (KinConf::default(), self.phis_for_pos(pos)?) ])
+        }
+
+        /// Returns the `Phi` values required to reach a certain position, selecting one of the valid solutions of 
+        /// `phis_for_pos_all`. `current` are the `Phi` values of the current pose
+        fn phis_for_pos_select(&self, pos : Position, current : &[Phi; C], select : ConfSelect) -> Result<[Phi; C], crate::Error> {
+            let solutions : Vec<_> = self.phis_for_pos_all(pos)?.into_iter()
+                .filter(|(_, phis)| phis.iter().all(|phi| phi.0.is_finite()))
+                .collect();
+
+            select.select(&solutions, current)
+                .map(|(_, phis)| phis)
+                .ok_or_else(|| SyError::kinematics("No valid solution for the position found!").into())
+        }
+
+        /// Returns wheiter the given position can be reached, the IK has to succeed with finite `Phi` values. Limits of 
+        /// the robot are not checked, see `Robot::valid_phis`
+        fn reachable(&self, pos : Position) -> bool {
+            self.phis_for_pos(pos).map_or(false, |phis| phis.iter().all(|phi| phi.0.is_finite()))
+        }
+
+        /// Returns the rotational degrees of freedom of the TCP, descriptors of robots with wrists or rotary tools 
+        /// have to override this method together with `phis_for_pos`. The wrist axes are expected to be the last 
+        /// axes of the kinematic, see `deco_for_ori()`
+        fn ori_dof(&self) -> OriDof {
+            OriDof::None
+        }
+
+        /// Returns an `OrientationError` if the orientation of the position cannot be reached with the rotational 
+        /// degrees of freedom of the robot (see `ori_dof()`), should be called by `phis_for_pos`
+        fn check_ori(&self, pos : &Position) -> Result<(), crate::Error> {
+            let requested = pos.quat();
+            let deviation = ori_deviation(requested, self.ori_dof());
+
+            if deviation > ORI_TOLERANCE {
+                Err(OrientationError { requested, dof: self.ori_dof(), deviation }.into())
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Maps the orientation of the position to the values of the wrist (decoration) axes: none for `OriDof::None`, 
+        /// the twist angle around the Z-axis for `OriDof::Twist` and the ZYX euler angles for `OriDof::Full`. Fails 
+        /// like `check_ori()`
+        fn deco_for_ori(&self, pos : &Position) -> Result<Vec<f32>, crate::Error> {
+            self.check_ori(pos)?;
+
+            Ok(match self.ori_dof() {
+                OriDof::None => Vec::new(),
+                OriDof::Twist => vec![ swing_twist(pos.quat()).1 ],
+                OriDof::Full => {
+                    let (z, y, x) = pos.quat().to_euler(EulerRot::ZYX);
+                    vec![ z, y, x ]
+                }
+            })
+        }
+
+        /// Returns the `Phi` values required to reach a certain position using a generic iterative `IkSolver`, for 
+        /// descriptors without an analytic solution. Starts at the current `Phi` values of the kinematic, which are 
+        /// restored afterwards
+        /// 
+        /// The orientation is mapped to the last axes with `deco_for_ori()`, which are kept fixed while the position 
+        /// is solved with the remaining axes
+        fn phis_for_pos_iter(&mut self, pos : Position, solver : &IkSolver) -> Result<[Phi; C], crate::Error> {
+            let deco = self.deco_for_ori(&pos)?;
+            let free = C.saturating_sub(deco.len());
+
+            let kin = self.kinematic_mut();
+            let phis_0 = kin.phis();
+
+            let mut seed = phis_0;
+            for (phi, value) in seed[free ..].iter_mut().zip(&deco) {
+                *phi = Phi(*value);
+            }
+
+            let res = solver.solve_partial(kin, *pos.pos(), seed, free);
+            kin.update(&phis_0)?;
+            res
+        }
     //
 
+    // Jacobian
+        /// Returns the positional jacobian at the given `Phi` values as columns, the column `i` is the TCP movement per 
+        /// unit of joint `i`. The kinematic is restored afterwards
+        fn jacobian(&mut self, phis : &[Phi; C]) -> Result<[Vec3; C], crate::Error> {
+            let kin = self.kinematic_mut();
+            let phis_0 = kin.phis();
+
+            let res = jacobian(kin, phis, 1e-4);
+            kin.update(&phis_0)?;
+            res
+        }
+
+        /// Returns the manipulability at the given `Phi` values, see `manipulability()`
+        fn manipulability(&mut self, phis : &[Phi; C]) -> Result<f32, crate::Error> {
+            Ok(manipulability(&self.jacobian(phis)?))
+        }
+
+        /// Returns the TCP velocity caused by the given joint velocities at the given `Phi` values
+        fn vel_from_omegas(&mut self, phis : &[Phi; C], omegas : &[Velocity; C]) -> Result<Vec3, crate::Error> {
+            let cols = self.jacobian(phis)?;
+            Ok(cols.iter().zip(omegas).map(|(col, omega)| *col * omega.0).sum())
+        }
+
+        /// Returns the joint velocities required for the given TCP velocity at the given `Phi` values, solved by damped 
+        /// least squares, so redundant joints share the movement and singularities do not cause infinite velocities
+        fn omegas_from_vel(&mut self, phis : &[Phi; C], vel : Vec3) -> Result<[Velocity; C], crate::Error> {
+            const DAMPING : f32 = 1e-3;
+
+            let cols = self.jacobian(phis)?;
+            let jjt = cols.iter().fold(Mat3::IDENTITY * DAMPING, |m, col| m + Mat3::from_cols(*col * col.x, *col * col.y, *col * col.z));
+            let f = jjt.inverse() * vel;
+
+            let mut omegas = [Velocity(0.0); C];
+            for i in 0 .. C {
+                omegas[i] = Velocity(cols[i].dot(f));
+            }
+
+            Ok(omegas)
+        }
+    // 
+
     // Kinematic
         /// Returns a reference to the kinematic system used
         fn kinematic(&self) -> &Self::Kinematic;
@@ -1,10 +1,7 @@
-use crate::{Descriptor, Robot};
-use crate::desc::{SerialKinematic, Kinematic, KinElement, Movement};
+use crate::desc::{SerialKinematic, KinElement, Movement};
 use crate::rcs::{Point, Position, PointRef, WorldObj};
 
 use glam::Vec3;
-use syact::math::movements::DefinedActuator;
-use syact::{SyncActuator, SyncActuatorGroup};
 use syunit::*;
 
 // Linear
@@ -15,66 +12,16 @@ use syunit::*;
         __axis_config : ()
     }
 
-    impl Descriptor<2> for LinearXYDescriptor {
-        // Types
-            type AxisConfig = ();
-            type Kinematic = SerialKinematic<2>;
-        // 
+    crate::impl_serial_descriptor!(LinearXYDescriptor, 2, {
+        fn phis_for_pos(&self, pos : Position) -> Result<[Phi; 2], crate::Error> {
+            self.check_ori(&pos)?;
 
-        // Axis config
-            fn axis_config(&self) -> &Self::AxisConfig {
-                &self.__axis_config
-            }
-
-            fn axis_config_mut(&mut self) -> &mut Self::AxisConfig {
-                &mut self.__axis_config
-            }
-        // 
-
-        // Calculation
-            fn phis_for_pos(&self, pos : Position) -> Result<[Phi; 2], crate::Error> {
-                Ok([
-                    Phi(pos.x()),
-                    Phi(pos.y())
-                ])
-            }
-        //
-
-        // Kinematic
-            fn kinematic(&self) -> &Self::Kinematic {
-                &self._kinematic
-            }
-
-            fn kinematic_mut(&mut self) -> &mut Self::Kinematic {
-                &mut self._kinematic
-            }
-        // 
-
-        // World object
-            fn world_obj(&self) -> &WorldObj {
-                &self._world_obj
-            }
-
-            fn world_obj_mut(&mut self) -> &mut WorldObj {
-                &mut self._world_obj
-            }
-
-            fn tcp(&self) -> &PointRef {
-                self._kinematic.tcp()
-            }
-        // 
-
-        // Events
-            fn update<R, G, T>(&mut self, _rob : &mut R, phis : &[Phi; 2]) -> Result<(), crate::Error>
-            where
-                R : Robot<G, T, 2>,
-                G : SyncActuatorGroup<T, 2>,
-                T : SyncActuator + DefinedActuator + ?Sized + 'static
-            {
-                self._kinematic.update(phis)
-            }
-        // 
-    }
+            Ok([
+                Phi(pos.x()),
+                Phi(pos.y())
+            ])
+        }
+    });
 
     impl LinearXYDescriptor {
         pub fn new() -> Self {
@@ -108,67 +55,17 @@ use syunit::*;
         __axis_config : ()
     }
 
-    impl Descriptor<3> for LinearXYZDescriptor {
-        // Types
-            type AxisConfig = ();
-            type Kinematic = SerialKinematic<3>;
-        // 
+    crate::impl_serial_descriptor!(LinearXYZDescriptor, 3, {
+        fn phis_for_pos(&self, pos : Position) -> Result<[Phi; 3], crate::Error> {
+            self.check_ori(&pos)?;
 
-        // Axis config
-            fn axis_config(&self) -> &Self::AxisConfig {
-                &self.__axis_config
-            }
-
-            fn axis_config_mut(&mut self) -> &mut Self::AxisConfig {
-                &mut self.__axis_config
-            }
-        // 
-
-        // Calculation
-            fn phis_for_pos(&self, pos : Position) -> Result<[Phi; 3], crate::Error> {
-                Ok([
-                    Phi(pos.x()),
-                    Phi(pos.y()),
-                    Phi(pos.z())
-                ])
-            }
-        //
-
-        // Kinematic
-            fn kinematic(&self) -> &Self::Kinematic {
-                &self._kinematic
-            }
-
-            fn kinematic_mut(&mut self) -> &mut Self::Kinematic {
-                &mut self._kinematic
-            }
-        // 
-
-        // World object
-            fn world_obj(&self) -> &WorldObj {
-                &self._world_obj
-            }
-
-            fn world_obj_mut(&mut self) -> &mut WorldObj {
-                &mut self._world_obj
-            }
-
-            fn tcp(&self) -> &PointRef {
-                self._kinematic.tcp()
-            }
-        // 
-
-        // Events
-            fn update<R, G, T>(&mut self, _rob : &mut R, phis : &[Phi; 3]) -> Result<(), crate::Error>
-            where
-                R : Robot<G, T, 3>,
-                G : SyncActuatorGroup<T, 3>,
-                T : SyncActuator + DefinedActuator + ?Sized + 'static
-            {
-                self._kinematic.update(phis)
-            }
-        // 
-    }
+            Ok([
+                Phi(pos.x()),
+                Phi(pos.y()),
+                Phi(pos.z())
+            ])
+        }
+    });
 
     impl LinearXYZDescriptor {
         pub fn new() -> Self {
@@ -44,6 +44,14 @@ impl KinElement {
         pub fn point(&self) -> &PointRef {
             &self._point
         }
+
+        pub fn movement(&self) -> &Movement {
+            &self.movement
+        }
+
+        pub fn point_0(&self) -> &PointRef {
+            &self.point_0
+        }
     // 
 
     #[inline]
@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use glam::Vec3;
+use syunit::*;
+
+use crate::SyError;
+use crate::desc::Kinematic;
+use crate::rcs::Point;
+
+/// The reachable envelope of a robot, precomputed by sampling the joint space within its limits and stored as a set of
+/// voxels. Used by planners and servers to reject unreachable targets before solving the IK
+#[derive(Clone, Debug)]
+pub struct Envelope {
+    cells : HashSet<[i32; 3]>,
+
+    /// The edge length of the voxels
+    pub voxel : f32,
+    /// Minimum corner of the bounding box of the envelope
+    pub min : Vec3,
+    /// Maximum corner of the bounding box of the envelope
+    pub max : Vec3,
+    /// The maximum distance of a reachable point from the origin
+    pub reach : f32
+}
+
+impl Envelope {
+    /// Samples the envelope with `steps` values per joint between the limits given as `(min, max)`, so `steps^C`
+    /// positions are calculated. The kinematic is restored afterwards. Pass the limits through `Robot::freeze_masked()`
+    /// to sample the envelope reachable with the masked axes frozen
+    pub fn sample<K : Kinematic<C>, const C : usize>(kin : &mut K, limits : &[(Phi, Phi); C], steps : usize, voxel : f32)
+        -> Result<Self, crate::Error>
+    {
+        if (steps < 2) | (voxel <= 0.0) {
+            return Err(SyError::config(format!("An envelope requires at least 2 steps and a positive voxel size! (Steps: {}, Voxel: {})",
+                steps, voxel)).into());
+        }
+
+        let phis_0 = kin.phis();
+        let mut env = Self {
+            cells: HashSet::new(),
+            voxel,
+            min: Vec3::splat(f32::INFINITY),
+            max: Vec3::splat(f32::NEG_INFINITY),
+            reach: 0.0
+        };
+
+        let mut index = [0usize; C];
+
+        'outer: loop {
+            let mut phis = [Phi::ZERO; C];
+            for i in 0 .. C {
+                let (min, max) = limits[i];
+                phis[i] = Phi(min.0 + (max.0 - min.0) * index[i] as f32 / (steps - 1) as f32);
+            }
+
+            kin.update(&phis)?;
+            let pos = *kin.calculate_end().pos();
+
+            if pos.is_finite() {
+                env.cells.insert(env.cell(pos));
+                env.min = env.min.min(pos);
+                env.max = env.max.max(pos);
+                env.reach = env.reach.max(pos.length());
+            }
+
+            // Advance the joint grid like a counter
+            for i in 0 .. C {
+                index[i] += 1;
+                if index[i] < steps {
+                    continue 'outer;
+                }
+                index[i] = 0;
+            }
+
+            break;
+        }
+
+        kin.update(&phis_0)?;
+        Ok(env)
+    }
+
+    fn cell(&self, pos : Vec3) -> [i32; 3] {
+        (pos / self.voxel).floor().as_ivec3().to_array()
+    }
+
+    /// Returns wheiter the position is inside of the envelope, the voxels next to a sampled one are accepted as well,
+    /// as the sampling is sparse
+    pub fn contains(&self, pos : Vec3) -> bool {
+        if !pos.is_finite() | (pos.length() > self.reach + self.voxel) {
+            return false;
+        }
+
+        let [x, y, z] = self.cell(pos);
+
+        (-1 ..= 1).any(|dx| (-1 ..= 1).any(|dy| (-1 ..= 1).any(|dz| {
+            self.cells.contains(&[ x + dx, y + dy, z + dz ])
+        })))
+    }
+
+    /// Returns an error if the position is outside of the envelope
+    pub fn check(&self, pos : Vec3) -> Result<(), crate::Error> {
+        if self.contains(pos) {
+            Ok(())
+        } else {
+            Err(SyError::kinematics(format!("Position {:?} is outside of the workspace! (Reach: {})", 
+                pos.to_array(), self.reach)).into())
+        }
+    }
+}
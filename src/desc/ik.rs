@@ -0,0 +1,98 @@
+use glam::{Mat3, Vec3};
+use syunit::*;
+
+use crate::SyError;
+use crate::desc::Kinematic;
+use crate::rcs::Point;
+
+/// Returns the positional jacobian of the kinematic at the given `Phi` values, the column `i` is the TCP movement 
+/// per unit of joint `i`. Calculated by finite differences, the kinematic is left at the given `phis`
+pub fn jacobian<const C : usize, K : Kinematic<C> + ?Sized>(kin : &mut K, phis : &[Phi; C], epsilon : f32) -> Result<[Vec3; C], crate::Error> {
+    kin.update(phis)?;
+    let pos_0 = *kin.calculate_end().pos();
+
+    let mut cols = [Vec3::ZERO; C];
+    for i in 0 .. C {
+        let mut phis_i = *phis;
+        phis_i[i] = Phi(phis_i[i].0 + epsilon);
+
+        kin.update(&phis_i)?;
+        cols[i] = (*kin.calculate_end().pos() - pos_0) / epsilon;
+    }
+
+    kin.update(phis)?;
+    Ok(cols)
+}
+
+/// Generic iterative inverse kinematics solver (damped least squares) for kinematics with any number of joints
+/// 
+/// Only the position of the TCP is solved, the orientation is ignored
+#[derive(Clone, Debug)]
+pub struct IkSolver {
+    /// Maximum number of iterations
+    pub max_iter : usize,
+    /// Maximum distance to the target accepted in millimeters
+    pub tolerance : f32,
+    /// Damping factor, higher values are more stable near singularities but converge slower
+    pub damping : f32,
+    /// Joint step used for the finite differences of the jacobian
+    pub epsilon : f32
+}
+
+impl Default for IkSolver {
+    fn default() -> Self {
+        Self {
+            max_iter: 100,
+            tolerance: 0.01,
+            damping: 0.5,
+            epsilon: 1e-4
+        }
+    }
+}
+
+impl IkSolver {
+    /// Solves the `Phi` values required to reach the `target`, starting at the `seed` values (usually the current 
+    /// position). The kinematic is left at the solution
+    pub fn solve<const C : usize, K : Kinematic<C> + ?Sized>(&self, kin : &mut K, target : Vec3, seed : [Phi; C]) 
+    -> Result<[Phi; C], crate::Error> {
+        self.solve_partial(kin, target, seed, C)
+    }
+
+    /// Same as `solve()`, but only the first `free` joints are moved, the others keep their `seed` values (e.g. wrist 
+    /// axes set by the orientation)
+    pub fn solve_partial<const C : usize, K : Kinematic<C> + ?Sized>(&self, kin : &mut K, target : Vec3, seed : [Phi; C], 
+        free : usize) -> Result<[Phi; C], crate::Error> {
+        let free = free.min(C);
+        let mut phis = seed;
+
+        for _ in 0 .. self.max_iter {
+            kin.update(&phis)?;
+            let error = target - *kin.calculate_end().pos();
+
+            if error.length() <= self.tolerance {
+                return Ok(phis);
+            }
+
+            let cols = jacobian(kin, &phis, self.epsilon)?;
+
+            // J * J^T + lambda^2 * I
+            let mut jjt = Mat3::ZERO;
+            for col in &cols[.. free] {
+                jjt = jjt + Mat3::from_cols(*col * col.x, *col * col.y, *col * col.z);
+            }
+            jjt = jjt + Mat3::IDENTITY * self.damping.powi(2);
+
+            if jjt.determinant().abs() < f32::EPSILON {
+                break;
+            }
+
+            // delta_phi = J^T * (J * J^T + lambda^2 * I)^-1 * error
+            let f = jjt.inverse() * error;
+            for i in 0 .. free {
+                phis[i] = Phi(phis[i].0 + cols[i].dot(f));
+            }
+        }
+
+        Err(SyError::kinematics(format!("The inverse kinematics did not converge! (Target: {:?})", target)).into())
+    }
+}
@@ -0,0 +1,70 @@
+/// Implements the `Descriptor` trait for a descriptor with a `SerialKinematic`, generating all the repetitive plumbing
+/// 
+/// The struct requires the fields `_kinematic : SerialKinematic<C>`, `_world_obj : WorldObj` and `__axis_config : ()`, 
+/// only the geometry-specific methods (e.g. `phis_for_pos`) have to be written
+/// 
+/// ```rust ,ignore
+/// sybot::impl_serial_descriptor!(LinearXYDescriptor, 2, {
+///     fn phis_for_pos(&self, pos : Position) -> Result<[Phi; 2], sybot::Error> {
+///         Ok([ Phi(pos.x()), Phi(pos.y()) ])
+///     }
+/// });
+/// ```
+#[macro_export]
+macro_rules! impl_serial_descriptor {
+    ($name:ty, $c:literal, { $($items:tt)* }) => {
+        impl $crate::Descriptor<$c> for $name {
+            // Types
+                type AxisConfig = ();
+                type Kinematic = $crate::desc::SerialKinematic<$c>;
+            // 
+
+            // Axis config
+                fn axis_config(&self) -> &Self::AxisConfig {
+                    &self.__axis_config
+                }
+
+                fn axis_config_mut(&mut self) -> &mut Self::AxisConfig {
+                    &mut self.__axis_config
+                }
+            // 
+
+            // Kinematic
+                fn kinematic(&self) -> &Self::Kinematic {
+                    &self._kinematic
+                }
+
+                fn kinematic_mut(&mut self) -> &mut Self::Kinematic {
+                    &mut self._kinematic
+                }
+            // 
+
+            // World object
+                fn world_obj(&self) -> &$crate::rcs::WorldObj {
+                    &self._world_obj
+                }
+
+                fn world_obj_mut(&mut self) -> &mut $crate::rcs::WorldObj {
+                    &mut self._world_obj
+                }
+
+                fn tcp(&self) -> &$crate::rcs::PointRef {
+                    $crate::desc::Kinematic::tcp(&self._kinematic)
+                }
+            // 
+
+            // Events
+                fn update<R, G, T>(&mut self, _rob : &mut R, phis : &[$crate::__syunit::Phi; $c]) -> Result<(), $crate::Error>
+                where
+                    R : $crate::Robot<G, T, $c>,
+                    G : $crate::__syact::SyncActuatorGroup<T, $c>,
+                    T : $crate::__syact::SyncActuator + $crate::__syact::math::movements::DefinedActuator + ?Sized + 'static
+                {
+                    $crate::desc::Kinematic::update(&mut self._kinematic, phis)
+                }
+            // 
+
+            $($items)*
+        }
+    };
+}
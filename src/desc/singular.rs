@@ -0,0 +1,39 @@
+use glam::{Mat3, Vec3};
+
+/// The minimum speed factor applied close to singularities, so movements never stall completely
+const MIN_FACTOR : f32 = 0.05;
+
+/// Returns the manipulability (`sqrt(det(J * J^T))`) of the positional jacobian given as columns, which drops to zero
+/// at singular configurations
+pub fn manipulability(cols : &[Vec3]) -> f32 {
+    let jjt = cols.iter().fold(Mat3::ZERO, |m, col| m + Mat3::from_cols(*col * col.x, *col * col.y, *col * col.z));
+    jjt.determinant().max(0.0).sqrt()
+}
+
+/// Thresholds of the manipulability for Cartesian movements, paths are slowed down when getting close to singular
+/// configurations and rejected if they pass through them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SingularityGuard {
+    /// Below this manipulability the speed is reduced linearly
+    pub slow : f32,
+    /// Below this manipulability the path is rejected
+    pub reject : f32
+}
+
+impl SingularityGuard {
+    /// Creates a new guard
+    pub fn new(slow : f32, reject : f32) -> Self {
+        Self { slow, reject }
+    }
+
+    /// Returns the speed factor for the given manipulability, `None` if the configuration is too close to a singularity
+    pub fn factor(&self, manipulability : f32) -> Option<f32> {
+        if manipulability < self.reject {
+            None
+        } else if manipulability < self.slow {
+            Some((manipulability / self.slow).max(MIN_FACTOR))
+        } else {
+            Some(1.0)
+        }
+    }
+}
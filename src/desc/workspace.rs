@@ -0,0 +1,125 @@
+use std::io::Write;
+
+use glam::Vec3;
+use syunit::*;
+
+use crate::SyError;
+use crate::desc::Descriptor;
+use crate::rcs::Position;
+use crate::rcs::math::sub_phis;
+
+/// Gravitational acceleration in meters per second squared
+const GRAVITY : f32 = 9.81;
+
+/// Parameters of a workspace speed analysis
+#[derive(Clone, Debug)]
+pub struct SpeedMapParams<const C : usize> {
+    /// Minimum corner of the sampled box in millimeters
+    pub min : Vec3,
+    /// Maximum corner of the sampled box in millimeters
+    pub max : Vec3,
+    /// Distance between samples in millimeters
+    pub step : f32,
+    /// Maximum angular velocity of each joint
+    pub omega_max : [Velocity; C],
+    /// Maximum holding torque of each joint, if given the static payload is calculated
+    pub torque_max : Option<[Force; C]>,
+    /// Distance used for the finite differences in millimeters
+    pub epsilon : f32
+}
+
+/// A single sample of a `SpeedMap`
+#[derive(Clone, Copy, Debug)]
+pub struct SpeedSample {
+    /// Position of the sample in millimeters
+    pub pos : Vec3,
+    /// The TCP speed achievable in every direction (the worst direction) in millimeters per second, 
+    /// `None` if the position cannot be reached
+    pub speed : Option<f32>,
+    /// The maximum static payload in kilograms, `None` if not calculated or unreachable
+    pub payload : Option<f32>
+}
+
+/// A grid of achievable TCP speeds and payloads across the workspace, used for cell layout decisions
+#[derive(Clone, Debug, Default)]
+pub struct SpeedMap {
+    /// The samples, ordered by x, then y, then z
+    pub samples : Vec<SpeedSample>
+}
+
+impl SpeedMap {
+    /// Samples the workspace of the given descriptor, the jacobian of the descriptor is used for the payload
+    pub fn sample<const C : usize, D : Descriptor<C>>(desc : &mut D, params : &SpeedMapParams<C>) -> Result<Self, crate::Error> {
+        if params.step <= 0.0 {
+            return Err(SyError::config("The step of the speed map must be positive!").into());
+        }
+
+        let count = ((params.max - params.min) / params.step).floor();
+        let mut samples = Vec::new();
+
+        for ix in 0 ..= count.x as usize {
+            for iy in 0 ..= count.y as usize {
+                for iz in 0 ..= count.z as usize {
+                    let pos = params.min + Vec3::new(ix as f32, iy as f32, iz as f32) * params.step;
+                    samples.push(Self::sample_point(desc, params, pos));
+                }
+            }
+        }
+
+        Ok(Self { samples })
+    }
+
+    fn sample_point<const C : usize, D : Descriptor<C>>(desc : &mut D, params : &SpeedMapParams<C>, pos : Vec3) -> SpeedSample {
+        let unreachable = SpeedSample { pos, speed: None, payload: None };
+
+        let Ok(phis) = desc.phis_for_pos(Position::from_vec3(pos)) else {
+            return unreachable;
+        };
+
+        // Speed: joint rates per millimeter in each direction (from the inverse kinematics), limited by the slowest joint
+        let mut speed = f32::INFINITY;
+        for dir in [ Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y, Vec3::Z, Vec3::NEG_Z ] {
+            let Ok(phis_d) = desc.phis_for_pos(Position::from_vec3(pos + dir * params.epsilon)) else {
+                return unreachable;
+            };
+
+            let deltas = sub_phis(phis_d, phis);
+            for i in 0 .. C {
+                let rate = deltas[i].0.abs() / params.epsilon;
+                if rate > 0.0 {
+                    speed = speed.min(params.omega_max[i].0 / rate);
+                }
+            }
+        }
+
+        // Payload: the torque of a weight on each joint is given by the vertical movement per joint angle (virtual work)
+        let payload = params.torque_max.and_then(|torque_max| {
+            let cols = desc.jacobian(&phis).ok()?;
+
+            let mut payload = f32::INFINITY;
+            for i in 0 .. C {
+                // Lever in meters
+                let lever = cols[i].z.abs() / 1000.0;
+                if lever > 0.0 {
+                    payload = payload.min(torque_max[i].0 / (lever * GRAVITY));
+                }
+            }
+
+            Some(payload)
+        });
+
+        SpeedSample { pos, speed: speed.is_finite().then_some(speed), payload }
+    }
+
+    /// Writes the map as CSV table (x, y, z, speed, payload), unreachable samples have empty values
+    pub fn write_csv(&self, mut writer : impl Write) -> Result<(), crate::Error> {
+        writeln!(writer, "x,y,z,speed,payload")?;
+
+        for s in &self.samples {
+            let opt = |v : Option<f32>| v.map_or(String::new(), |v| v.to_string());
+            writeln!(writer, "{},{},{},{},{}", s.pos.x, s.pos.y, s.pos.z, opt(s.speed), opt(s.payload))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use core::fmt::Display;
+
+use crate::catalog::Message;
+use crate::rcs::math::{OrientationError, OutOfReach};
+use crate::robs::{GantryFault, VerifyError};
+
+/// Classes of failures occuring in the crate, each with a stable error code
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// A position or joint value is out of the reachable range
+    Kinematics,
+    /// A component or driver failed or reported a fault
+    Component,
+    /// A configuration could not be parsed or is invalid
+    Config,
+    /// A program or gcode line could not be interpreted
+    Interpreter,
+    /// Communication with a remote failed
+    Remote,
+    /// A safety condition (homing, interlock, limits) prevented the operation
+    Safety,
+    /// The client is not authenticated or not permitted to perform the operation
+    Access,
+    /// Any other failure
+    Other
+}
+
+impl ErrorKind {
+    /// Returns the error code of the kind, stable across versions
+    pub fn code(self) -> u16 {
+        match self {
+            Self::Kinematics => 100,
+            Self::Component => 200,
+            Self::Config => 300,
+            Self::Interpreter => 400,
+            Self::Remote => 500,
+            Self::Safety => 600,
+            Self::Access => 700,
+            Self::Other => 900
+        }
+    }
+
+    /// Returns the kind of any error of the crate, see `Error::kind()`
+    pub fn of(err : &Error) -> Self {
+        err.kind()
+    }
+}
+
+/// The error type of the crate, failures can be matched by their variant or classified with `Error::kind()`
+/// 
+/// Errors of other types are converted with `?`, errors of known types (e.g. `SyError` or `serde_json::Error`) are 
+/// sorted into their variant, all others become `Error::Other`. Errors of `syact` components are converted with 
+/// `Error::from_syact()`
+#[derive(Debug)]
+pub enum Error {
+    /// A failure of the crate itself, see `SyError`
+    Sy(SyError),
+    /// A cartesian target is out of reach of the segments
+    OutOfReach(OutOfReach),
+    /// A target orientation cannot be reached with the rotational degrees of freedom of the robot
+    Orientation(OrientationError),
+    /// Components are outside of the tolerance after a verified movement
+    Verify(VerifyError),
+    /// The two sides of a gantry diverged
+    Gantry(GantryFault),
+    /// A configuration or message could not be parsed
+    Json(serde_json::Error),
+    /// Reading or writing a file or connection failed
+    Io(std::io::Error),
+    /// A component driven by `syact` failed
+    Component(syact::Error),
+    /// Any other failure
+    Other(Box<dyn std::error::Error>)
+}
+
+impl Error {
+    /// Converts an error of a `syact` component
+    pub fn from_syact(err : syact::Error) -> Self {
+        Self::Component(err)
+    }
+
+    /// Returns the kind of the error, IO errors are classified as `ErrorKind::Remote` if a connection failed and as 
+    /// `ErrorKind::Other` otherwise (e.g. for files)
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Sy(err) => err.kind,
+            Self::OutOfReach(_) | Self::Orientation(_) => ErrorKind::Kinematics,
+            Self::Verify(_) | Self::Gantry(_) | Self::Component(_) => ErrorKind::Component,
+            Self::Json(_) => ErrorKind::Config,
+            Self::Io(err) => match err.kind() {
+                std::io::ErrorKind::ConnectionRefused | std::io::ErrorKind::ConnectionReset 
+                    | std::io::ErrorKind::ConnectionAborted | std::io::ErrorKind::NotConnected 
+                    | std::io::ErrorKind::AddrInUse | std::io::ErrorKind::AddrNotAvailable
+                    | std::io::ErrorKind::BrokenPipe | std::io::ErrorKind::TimedOut => ErrorKind::Remote,
+                _ => ErrorKind::Other
+            },
+            Self::Other(_) => ErrorKind::Other
+        }
+    }
+
+    /// Returns the error code, see `ErrorKind::code`
+    pub fn code(&self) -> u16 {
+        self.kind().code()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Sy(err) => err.fmt(f),
+            Self::OutOfReach(err) => err.fmt(f),
+            Self::Orientation(err) => err.fmt(f),
+            Self::Verify(err) => err.fmt(f),
+            Self::Gantry(err) => err.fmt(f),
+            Self::Json(err) => err.fmt(f),
+            Self::Io(err) => err.fmt(f),
+            Self::Component(err) => err.fmt(f),
+            Self::Other(err) => err.fmt(f)
+        }
+    }
+}
+
+impl<E : std::error::Error + 'static> From<E> for Error {
+    fn from(err : E) -> Self {
+        let err : Box<dyn std::error::Error> = Box::new(err);
+
+        // Errors of known types keep their own variant
+        let err = match err.downcast::<SyError>() { Ok(err) => return Self::Sy(*err), Err(err) => err };
+        let err = match err.downcast::<OutOfReach>() { Ok(err) => return Self::OutOfReach(*err), Err(err) => err };
+        let err = match err.downcast::<OrientationError>() { Ok(err) => return Self::Orientation(*err), Err(err) => err };
+        let err = match err.downcast::<VerifyError>() { Ok(err) => return Self::Verify(*err), Err(err) => err };
+        let err = match err.downcast::<GantryFault>() { Ok(err) => return Self::Gantry(*err), Err(err) => err };
+        let err = match err.downcast::<serde_json::Error>() { Ok(err) => return Self::Json(*err), Err(err) => err };
+        let err = match err.downcast::<std::io::Error>() { Ok(err) => return Self::Io(*err), Err(err) => err };
+
+        Self::Other(err)
+    }
+}
+
+impl From<Error> for Box<dyn std::error::Error> {
+    fn from(err : Error) -> Self {
+        match err {
+            Error::Sy(err) => Box::new(err),
+            Error::OutOfReach(err) => Box::new(err),
+            Error::Orientation(err) => Box::new(err),
+            Error::Verify(err) => Box::new(err),
+            Error::Gantry(err) => Box::new(err),
+            Error::Json(err) => Box::new(err),
+            Error::Io(err) => Box::new(err),
+            Error::Component(err) => err.into(),
+            Error::Other(err) => err
+        }
+    }
+}
+
+/// A failure of the crate itself, can be matched by downstream code with `Error::Sy` or classified with `ErrorKind::of`
+#[derive(Clone, Debug)]
+pub struct SyError {
+    /// The class of the failure
+    pub kind : ErrorKind,
+    /// Description of the failure
+    pub msg : String,
+    /// Named parameters for rendering the error with a `Catalog`
+    pub params : Vec<(String, String)>,
+    /// Code of the specific message of the error (`catalog::ERR_*`), rendered with the parameters instead of the 
+    /// description if the catalog has a template for it
+    pub detail : Option<u16>
+}
+
+impl SyError {
+    /// Creates a new error of the given kind
+    pub fn new<S : Into<String>>(kind : ErrorKind, msg : S) -> Self {
+        Self { kind, msg: msg.into(), params: Vec::new(), detail: None }
+    }
+
+    /// Creates a new kinematics error
+    pub fn kinematics<S : Into<String>>(msg : S) -> Self {
+        Self::new(ErrorKind::Kinematics, msg)
+    }
+
+    /// Creates a new remote error, e.g. for closed channels
+    pub fn remote<S : Into<String>>(msg : S) -> Self {
+        Self::new(ErrorKind::Remote, msg)
+    }
+
+    /// Creates a new safety error
+    pub fn safety<S : Into<String>>(msg : S) -> Self {
+        Self::new(ErrorKind::Safety, msg)
+    }
+
+    /// Creates a new component error
+    pub fn component<S : Into<String>>(msg : S) -> Self {
+        Self::new(ErrorKind::Component, msg)
+    }
+
+    /// Creates a new configuration error
+    pub fn config<S : Into<String>>(msg : S) -> Self {
+        Self::new(ErrorKind::Config, msg)
+    }
+
+    /// Creates a new interpreter error, e.g. for invalid gcode
+    pub fn interpreter<S : Into<String>>(msg : S) -> Self {
+        Self::new(ErrorKind::Interpreter, msg)
+    }
+
+    /// Creates a new access error
+    pub fn access<S : Into<String>>(msg : S) -> Self {
+        Self::new(ErrorKind::Access, msg)
+    }
+
+    /// Converts any error of the crate into a `SyError`, errors of other types keep their kind (see `Error::kind()`)
+    pub fn from_error(err : &Error) -> Self {
+        match err {
+            Error::Sy(err) => err.clone(),
+            err => Self::new(err.kind(), err.to_string())
+        }
+    }
+
+    /// Adds a named parameter, used by catalogs to render the error in other languages
+    pub fn with_param<K : Into<String>, V : ToString>(mut self, key : K, value : V) -> Self {
+        self.params.push((key.into(), value.to_string()));
+        self
+    }
+
+    /// Sets the code of the specific message of the error, see `SyError::detail`
+    pub fn with_detail(mut self, code : u16) -> Self {
+        self.detail = Some(code);
+        self
+    }
+
+    /// Returns the error code, see `ErrorKind::code`
+    pub fn code(&self) -> u16 {
+        self.kind.code()
+    }
+
+    /// Returns the error as a language independent message with the code of the specific message if there is one 
+    /// (see `SyError::detail`), the description is available as the `msg` parameter
+    pub fn message(&self) -> Message {
+        let mut message = Message::new(self.detail.unwrap_or(self.code())).param("msg", &self.msg);
+        message.params.extend(self.params.iter().cloned());
+        message
+    }
+}
+
+impl Display for SyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[E{}] {}", self.code(), self.msg)
+    }
+}
+
+impl std::error::Error for SyError { }
@@ -0,0 +1,77 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::SyError;
+use crate::cancel::CancelToken;
+use crate::catalog::ERR_ESTOP;
+
+/// A cloneable, thread-safe software emergency stop
+/// 
+/// Triggering the stop aborts all the drives of the robots sharing it, afterwards no new motion is accepted until 
+/// `reset()` is called explicitly
+/// 
+/// ```rust
+/// use sybot::estop::EStop;
+/// 
+/// let estop = EStop::new();
+/// estop.trigger("Door opened");
+/// 
+/// assert!(estop.check().is_err());
+/// estop.reset();
+/// assert!(estop.check().is_ok());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EStop {
+    token : CancelToken,
+    reason : Arc<Mutex<Option<String>>>
+}
+
+impl EStop {
+    /// Creates a new stop, that has not been triggered
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Triggers the stop, aborting all the drives guarded
+    pub fn trigger<S : Into<String>>(&self, reason : S) {
+        *self.reason.lock().unwrap() = Some(reason.into());
+        self.token.cancel();
+    }
+
+    /// Resets the stop, so new motion is accepted again
+    pub fn reset(&self) {
+        *self.reason.lock().unwrap() = None;
+        self.token.reset();
+    }
+
+    /// Returns wheiter the stop has been triggered
+    pub fn is_triggered(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Returns the reason the stop has been triggered with
+    pub fn reason(&self) -> Option<String> {
+        self.reason.lock().unwrap().clone()
+    }
+
+    fn error(&self) -> crate::Error {
+        let reason = self.reason().unwrap_or_default();
+        SyError::safety(format!("Emergency stop! ({})", reason)).with_detail(ERR_ESTOP).with_param("reason", reason).into()
+    }
+
+    /// Returns an error if the stop has been triggered and not been reset yet
+    pub fn check(&self) -> Result<(), crate::Error> {
+        if self.is_triggered() {
+            Err(self.error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Runs the given future until it finishes or the stop is triggered, in which case the future is dropped and an 
+    /// error is returned
+    pub async fn guard<T, F : Future<Output = Result<T, crate::Error>>>(&self, fut : F) -> Result<T, crate::Error> {
+        self.check()?;
+        self.token.guard(fut).await.map_err(|err| if self.is_triggered() { self.error() } else { err })
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+use glam::Vec3;
+use syunit::*;
+use tokio::sync::broadcast;
+
+/// Default capacity of an `EventBus`
+pub const DEFAULT_CAPACITY : usize = 64;
+
+/// Typed state events published by a robot
+#[derive(Clone, Debug)]
+pub enum StateEvent<const C : usize> {
+    /// The position of the robot has been updated
+    Phis([Phi; C]),
+    /// The tool of the robot has been changed
+    ToolChange { 
+        /// The id of the previous tool
+        old : Option<usize>, 
+        /// The id of the new tool
+        new : Option<usize> 
+    },
+    /// An error occured
+    Error(String),
+    /// The robot entered the soft limit margin zone of an axis
+    LimitWarning {
+        /// The index of the axis
+        axis : usize,
+        /// The distance to the limit
+        distance : f32
+    },
+    /// Step loss has been detected on an axis, see `StepCheck`
+    StepLoss {
+        /// The index of the axis
+        axis : usize,
+        /// The deviation of the reference switch
+        deviation : f32
+    },
+    /// A jog has been stopped at the soft limit of an axis
+    JogLimit {
+        /// The index of the axis
+        axis : usize
+    },
+    /// A cartesian target out of reach has been snapped to the nearest reachable position, see `Vars::snap_tolerance`
+    TargetSnapped {
+        /// The correction applied to the target
+        correction : Vec3
+    },
+    /// Tools crossed their maintenance thresholds, see `Station::store_tool_usage()`
+    Maintenance {
+        /// The ids of the tools
+        tools : Vec<usize>
+    }
+}
+
+/// Bus distributing `StateEvent`s to all subscribers
+#[derive(Debug)]
+pub struct EventBus<const C : usize> {
+    tx : broadcast::Sender<StateEvent<C>>
+}
+
+impl<const C : usize> EventBus<C> {
+    /// Creates a new bus, `capacity` is the number of events buffered for slow subscribers
+    pub fn new(capacity : usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Publishes an event to all subscribers, events are dropped if there are no subscribers
+    pub fn publish(&self, event : StateEvent<C>) {
+        self.tx.send(event).ok();
+    }
+
+    /// Subscribes to all the events, position updates are limited to one per `rate` (`Duration::ZERO` for all updates)
+    pub fn subscribe(&self, rate : Duration) -> Subscription<C> {
+        Subscription {
+            rx: self.tx.subscribe(),
+            rate,
+            last_phis: None
+        }
+    }
+}
+
+impl<const C : usize> Default for EventBus<C> {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+/// A subscription to an `EventBus`
+pub struct Subscription<const C : usize> {
+    rx : broadcast::Receiver<StateEvent<C>>,
+    rate : Duration,
+    last_phis : Option<Instant>
+}
+
+impl<const C : usize> Subscription<C> {
+    /// Receives the next event, returns `None` if the bus has been dropped
+    /// 
+    /// Events missed because the subscriber was too slow are skipped
+    pub async fn recv(&mut self) -> Option<StateEvent<C>> {
+        loop {
+            match self.rx.recv().await {
+                Ok(StateEvent::Phis(phis)) => {
+                    if self.last_phis.map_or(false, |inst| inst.elapsed() < self.rate) {
+                        continue;
+                    }
+
+                    self.last_phis = Some(Instant::now());
+                    return Some(StateEvent::Phis(phis));
+                },
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// Submodules
+    /// Circular movements (`G2` / `G3`)
+    pub mod arc;
+
+    /// Line-wise program editing with validation feedback
+    pub mod edit;
+
+    /// Conversion of paths and programs into gcode text
+    pub mod emit;
+
+    /// Machine commands changing the state of the robot and tool (`M42`, `M220`)
+    pub mod mcode;
+
+    /// Motion lines (`G0` - `G3`) including the arc words (`I`, `J`, `K`, `R`)
+    pub mod motion;
+
+    /// Move targets given by named frames and positions
+    pub mod target;
+
+    /// Parsing of gcode lines into words
+    pub mod word;
+// 
@@ -0,0 +1,26 @@
+use glam::Vec3;
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, Robot};
+use crate::rcs::Position;
+
+pub use crate::rcs::{arc_points, ArcCenter, Plane};
+
+/// Drives the robot along the given arc points using `Robot::move_p_sync`, the points are given in program coordinates
+/// (see `Robot::resolve_target()`)
+pub async fn move_arc<R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, points : &[Vec3], speed_f : Factor) 
+-> Result<(), crate::Error> 
+where
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    for point in points {
+        rob.move_p_sync(desc, Position::from_vec3(*point), speed_f).await?;
+    }
+
+    Ok(())
+}
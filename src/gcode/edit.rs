@@ -0,0 +1,142 @@
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Robot, SyError};
+use crate::gcode::mcode::m220_factor;
+use crate::gcode::motion::motion_points;
+use crate::gcode::word::parse_line;
+use crate::rcs::Position;
+use crate::sender::ModalState;
+
+/// Validation feedback for a single line of a program
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineDiagnostic {
+    /// Index of the line
+    pub line : usize,
+    /// Description of the problem
+    pub msg : String
+}
+
+/// A line-wise editable gcode program with immediate validation feedback (syntax and reachability), the backend 
+/// of lightweight program editors
+#[derive(Clone, Debug, Default)]
+pub struct ProgramEditor {
+    lines : Vec<String>
+}
+
+impl ProgramEditor {
+    /// Creates a new editor with the given program text
+    pub fn new(text : &str) -> Self {
+        Self {
+            lines: text.lines().map(|l| l.to_owned()).collect()
+        }
+    }
+
+    /// Returns all the lines of the program
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns the program text
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    // Editing
+        /// Replaces the line with the given index, returns the syntax error of the new line if there is one
+        pub fn set_line(&mut self, index : usize, text : &str) -> Result<Option<LineDiagnostic>, crate::Error> {
+            let line = self.lines.get_mut(index).ok_or_else(|| SyError::interpreter(format!("Invalid line index! (Index: {})", 
+                index)))?;
+            *line = text.to_owned();
+            Ok(Self::check_syntax(index, text))
+        }
+
+        /// Inserts a line before the given index, returns the syntax error of the new line if there is one
+        pub fn insert_line(&mut self, index : usize, text : &str) -> Result<Option<LineDiagnostic>, crate::Error> {
+            if index > self.lines.len() {
+                return Err(SyError::interpreter(format!("Invalid line index! (Index: {}, Lines: {})", 
+                    index, self.lines.len())).into());
+            }
+
+            self.lines.insert(index, text.to_owned());
+            Ok(Self::check_syntax(index, text))
+        }
+
+        /// Removes the line with the given index
+        pub fn remove_line(&mut self, index : usize) -> Result<String, crate::Error> {
+            if index >= self.lines.len() {
+                return Err(SyError::interpreter(format!("Invalid line index! (Index: {}, Lines: {})", 
+                    index, self.lines.len())).into());
+            }
+
+            Ok(self.lines.remove(index))
+        }
+    // 
+
+    // Validation
+        fn check_syntax(index : usize, text : &str) -> Option<LineDiagnostic> {
+            parse_line(text).err().map(|err| LineDiagnostic { line: index, msg: err.to_string() })
+        }
+
+        /// Checks the syntax of all lines
+        pub fn check(&self) -> Vec<LineDiagnostic> {
+            self.lines.iter().enumerate().filter_map(|(i, l)| Self::check_syntax(i, l)).collect()
+        }
+
+        /// Checks the syntax of all lines and the reachability of every move (`G0` - `G3`, arcs are checked with points 
+        /// spaced by `seg_len`), starting at the current TCP position of the descriptor. The modal state (e.g. `G91`, 
+        /// `G18`, `G20`) is tracked across the lines
+        pub fn validate<R, G, T, D, const C : usize>(&self, rob : &R, desc : &D, seg_len : f32) -> Vec<LineDiagnostic> 
+        where
+            R : Robot<G, T, C>,
+            G : SyncActuatorGroup<T, C>,
+            T : SyncActuator + DefinedActuator + ?Sized + 'static,
+            D : Descriptor<C>
+        {
+            let mut diags = Vec::new();
+            let mut pos = desc.tcp().pos();
+            let mut modal = ModalState::default();
+
+            for (i, line) in self.lines.iter().enumerate() {
+                let words = match parse_line(line) {
+                    Ok(words) => words,
+                    Err(err) => {
+                        diags.push(LineDiagnostic { line: i, msg: err.to_string() });
+                        continue;
+                    }
+                };
+
+                if let Some(Err(err)) = m220_factor(&words) {
+                    diags.push(LineDiagnostic { line: i, msg: err.to_string() });
+                }
+
+                if let Err(err) = modal.update(&words) {
+                    diags.push(LineDiagnostic { line: i, msg: err.to_string() });
+                    continue;
+                }
+
+                let points = match motion_points(&words, &modal, pos, seg_len) {
+                    Some(Ok(points)) => points,
+                    Some(Err(err)) => {
+                        diags.push(LineDiagnostic { line: i, msg: err.to_string() });
+                        continue;
+                    },
+                    None => continue
+                };
+
+                let unreachable = points.iter().find_map(|p| desc.phis_for_pos(Position::from_vec3(*p))
+                    .and_then(|phis| rob.valid_phis(&phis)).err().map(|err| (*p, err)));
+
+                match unreachable {
+                    None => pos = points.last().copied().unwrap_or(pos),
+                    Some((p, err)) => diags.push(LineDiagnostic { 
+                        line: i, 
+                        msg: format!("Position {:?} is not reachable! ({})", p.to_array(), err) 
+                    })
+                }
+            }
+
+            diags
+        }
+    //
+}
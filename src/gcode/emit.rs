@@ -0,0 +1,199 @@
+use core::fmt::Write;
+
+use glam::Vec3;
+
+use crate::SyError;
+use crate::prog::{Program, Step};
+use crate::rcs::Point;
+
+/// Length units of the gcode emitted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Units {
+    /// Millimeters (`G21`)
+    #[default]
+    Millimeters,
+    /// Inches (`G20`)
+    Inches
+}
+
+impl Units {
+    /// Converts a length in millimeters into the unit
+    pub fn from_mm(self, mm : f32) -> f32 {
+        match self {
+            Self::Millimeters => mm,
+            Self::Inches => mm / 25.4
+        }
+    }
+
+    /// Converts a length given in the unit into millimeters
+    pub fn to_mm(self, value : f32) -> f32 {
+        match self {
+            Self::Millimeters => value,
+            Self::Inches => value * 25.4
+        }
+    }
+
+    /// The gcode selecting the unit
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Millimeters => "G21",
+            Self::Inches => "G20"
+        }
+    }
+}
+
+/// Options of the gcode output
+#[derive(Clone, Debug)]
+pub struct EmitOptions {
+    /// The length units used
+    pub units : Units,
+    /// The work offset selected (`1` to `6` for `G54` to `G59`), `None` to use machine coordinates
+    pub work_offset : Option<u8>,
+    /// The origin of the work offset in machine coordinates (millimeters), subtracted from all positions
+    pub origin : Vec3,
+    /// Feed rate for linear moves in units per minute, `None` to omit the feed rate
+    pub feed : Option<f32>,
+    /// Number of decimal places of coordinates
+    pub precision : usize
+}
+
+impl Default for EmitOptions {
+    fn default() -> Self {
+        Self {
+            units: Units::Millimeters,
+            work_offset: None,
+            origin: Vec3::ZERO,
+            feed: None,
+            precision: 3
+        }
+    }
+}
+
+/// Generates gcode text, the lines written start with a header selecting the units and the work offset
+pub struct Emitter {
+    opts : EmitOptions,
+    out : String
+}
+
+impl Emitter {
+    /// Creates a new emitter, fails if the work offset is invalid
+    pub fn new(opts : EmitOptions) -> Result<Self, crate::Error> {
+        let mut out = String::new();
+        writeln!(out, "{} G90", opts.units.code())?;
+
+        if let Some(offset) = opts.work_offset {
+            if !(1 ..= 6).contains(&offset) {
+                return Err(SyError::config(format!("Invalid work offset! Must be between 1 (G54) and 6 (G59) (Offset: {})", 
+                    offset)).into());
+            }
+
+            writeln!(out, "G{}", 53 + offset)?;
+        }
+
+        Ok(Self { opts, out })
+    }
+
+    fn coords(&self, pos : Vec3) -> String {
+        let pos = pos - self.opts.origin;
+        let p = self.opts.precision;
+
+        format!("X{:.p$} Y{:.p$} Z{:.p$}", 
+            self.opts.units.from_mm(pos.x), self.opts.units.from_mm(pos.y), self.opts.units.from_mm(pos.z))
+    }
+
+    /// Writes a rapid move (`G0`) to the given position
+    pub fn rapid(&mut self, pos : Vec3) -> Result<(), crate::Error> {
+        let coords = self.coords(pos);
+        writeln!(self.out, "G0 {}", coords)?;
+        Ok(())
+    }
+
+    /// Writes a linear move (`G1`) to the given position
+    pub fn linear(&mut self, pos : Vec3) -> Result<(), crate::Error> {
+        let coords = self.coords(pos);
+
+        if let Some(feed) = self.opts.feed {
+            writeln!(self.out, "G1 {} F{:.1}", coords, feed)?;
+        } else {
+            writeln!(self.out, "G1 {}", coords)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a comment
+    pub fn comment(&mut self, msg : &str) -> Result<(), crate::Error> {
+        writeln!(self.out, "; {}", msg)?;
+        Ok(())
+    }
+
+    /// Writes a path, the first point is approached with a rapid move, all others with linear moves
+    pub fn path(&mut self, path : &[Vec3]) -> Result<(), crate::Error> {
+        if let Some((first, rest)) = path.split_first() {
+            self.rapid(*first)?;
+
+            for pos in rest {
+                self.linear(*pos)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a program, joint movements cannot be expressed in cartesian gcode and are written as comments
+    pub fn program<const C : usize>(&mut self, prog : &Program<C>) -> Result<(), crate::Error> {
+        self.comment(&format!("Program '{}'", prog.name))?;
+
+        for step in &prog.steps {
+            match step {
+                Step::MoveP { pos, .. } => self.linear(*pos.pos())?,
+                Step::MoveJ { deltas, .. } => self.comment(&format!("MoveJ {:?}", deltas))?,
+                Step::MoveAbsJ { phis, .. } => self.comment(&format!("MoveAbsJ {:?}", phis))?,
+                Step::ToolChange(Some(id)) => writeln!(self.out, "M6 T{}", id)?,
+                Step::ToolChange(None) => self.comment("Tool removed")?,
+                Step::ToolOn => writeln!(self.out, "M3")?,
+                Step::ToolOff => writeln!(self.out, "M5")?,
+                Step::Output { name, state } => self.comment(&format!("Output '{}' {}", name, if *state { "on" } else { "off" }))?,
+                Step::Dwell(time) => writeln!(self.out, "G4 P{}", time.as_millis())?,
+                Step::Count(event) => self.comment(&format!("Count {:?}", event))?,
+                Step::Tuning(name) => self.comment(&format!("Tuning '{}'", name))?,
+                Step::Conditioned { pre, motion, post } => {
+                    for cond in pre {
+                        self.comment(&format!("Before {:?}", cond))?;
+                    }
+
+                    if let Step::MoveP { pos, .. } = motion.as_ref() {
+                        self.linear(*pos.pos())?;
+                    } else {
+                        self.comment(&format!("{:?}", motion))?;
+                    }
+
+                    for cond in post {
+                        self.comment(&format!("After {:?}", cond))?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the output, returning the gcode text
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Converts a path into gcode text, see `Emitter::path`
+pub fn emit_path(path : &[Vec3], opts : EmitOptions) -> Result<String, crate::Error> {
+    let mut emitter = Emitter::new(opts)?;
+    emitter.path(path)?;
+    Ok(emitter.finish())
+}
+
+/// Converts a program into gcode text, see `Emitter::program`
+pub fn emit_program<const C : usize>(prog : &Program<C>, opts : EmitOptions) -> Result<String, crate::Error> {
+    let mut emitter = Emitter::new(opts)?;
+    emitter.program(prog)?;
+    Ok(emitter.finish())
+}
@@ -0,0 +1,74 @@
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Robot, SyError};
+use crate::gcode::word::{self, has_code, Word};
+use crate::robs::SpeedOverride;
+
+/// Returns the speed override factor set by a `M220` line, `None` if the line is no `M220` command
+/// 
+/// `M220 S<percent>` sets the override in percent, `M220` without `S` resets it to 100%
+pub fn m220_factor(words : &[Word]) -> Option<Result<f32, crate::Error>> {
+    if !has_code(words, 'M', 220) {
+        return None;
+    }
+
+    let factor = word::num(words, 'S').map_or(1.0, |s| s / 100.0);
+
+    if (0.0 ..= SpeedOverride::MAX).contains(&factor) {
+        Some(Ok(factor))
+    } else {
+        Some(Err(SyError::interpreter(format!("Invalid speed override! Must be between 0 and {}% (S: {})", 
+            SpeedOverride::MAX * 100.0, factor * 100.0)).into()))
+    }
+}
+
+/// Returns the channel and state set by a `M42` line, `None` if the line is no `M42` command
+/// 
+/// `M42 P<channel> S<state>` sets the output channel of the tool, any non-zero `S` value turns the channel on
+pub fn m42_output(words : &[Word]) -> Option<Result<(usize, bool), crate::Error>> {
+    if !has_code(words, 'M', 42) {
+        return None;
+    }
+
+    let Some(channel) = word::num(words, 'P') else {
+        return Some(Err(SyError::interpreter("The M42 command requires a channel (P)!").into()));
+    };
+
+    if (channel < 0.0) | (channel.fract() != 0.0) {
+        return Some(Err(SyError::interpreter(format!("The channel of the M42 command must be a non-negative integer! (P: {})", 
+            channel)).into()));
+    }
+
+    let state = word::num(words, 'S').map_or(true, |s| s != 0.0);
+
+    Some(Ok((channel as usize, state)))
+}
+
+/// Executes the machine commands of a line changing the state of the robot (`M42`, `M220`), returns wheiter the line 
+/// contained such a command
+pub fn exec_mcode<R, G, T, const C : usize>(rob : &mut R, words : &[Word]) -> Result<bool, crate::Error> 
+where
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static
+{
+    if let Some(factor) = m220_factor(words) {
+        rob.set_speed_override(factor?)?;
+        return Ok(true);
+    }
+
+    if let Some(output) = m42_output(words) {
+        let (channel, state) = output?;
+
+        rob.get_tool_mut()
+            .ok_or_else(|| SyError::component("No tool has been equipped yet!"))?
+            .output_tool_mut()
+            .ok_or_else(|| SyError::component("The tool equipped has no output channels!"))?
+            .set_channel(channel, state)?;
+
+        return Ok(true);
+    }
+
+    Ok(false)
+}
@@ -0,0 +1,135 @@
+use glam::Vec3;
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, Robot, SyError};
+use crate::gcode::arc::{arc_points, move_arc, ArcCenter};
+use crate::gcode::word::{self, Word};
+use crate::rcs::{Linear, Point, Position};
+use crate::sender::ModalState;
+
+/// The motion modes of gcode, modal until another one is selected
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Motion {
+    /// Rapid movement (`G0`)
+    #[default]
+    Rapid,
+    /// Linear movement (`G1`)
+    Linear,
+    /// Clockwise arc (`G2`)
+    ArcCw,
+    /// Counterclockwise arc (`G3`)
+    ArcCcw
+}
+
+impl Motion {
+    /// Returns the motion mode selected by the given G-code number (`0` to `3`)
+    pub fn from_gcode(code : u32) -> Option<Self> {
+        match code {
+            0 => Some(Self::Rapid),
+            1 => Some(Self::Linear),
+            2 => Some(Self::ArcCw),
+            3 => Some(Self::ArcCcw),
+            _ => None
+        }
+    }
+
+    /// Returns wheiter the motion is an arc (`G2` / `G3`)
+    pub fn is_arc(self) -> bool {
+        matches!(self, Self::ArcCw | Self::ArcCcw)
+    }
+}
+
+/// Returns the center of the arc given by the line, either by the offsets `I`, `J` and `K` from the start point or
+/// by the radius `R`. Lengths are converted from the `units` of the program (see `ModalState`)
+pub fn arc_center(words : &[Word], modal : &ModalState) -> Result<ArcCenter, crate::Error> {
+    if let Some(r) = word::num(words, 'R') {
+        return Ok(ArcCenter::Radius(modal.units.to_mm(r)));
+    }
+
+    let offset = [ 'I', 'J', 'K' ].map(|letter| word::num(words, letter));
+
+    if offset.iter().all(Option::is_none) {
+        return Err(SyError::interpreter("Arcs require a center offset (I, J, K) or a radius (R)!").into());
+    }
+
+    Ok(ArcCenter::Offset(Vec3::from_array(offset.map(|v| modal.units.to_mm(v.unwrap_or(0.0))))))
+}
+
+/// Returns the target of a motion line, coordinates missing are taken from `start`, relative coordinates (`G91`)
+/// are added to it
+pub fn motion_target(words : &[Word], modal : &ModalState, start : Vec3) -> Vec3 {
+    let mut target = start.to_array();
+
+    for (i, letter) in [ 'X', 'Y', 'Z' ].into_iter().enumerate() {
+        if let Some(value) = word::num(words, letter) {
+            let value = modal.units.to_mm(value);
+            target[i] = if modal.relative { start[i] + value } else { value };
+        }
+    }
+
+    Vec3::from_array(target)
+}
+
+/// Returns the points the TCP moves along for a motion line in the motion mode of `modal`, `None` if the line
+/// contains no coordinates. Linear movements (`G0` / `G1`) consist of the target only, arcs (`G2` / `G3`) are
+/// split into segments of at most `seg_len` in the plane selected (see `arc_points()`)
+///
+/// The `modal` state has to be updated with the line first (see `ModalState::update()`)
+pub fn motion_points(words : &[Word], modal : &ModalState, start : Vec3, seg_len : f32)
+-> Option<Result<Vec<Vec3>, crate::Error>> {
+    if !words.iter().any(|w| "XYZIJKR".contains(w.letter)) {
+        return None;
+    }
+
+    let end = motion_target(words, modal, start);
+
+    if !modal.motion.is_arc() {
+        return Some(Ok(vec![ end ]));
+    }
+
+    Some(arc_center(words, modal).and_then(|center|
+        arc_points(start, end, center, modal.motion == Motion::ArcCw, modal.plane, seg_len)
+    ))
+}
+
+/// Executes a motion line (`G0` - `G3`) with the modal state given, starting at the current TCP position in program
+/// coordinates. Returns wheiter the line contained a movement
+///
+/// Rapids (`G0`) and arcs are driven point to point with `speed_f`, linear movements (`G1`) follow a straight line 
+/// (see `Robot::move_path()`) with the modal feed rate (see `ModalState::feed_speed()`), spaced by `seg_len` at most
+///
+/// Targets given by frames and named positions are resolved by `resolve_target()` instead
+pub async fn exec_motion<R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, words : &[Word], modal : &ModalState,
+    seg_len : f32, speed_f : Factor) -> Result<bool, crate::Error>
+where
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    let start = *rob.tcp_pose(desc).pos() - rob.vars().scope.offset;
+
+    let Some(points) = motion_points(words, modal, start, seg_len) else {
+        return Ok(false);
+    };
+    let points = points?;
+
+    match modal.motion {
+        Motion::Linear => {
+            let speed = modal.feed_speed()
+                .ok_or_else(|| SyError::interpreter("Linear movements (G1) require a feed rate (F)!"))?;
+
+            for point in points {
+                rob.move_path(desc, &Linear, Position::from_vec3(point), seg_len, speed).await?;
+            }
+        },
+        Motion::ArcCw | Motion::ArcCcw => move_arc(rob, desc, &points, speed_f).await?,
+        Motion::Rapid => for point in points {
+            rob.move_p_sync(desc, Position::from_vec3(point), speed_f).await?;
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,59 @@
+use glam::Vec3;
+
+use crate::SyError;
+use crate::gcode::motion::motion_target;
+use crate::gcode::word::{Word, WordValue};
+use crate::rcs::{work_offset_frame, Point, WorldObj};
+use crate::sender::ModalState;
+
+/// Returns the work offset selected by the line, `Some(None)` for machine coordinates (`G53`), `Some(Some(frame))` 
+/// for the frames of `G54` to `G59` (see `WORK_OFFSETS`) and `None` if the line selects none. Selections are modal,
+/// the last one is kept by the `ModalState`
+pub fn work_offset(words : &[Word]) -> Option<Option<&'static str>> {
+    words.iter().find_map(|w| match (&w.value, w.letter) {
+        (WordValue::Num(n), 'G') if *n == 53.0 => Some(None),
+        (WordValue::Num(n), 'G') if n.fract() == 0.0 => work_offset_frame(*n as u16).map(Some),
+        _ => None
+    })
+}
+
+/// Resolves the target position of a move (e.g. `G0`) in world coordinates
+/// 
+/// - `F"frame"` selects a frame (a point path in the world object), all coordinates are given relative to it
+/// - `G54` to `G59` select the work offset frames (see `WORK_OFFSETS`) the same way, the selection is modal (see 
+///   `ModalState::work_offset`)
+/// - `P"name"` references a named position inside the selected frame (or the world), used as base of the target
+/// - `X`, `Y` and `Z` set the coordinates in the frame, missing coordinates are taken from the base, which is the 
+///   named position or the `current` position of the TCP. The coordinates are converted from the units of the program
+///   and added to the base in relative mode (`G91`), the same as for motion lines (see `motion_target()`)
+///
+/// The `modal` state has to be updated with the line first (see `ModalState::update()`). The target is returned in 
+/// program coordinates, the offset of the program scope is added when moving to it (see `Robot::resolve_target()`), 
+/// so `current` has to be given without the offset as well
+pub fn resolve_target(words : &[Word], wobj : &WorldObj, current : Vec3, modal : &ModalState) -> Result<Vec3, crate::Error> {
+    // Numeric words with the same letter (e.g. the feed rate `F`) are skipped
+    let text = |letter : char| words.iter().filter(|w| w.letter == letter).find_map(Word::text);
+
+    let frame = text('F').or(modal.work_offset);
+
+    let (origin, ori) = match frame {
+        Some(frame) => {
+            let pose = wobj.global_pose(frame).ok_or_else(|| SyError::interpreter(format!("No frame named '{}'!", frame)))?;
+            (*pose.pos(), *pose.ori())
+        },
+        None => (Vec3::ZERO, glam::Mat3::IDENTITY)
+    };
+
+    let base = match text('P') {
+        Some(name) => {
+            // Named positions are looked up inside the frame first, then in the world
+            let pose = frame.and_then(|frame| wobj.global_pose(format!("{}/{}", frame, name)))
+                .or_else(|| wobj.global_pose(name))
+                .ok_or_else(|| SyError::interpreter(format!("No position named '{}'!", name)))?;
+            ori.transpose() * (*pose.pos() - origin)
+        },
+        None => ori.transpose() * (current - origin)
+    };
+
+    Ok(origin + ori * motion_target(words, modal, base))
+}
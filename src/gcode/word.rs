@@ -0,0 +1,109 @@
+use crate::SyError;
+
+/// The value of a gcode word
+#[derive(Clone, Debug, PartialEq)]
+pub enum WordValue {
+    /// A number, e.g. `X10.5`
+    Num(f32),
+    /// A quoted string, e.g. `F"fixture1"`
+    Str(String)
+}
+
+/// A single word of a gcode line, e.g. `X10.5` or `P"slot"`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Word {
+    /// The letter of the word, always uppercase
+    pub letter : char,
+    /// The value of the word
+    pub value : WordValue
+}
+
+impl Word {
+    /// Returns the numeric value of the word, `None` for strings
+    pub fn num(&self) -> Option<f32> {
+        match self.value {
+            WordValue::Num(num) => Some(num),
+            WordValue::Str(_) => None
+        }
+    }
+
+    /// Returns the text of the word, `None` for numbers
+    pub fn text(&self) -> Option<&str> {
+        match &self.value {
+            WordValue::Str(text) => Some(text),
+            WordValue::Num(_) => None
+        }
+    }
+
+    /// Checks if the word is the given code, e.g. `('G', 2)` for `G2`
+    pub fn is_code(&self, letter : char, code : u32) -> bool {
+        (self.letter == letter) & (self.num() == Some(code as f32))
+    }
+}
+
+/// Returns the numeric value of the first word with the given letter
+pub fn num(words : &[Word], letter : char) -> Option<f32> {
+    words.iter().find(|w| w.letter == letter).and_then(Word::num)
+}
+
+/// Returns the text of the first word with the given letter
+pub fn text(words : &[Word], letter : char) -> Option<&str> {
+    words.iter().find(|w| w.letter == letter).and_then(Word::text)
+}
+
+/// Checks if the line contains the given code, e.g. `('M', 220)`
+pub fn has_code(words : &[Word], letter : char, code : u32) -> bool {
+    words.iter().any(|w| w.is_code(letter, code))
+}
+
+/// Parses a gcode line into its words, the single parser used by the editor, the executor and all the commands.
+/// Comments (`;` and `(...)`) are removed, values are numbers or quoted strings (e.g. `F"fixture1"`). Returns an
+/// error describing the first invalid word
+pub fn parse_line(line : &str) -> Result<Vec<Word>, crate::Error> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+    let mut depth = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            ';' if depth == 0 => break,
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            ')' => return Err(SyError::interpreter("Unmatched ')'").into()),
+            _ if (depth > 0) | c.is_whitespace() => { },
+            _ if !c.is_ascii_alphabetic() =>
+                return Err(SyError::interpreter(format!("Invalid character '{}', words must start with a letter", c)).into()),
+            _ => {
+                let letter = c.to_ascii_uppercase();
+
+                if chars.next_if_eq(&'"').is_some() {
+                    let mut text = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => text.push(c),
+                            None => return Err(SyError::interpreter(format!("Unclosed string in word '{}'", letter)).into())
+                        }
+                    }
+
+                    words.push(Word { letter, value: WordValue::Str(text) });
+                } else {
+                    let mut num = String::new();
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() & !c.is_ascii_alphabetic() & !"(;".contains(*c)) {
+                        num.push(c);
+                    }
+
+                    let value = num.parse::<f32>()
+                        .map_err(|_| SyError::interpreter(format!("Invalid value '{}' in word '{}'", num, letter)))?;
+                    words.push(Word { letter, value: WordValue::Num(value) });
+                }
+            }
+        }
+    }
+
+    if depth > 0 {
+        return Err(SyError::interpreter("Unclosed comment '('").into());
+    }
+
+    Ok(words)
+}
@@ -11,17 +11,68 @@ extern crate alloc;
 // ####################
 // #    SUBMODULES    #
 // ####################
+    /// Cancellation tokens for long-running operations
+    pub mod cancel;
+
+    /// Catalog of messages keyed by stable codes, for rendering errors and prompts in different languages
+    pub mod catalog;
+
+    /// Command channel for driving the robot from multiple producers
+    pub mod cmd;
+
     /// Configurations for the robot in terms of position, speed and mode
     pub mod config;
 
+    /// Software emergency stop
+    pub mod estop;
+
+    /// Typed state events and subscriptions
+    pub mod events;
+
+    /// Gcode generation
+    pub mod gcode;
+
+    /// Collision-free path planning in joint space
+    pub mod plan;
+
     /// Quick and easy import of the library essentials
     pub mod prelude; 
 
+    /// Programs interleaving motion with tool changes, I/O and dwell actions
+    pub mod prog;
+
     /// RCS (Robot-Coordinate-System) module, manages the coordinate system and positions
     pub mod rcs;
 
+    /// ROS 2 bridge for robot state and commands
+    #[cfg(feature = "ros2")]
+    pub mod ros2;
+
+    /// Interactive REPL front end for interpreters
+    #[cfg(feature = "repl")]
+    pub mod repl;
+
+    /// Shared tokio runtime for robots, remotes and interpreters
+    #[cfg(feature = "runtime")]
+    pub mod rt;
+
+    /// Resumable program sender with progress reporting, execution control and program storage
+    pub mod sender;
+
+    /// In-memory telemetry buffer and streaming to clients
+    pub mod telemetry;
+
+    /// Time-parameterized trajectories
+    pub mod traj;
+
     #[cfg(test)]
     pub mod tests;
+
+    // Dependencies used by the exported macros, so downstream crates do not require them in scope
+    #[doc(hidden)]
+    pub use syact as __syact;
+    #[doc(hidden)]
+    pub use syunit as __syunit;
 //
 
 // ########################
@@ -43,17 +94,108 @@ extern crate alloc;
 // ################
 // #    ERRORS    #
 // ################
-    /// Universal error type used in the crate
-    pub type Error = Box<dyn std::error::Error>;
+    mod error;
+    pub use error::{Error, ErrorKind, SyError};
 //
 
 // Remotes
-    /// Different types of events that can occur
+    /// Different types of events that can occur, serialized as JSON object tagged with `type` (see `to_json()`)
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
     pub enum PushMsg {
         /// The robot has conducted a measurement
-        Measurement,
+        Measurement {
+            /// The results of the measurement for each axis, `None` for axes that have not been measured
+            results : Vec<Option<f32>>
+        },
         /// The robot has undergone a tool change
-        ToolChange
+        ToolChange {
+            /// The id of the previous tool
+            old : Option<usize>,
+            /// The id of the new tool
+            new : Option<usize>
+        },
+        /// Tools have exceeded their maintenance thresholds
+        ToolMaintenance {
+            /// The ids of the tools requiring maintenance
+            tools : Vec<usize>
+        },
+        /// An error occured
+        Error {
+            /// The code of the `ErrorKind`
+            code : u16,
+            /// Description of the error
+            msg : String
+        }
+    }
+
+    impl PushMsg {
+        /// Creates an error message, classified with `ErrorKind::of`
+        pub fn error(err : &crate::Error) -> Self {
+            Self::Error { code: ErrorKind::of(err).code(), msg: err.to_string() }
+        }
+
+        /// Returns the name of the message type, equal to the `type` field of the JSON object
+        pub fn msg_type(&self) -> &'static str {
+            match self {
+                Self::Measurement { .. } => "measurement",
+                Self::ToolChange { .. } => "tool_change",
+                Self::ToolMaintenance { .. } => "tool_maintenance",
+                Self::Error { .. } => "error"
+            }
+        }
+
+        /// Serializes the message as JSON, the format used by all remotes
+        pub fn to_json(&self) -> Result<String, crate::Error> {
+            Ok(serde_json::to_string(self)?)
+        }
+
+        /// Serializes the message as JSON like `to_json()`, adding the identity of the sender as `source` field
+        pub fn to_json_from(&self, identity : &Identity) -> Result<String, crate::Error> {
+            let mut value = serde_json::to_value(self)?;
+
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("source".to_owned(), serde_json::to_value(identity)?);
+            }
+
+            Ok(serde_json::to_string(&value)?)
+        }
+    }
+
+    /// The identity of a robot or station, distinguishing multiple controllers publishing to the same broker or server
+    #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct Identity {
+        /// Name of the robot or station
+        pub name : String,
+        /// Serial number of the controller, required if multiple robots share the same name
+        #[serde(default)]
+        pub serial : Option<String>
+    }
+
+    impl Identity {
+        /// Creates a new identity without serial number
+        pub fn new<S : Into<String>>(name : S) -> Self {
+            Self { name: name.into(), serial: None }
+        }
+
+        /// Sets the serial number of the identity
+        pub fn serial<S : Into<String>>(mut self, serial : S) -> Self {
+            self.serial = Some(serial.into());
+            self
+        }
+
+        /// Returns the namespace of the identity, `name` or `name/serial`
+        pub fn namespace(&self) -> String {
+            match &self.serial {
+                Some(serial) => format!("{}/{}", self.name, serial),
+                None => self.name.clone()
+            }
+        }
+
+        /// Returns the topic path of a message type inside the namespace, e.g. `arm/0042/tool_change`
+        pub fn topic(&self, msg_type : &str) -> String {
+            format!("{}/{}", self.namespace(), msg_type)
+        }
     }
 
     /// A `PushRemote` defines a remote connection that the robot can push values to
@@ -61,11 +203,50 @@ extern crate alloc;
         /// Publish a set of phis to the remote connection
         fn push_phis(&mut self, phis : &[Phi]) -> Result<(), crate::Error>;
 
-        /// Publish a new `PushMsg`
-        fn push_other(&mut self, other : PushMsg) -> Result<(), crate::Error>;
+        /// Publish a new `PushMsg`, by default the message is serialized with `PushMsg::to_json()` and published
+        /// with `push_any()`, using `PushMsg::msg_type()` as type
+        fn push_other(&mut self, other : PushMsg) -> Result<(), crate::Error> {
+            self.push_any(other.msg_type(), other.to_json()?.as_bytes())
+        }
 
         /// Publish any type via bytes
         fn push_any(&mut self, msg_type : &str, msg : &[u8]) -> Result<(), crate::Error>;
+
+        // Identity
+            /// Publish a set of phis sent by the robot with the given identity. Without identity `push_phis()` is used,
+            /// otherwise the phis are published as JSON object with `source` field on the topic of the identity
+            fn push_phis_from(&mut self, identity : Option<&Identity>, phis : &[Phi]) -> Result<(), crate::Error> {
+                let Some(identity) = identity else {
+                    return self.push_phis(phis);
+                };
+
+                let msg = serde_json::json!({
+                    "phis": phis.iter().map(|p| p.0).collect::<Vec<f32>>(),
+                    "source": identity
+                });
+
+                self.push_any(&identity.topic("phis"), msg.to_string().as_bytes())
+            }
+
+            /// Publish a `PushMsg` sent by the robot with the given identity. Without identity `push_other()` is used,
+            /// otherwise the message is serialized with `PushMsg::to_json_from()` and published on the topic of the
+            /// identity (see `Identity::topic()`)
+            fn push_other_from(&mut self, identity : Option<&Identity>, other : PushMsg) -> Result<(), crate::Error> {
+                match identity {
+                    Some(identity) => self.push_any(&identity.topic(other.msg_type()), other.to_json_from(identity)?.as_bytes()),
+                    None => self.push_other(other)
+                }
+            }
+
+            /// Publish any type via bytes sent by the robot with the given identity, the type is prefixed with the
+            /// namespace of the identity
+            fn push_any_from(&mut self, identity : Option<&Identity>, msg_type : &str, msg : &[u8]) -> Result<(), crate::Error> {
+                match identity {
+                    Some(identity) => self.push_any(&identity.topic(msg_type), msg),
+                    None => self.push_any(msg_type, msg)
+                }
+            }
+        //
     }
 // 
 
@@ -81,9 +262,55 @@ extern crate alloc;
         /// Interpret a code string for a given robot
         fn interpret(&self, rob : &mut R, desc : &mut D, stat : &mut S, code : &str) -> Vec<O>; 
 
+        /// Returns the planned TCP velocity profile of a code string without executing it, used by front ends to plot 
+        /// the feed behavior per line. Interpreters planning their movements should override this method, the default 
+        /// returns `None`
+        fn velocity_profile(&self, _rob : &R, _desc : &mut D, _code : &str) -> Option<traj::VelocityProfile> {
+            None
+        }
+
         /// Interpret a file for a given robot
         fn interpret_file(&self, rob : &mut R, desc : &mut D, stat : &mut S, path : &str) -> Vec<O> {
             self.interpret(rob, desc, stat, std::fs::read_to_string(path).unwrap().as_str())
         }
+
+        /// Runs the program with the given name from the store line by line, `on_progress` is called after every line. The
+        /// program runs in its own scope (see `Robot::push_scope()`), the tool mounted is verified before the first line
+        /// (see `Station::verify_tool()`), the tool usage is stored in the station after every line (see 
+        /// `Station::store_tool_usage()`). The program stops once the token of the robot is cancelled
+        fn run_program<F : FnMut(&sender::Progress)>(&self, store : &sender::ProgramStore, name : &str, rob : &mut R, 
+            desc : &mut D, stat : &mut S, mut on_progress : F) -> Result<Vec<O>, crate::Error> 
+        where 
+            Self : Sized,
+            S : Station<G, T, C, Robot = R>
+        {
+            let mut sender = store.sender(name)?;
+            let mut outputs = Vec::new();
+
+            stat.verify_tool(rob)?;
+
+            // Offsets and speed limits of the program must not leak into the next one
+            rob.push_scope();
+
+            let res = loop {
+                if let Some(Err(err)) = rob.cancel_token().map(|c| c.check()) {
+                    sender.stop();
+                    break Err(err);
+                }
+
+                match sender.send_next(self, rob, desc, stat) {
+                    Ok(Some(res)) => {
+                        outputs.extend(res);
+                        stat.store_tool_usage(rob);
+                        on_progress(&sender.progress());
+                    },
+                    Ok(None) => break Ok(()),
+                    Err(err) => break Err(err)
+                }
+            };
+
+            rob.pop_scope();
+            res.map(|_| outputs)
+        }
     }
 // 
\ No newline at end of file
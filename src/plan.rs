@@ -0,0 +1,221 @@
+use syunit::*;
+
+use crate::SyError;
+use crate::robs::CollisionCheck;
+
+/// Configuration of a `RrtConnect` planner
+#[derive(Clone, Copy, Debug)]
+pub struct RrtConfig {
+    /// Maximum joint distance the trees are extended by in one step
+    pub step : f32,
+    /// Maximum joint distance between two poses checked for collisions along an edge
+    pub resolution : f32,
+    /// Maximum number of samples drawn before the planning fails
+    pub max_iter : usize,
+    /// Number of shortcut attempts when smoothing the path
+    pub smooth_iter : usize,
+    /// Seed of the random samples, plans are reproducible for the same seed
+    pub seed : u64
+}
+
+impl Default for RrtConfig {
+    fn default() -> Self {
+        Self {
+            step: 0.2,
+            resolution: 0.05,
+            max_iter: 5000,
+            smooth_iter: 100,
+            seed: 0x5EED
+        }
+    }
+}
+
+/// Small xorshift generator, planning does not require a cryptographic source
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed : u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    /// Returns a random value in `[0, 1)`
+    fn sample(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+fn dist<const C : usize>(a : &[Phi; C], b : &[Phi; C]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a.0 - b.0).powi(2)).sum::<f32>().sqrt()
+}
+
+fn lerp<const C : usize>(a : &[Phi; C], b : &[Phi; C], t : f32) -> [Phi; C] {
+    let mut phis = *a;
+    for i in 0 .. C {
+        phis[i] = Phi(a[i].0 + (b[i].0 - a[i].0) * t);
+    }
+    phis
+}
+
+/// A tree of poses, each node storing the index of its parent
+struct Tree<const C : usize> {
+    nodes : Vec<([Phi; C], usize)>
+}
+
+impl<const C : usize> Tree<C> {
+    fn nearest(&self, phis : &[Phi; C]) -> usize {
+        (0 .. self.nodes.len()).min_by(|&a, &b| {
+            dist(&self.nodes[a].0, phis).total_cmp(&dist(&self.nodes[b].0, phis))
+        }).unwrap_or(0)
+    }
+
+    /// Returns the poses from the root to the given node
+    fn path(&self, mut index : usize) -> Vec<[Phi; C]> {
+        let mut path = vec![ self.nodes[index].0 ];
+
+        while index != 0 {
+            index = self.nodes[index].1;
+            path.push(self.nodes[index].0);
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Result of extending a tree towards a pose
+enum Extend {
+    Trapped,
+    Advanced(usize),
+    Reached(usize)
+}
+
+/// Collision-free joint space path planning between two poses using RRT-Connect with shortcut smoothing
+pub struct RrtConnect<'a, const C : usize> {
+    /// The range of each joint sampled, usually the limits of the robot
+    pub limits : [(Phi, Phi); C],
+    /// The collision check of the poses
+    pub check : CollisionCheck<'a, C>,
+    /// The configuration of the planner
+    pub conf : RrtConfig
+}
+
+impl<'a, const C : usize> RrtConnect<'a, C> {
+    /// Creates a new planner with the default configuration
+    pub fn new(limits : [(Phi, Phi); C], check : CollisionCheck<'a, C>) -> Self {
+        Self { limits, check, conf: RrtConfig::default() }
+    }
+
+    fn pose_free(&self, phis : &[Phi; C]) -> bool {
+        phis.iter().zip(&self.limits).all(|(p, (min, max))| (min.0 <= p.0) & (p.0 <= max.0))
+            & (self.check)(phis).is_none()
+    }
+
+    /// Checks the straight joint space motion between two poses
+    pub fn motion_free(&self, a : &[Phi; C], b : &[Phi; C]) -> bool {
+        let n = ((dist(a, b) / self.conf.resolution).ceil() as usize).max(1);
+        (1 ..= n).all(|s| self.pose_free(&lerp(a, b, s as f32 / n as f32)))
+    }
+
+    fn extend(&self, tree : &mut Tree<C>, target : &[Phi; C]) -> Extend {
+        let near = tree.nearest(target);
+        let from = tree.nodes[near].0;
+        let d = dist(&from, target);
+
+        let (new, reached) = if d <= self.conf.step {
+            (*target, true)
+        } else {
+            (lerp(&from, target, self.conf.step / d), false)
+        };
+
+        if !self.motion_free(&from, &new) {
+            return Extend::Trapped;
+        }
+
+        tree.nodes.push((new, near));
+        let index = tree.nodes.len() - 1;
+
+        if reached { Extend::Reached(index) } else { Extend::Advanced(index) }
+    }
+
+    fn connect(&self, tree : &mut Tree<C>, target : &[Phi; C]) -> Extend {
+        loop {
+            match self.extend(tree, target) {
+                Extend::Advanced(_) => continue,
+                res => return res
+            }
+        }
+    }
+
+    /// Plans a collision-free path from `start` to `goal`, including both. The path is smoothed afterwards
+    pub fn plan(&self, start : [Phi; C], goal : [Phi; C]) -> Result<Vec<[Phi; C]>, crate::Error> {
+        for (name, phis) in [ ("start", &start), ("goal", &goal) ] {
+            if !self.pose_free(phis) {
+                return Err(SyError::kinematics(format!("The {} pose of the path is in collision or out of the limits!", name)).into());
+            }
+        }
+
+        if self.motion_free(&start, &goal) {
+            return Ok(vec![ start, goal ]);
+        }
+
+        let mut rng = Rng::new(self.conf.seed);
+        let mut a = Tree { nodes: vec![ (start, 0) ] };
+        let mut b = Tree { nodes: vec![ (goal, 0) ] };
+        let mut swapped = false;
+
+        for _ in 0 .. self.conf.max_iter {
+            let mut sample = [Phi::ZERO; C];
+            for i in 0 .. C {
+                let (min, max) = self.limits[i];
+                sample[i] = Phi(min.0 + (max.0 - min.0) * rng.sample());
+            }
+
+            let new = match self.extend(&mut a, &sample) {
+                Extend::Trapped => None,
+                Extend::Advanced(i) | Extend::Reached(i) => Some(i)
+            };
+
+            if let Some(i_a) = new {
+                let target = a.nodes[i_a].0;
+
+                if let Extend::Reached(i_b) = self.connect(&mut b, &target) {
+                    let (tree_s, i_s, tree_g, i_g) = if swapped { (&b, i_b, &a, i_a) } else { (&a, i_a, &b, i_b) };
+
+                    let mut path = tree_s.path(i_s);
+                    let mut rest = tree_g.path(i_g);
+                    rest.reverse();
+                    path.extend(rest.into_iter().skip(1));
+
+                    return Ok(self.smooth(path, &mut rng));
+                }
+            }
+
+            core::mem::swap(&mut a, &mut b);
+            swapped = !swapped;
+        }
+
+        Err(SyError::kinematics(format!("No collision-free path found within {} samples!", self.conf.max_iter)).into())
+    }
+
+    /// Shortens the path by replacing random sections with straight motions if they are collision-free
+    fn smooth(&self, mut path : Vec<[Phi; C]>, rng : &mut Rng) -> Vec<[Phi; C]> {
+        for _ in 0 .. self.conf.smooth_iter {
+            if path.len() < 3 {
+                break;
+            }
+
+            let i = (rng.sample() * path.len() as f32) as usize;
+            let j = (rng.sample() * path.len() as f32) as usize;
+            let (i, j) = (i.min(j), i.max(j).min(path.len() - 1));
+
+            if (j > i + 1) && self.motion_free(&path[i], &path[j]) {
+                path.drain(i + 1 .. j);
+            }
+        }
+
+        path
+    }
+}
@@ -2,4 +2,4 @@ pub use crate::{Robot, Descriptor, Station, PushRemote};
 pub use crate::config::AngleConfig;
 pub use crate::desc::{KinElement, Movement, Rot, Kinematic, SerialKinematic};
 pub use crate::rcs::{Point, PointRef, Position, WorldObj};
-pub use crate::robs::{StepperRobot, Vars};
\ No newline at end of file
+pub use crate::robs::{StepperRobot, Tool, Vars};
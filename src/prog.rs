@@ -0,0 +1,323 @@
+use core::time::Duration;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, Robot, Station, SyError};
+use crate::rcs::Position;
+use crate::stat::{CounterEvent, Operator};
+
+// Submodules
+    mod approach;
+    pub use approach::{Approach, ApproachRetract};
+
+    mod cond;
+    pub use cond::Condition;
+
+    mod params;
+    pub use params::{Args, ParamDecl, ProgramTemplate, StepGen};
+
+    mod policy;
+    pub use policy::{ErrorPolicies, ErrorPolicy, OpClass};
+
+    mod sim;
+    pub use sim::{Diagnostic, DiagnosticKind, SimChecks};
+// 
+
+/// A single step of a `Program`
+#[derive(Clone, Debug)]
+pub enum Step<const C : usize> {
+    /// Relative joint movement
+    MoveJ { deltas : [Delta; C], speed_f : Factor },
+    /// Absolute joint movement
+    MoveAbsJ { phis : [Phi; C], speed_f : Factor },
+    /// Movement to a position
+    MoveP { pos : Position, speed_f : Factor },
+    /// Selects a new tool
+    ToolChange(Option<usize>),
+    /// Activates the current tool
+    ToolOn,
+    /// Deactivates the current tool
+    ToolOff,
+    /// Sets a named output of the station
+    Output { name : String, state : bool },
+    /// Waits for the given duration
+    Dwell(Duration),
+    /// Updates the production counters of the station
+    Count(CounterEvent),
+    /// Activates the named motion tuning profile for the following steps
+    Tuning(String),
+    /// Movement with conditions on the I/O of the station, applied right before and after the movement. Retries 
+    /// by the error policies repeat the whole step including the conditions
+    Conditioned { pre : Vec<Condition>, motion : Box<Step<C>>, post : Vec<Condition> }
+}
+
+impl<const C : usize> Step<C> {
+    /// Returns the class of operation of the step, used to select the `ErrorPolicy`
+    pub fn class(&self) -> Option<OpClass> {
+        match self.motion() {
+            Self::MoveJ { .. } | Self::MoveAbsJ { .. } | Self::MoveP { .. } => Some(OpClass::Motion),
+            Self::ToolChange(_) | Self::ToolOn | Self::ToolOff => Some(OpClass::Tool),
+            Self::Output { .. } => Some(OpClass::Io),
+            Self::Dwell(_) | Self::Count(_) | Self::Tuning(_) | Self::Conditioned { .. } => None
+        }
+    }
+
+    /// Creates a new conditioned movement, see `Step::Conditioned`
+    pub fn conditioned(pre : Vec<Condition>, motion : Step<C>, post : Vec<Condition>) -> Self {
+        Self::Conditioned { pre, motion: Box::new(motion), post }
+    }
+
+    /// Returns the step without its conditions, the step itself if it has none
+    pub fn motion(&self) -> &Step<C> {
+        match self {
+            Self::Conditioned { motion, .. } => motion,
+            _ => self
+        }
+    }
+
+    /// Returns the `Phi` values the robot reaches with the movement of the step when starting at `phis`, `None` for 
+    /// steps without movement. The deltas of `MoveJ` steps are `Gamma` deltas (see `Robot::move_j()`), so they are 
+    /// applied to the gammas, including offsets, mirroring and couplings
+    pub fn target<R, G, T, D>(&self, rob : &R, desc : &D, phis : [Phi; C]) -> Option<Result<[Phi; C], crate::Error>>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        match self.motion() {
+            Self::MoveJ { deltas, .. } => Some(Ok(rob.phis_from_gammas(add_unit_arrays(rob.gammas_from_phis(phis), *deltas)))),
+            Self::MoveAbsJ { phis, .. } => Some(Ok(*phis)),
+            Self::MoveP { pos, .. } => Some(desc.phis_for_pos(pos.clone())),
+            _ => None
+        }
+    }
+}
+
+/// A program interleaving motion segments with tool changes, I/O and dwell actions, validated and time-estimated as a unit
+#[derive(Clone, Debug, Default)]
+pub struct Program<const C : usize> {
+    /// Name of the program
+    pub name : String,
+    /// The steps of the program
+    pub steps : Vec<Step<C>>,
+    /// The arguments the program has been instantiated with, accessible for interpreters and scripts
+    pub args : Args,
+    /// The policies applied when a step fails
+    pub policies : ErrorPolicies,
+    /// The approach and retract moves inserted around the contact poses added by the builder (see `Program::contact()`)
+    pub approach : Option<ApproachRetract>
+}
+
+impl<const C : usize> Program<C> {
+    /// Creates a new empty program
+    pub fn new<S : Into<String>>(name : S) -> Self {
+        Self {
+            name: name.into(),
+            steps: Vec::new(),
+            args: Args::new(),
+            policies: ErrorPolicies::default(),
+            approach: None
+        }
+    }
+
+    /// Sets the error policies of the program
+    pub fn policies(mut self, policies : ErrorPolicies) -> Self {
+        self.policies = policies;
+        self
+    }
+
+    /// Adds a step to the program
+    pub fn step(mut self, step : Step<C>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Validates the whole program, checking every movement for valid `Phi` values and every tool id, returns the 
+    /// index of the first invalid step with the error
+    pub fn validate<R, G, T, D>(&self, rob : &R, desc : &D) -> Result<(), (usize, crate::Error)> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        let mut phis = rob.phis();
+
+        for (i, step) in self.steps.iter().enumerate() {
+            if let Some(target) = step.target(rob, desc, phis) {
+                phis = target.map_err(|err| (i, err))?;
+                rob.valid_phis(&phis).map_err(|err| (i, err))?;
+                continue;
+            }
+
+            match step.motion() {
+                Step::ToolChange(Some(id)) => {
+                    if *id >= rob.get_tools().len() {
+                        return Err((i, SyError::config(format!("Invalid tool id! (Id: {}, Tools: {})", id, rob.get_tools().len())).into()));
+                    }
+                },
+                Step::Conditioned { .. } => {
+                    return Err((i, SyError::interpreter("Conditioned steps cannot be nested!").into()));
+                },
+                _ => { }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Estimates the time required to run the program, `move_time` estimates the time of a single movement step
+    pub fn estimate<F : Fn(&Step<C>) -> Duration>(&self, move_time : F) -> Duration {
+        self.steps.iter().map(|step| match step.motion() {
+            Step::Dwell(time) => *time,
+            Step::MoveJ { .. } | Step::MoveAbsJ { .. } | Step::MoveP { .. } => move_time(step),
+            _ => Duration::ZERO
+        }).sum()
+    }
+
+    async fn run_step<S, R, G, T, D>(step : &Step<C>, rob : &mut R, desc : &mut D, stat : &mut S) -> Result<(), crate::Error> 
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        let Step::Conditioned { pre, motion, post } = step else {
+            return Self::run_action(step, rob, desc, stat).await;
+        };
+
+        for cond in pre {
+            cond.apply(stat).await?;
+        }
+
+        Self::run_action(motion, rob, desc, stat).await?;
+
+        for cond in post {
+            cond.apply(stat).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_action<S, R, G, T, D>(step : &Step<C>, rob : &mut R, desc : &mut D, stat : &mut S) -> Result<(), crate::Error> 
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        match step {
+            Step::MoveJ { deltas, speed_f } => rob.move_j(*deltas, *speed_f).await?,
+            Step::MoveAbsJ { phis, speed_f } => rob.move_abs_j(*phis, *speed_f).await?,
+            Step::MoveP { pos, speed_f } => rob.move_p(desc, pos.clone(), *speed_f).await?,
+            Step::ToolChange(id) => {
+                rob.set_tool_id(*id);
+            },
+            Step::ToolOn => {
+                rob.activate_tool()?;
+            },
+            Step::ToolOff => {
+                rob.deactivate_tool()?;
+            },
+            Step::Output { name, state } => stat.set_output(name, *state)?,
+            Step::Dwell(time) => tokio::time::sleep(*time).await,
+            Step::Count(event) => stat.counters_mut()
+                .ok_or_else(|| SyError::config("The station has no production counters!"))?
+                .count(event),
+            Step::Tuning(name) => stat.activate_tuning(rob, name)?,
+            Step::Conditioned { .. } => return Err(SyError::interpreter("Conditioned steps cannot be nested!").into())
+        }
+
+        Ok(())
+    }
+
+    /// Validates and runs the program, steps that fail are handled by the error policies of the program. The 
+    /// `ErrorPolicy::Pause` policy aborts, as there is no operator to prompt, see `run_supervised`
+    pub async fn run<S, R, G, T, D>(&self, rob : &mut R, desc : &mut D, stat : &mut S) -> Result<(), crate::Error> 
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        self.execute(rob, desc, stat, None).await
+    }
+
+    /// Validates and runs the program, prompting the operator on steps failing with the `ErrorPolicy::Pause` policy
+    pub async fn run_supervised<S, R, G, T, D>(&self, rob : &mut R, desc : &mut D, stat : &mut S, op : &mut dyn Operator) 
+    -> Result<(), crate::Error> 
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        self.execute(rob, desc, stat, Some(op)).await
+    }
+
+    async fn execute<S, R, G, T, D>(&self, rob : &mut R, desc : &mut D, stat : &mut S, mut op : Option<&mut dyn Operator>) 
+    -> Result<(), crate::Error> 
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        self.validate(rob, desc).map_err(|(i, err)| format!("Step {} of program '{}' is invalid! ({})", i, self.name, err))?;
+
+        // The tool mounted is verified before the first movement and before the first movement after every tool change
+        let mut verified = false;
+
+        for (i, step) in self.steps.iter().enumerate() {
+            if let Some(cancel) = rob.cancel_token() {
+                cancel.check()?;
+            }
+
+            match step.class() {
+                Some(OpClass::Motion) if !verified => {
+                    stat.verify_tool(rob)?;
+                    verified = true;
+                },
+                _ => if let Step::ToolChange(_) = step.motion() {
+                    verified = false;
+                }
+            }
+
+            let mut attempts = 0;
+
+            while let Err(err) = Self::run_step(step, rob, desc, stat).await {
+                attempts += 1;
+
+                match self.policies.for_step(step) {
+                    ErrorPolicy::Abort => return Err(err),
+                    ErrorPolicy::Retry(max) => {
+                        if attempts > max {
+                            return Err(SyError::interpreter(format!("Step {} failed after {} retries! ({})", 
+                                i, max, err)).into());
+                        }
+                    },
+                    ErrorPolicy::Skip => break,
+                    ErrorPolicy::Pause => {
+                        let Some(op) = op.as_deref_mut() else {
+                            return Err(err);
+                        };
+
+                        if !op.confirm(&format!("Step {} of program '{}' failed! ({}) Retry?", i, self.name, err))? {
+                            return Err(err);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
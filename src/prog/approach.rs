@@ -0,0 +1,157 @@
+use glam::Vec3;
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, Robot};
+use crate::prog::{Program, Step};
+use crate::rcs::{Point, Position};
+
+/// Strategy to approach or leave a contact pose
+#[derive(Clone, Debug, Default)]
+pub enum Approach {
+    /// Move directly to / away from the contact pose
+    #[default]
+    Direct,
+    /// Offset along the tool Z-axis of the contact pose by the given distance in millimeters, positive values move 
+    /// away from the contact (against the tool direction)
+    ToolZ(f32),
+    /// Move through the given via-point
+    Via(Position)
+}
+
+impl Approach {
+    /// Returns the intermediate pose of the strategy for the given contact pose, `None` if moved directly
+    pub fn pose(&self, contact : &Position) -> Option<Position> {
+        match self {
+            Self::Direct => None,
+            Self::ToolZ(dist) => {
+                let tool_z = *contact.ori() * Vec3::Z;
+                Some(Position::new_ori(*contact.pos() - tool_z * *dist, *contact.ori()))
+            },
+            Self::Via(via) => Some(via.clone())
+        }
+    }
+}
+
+/// Approach and retract moves automatically inserted around a contact pose, so programs specify only the contact itself
+#[derive(Clone, Debug)]
+pub struct ApproachRetract {
+    /// Strategy used to approach the contact
+    pub approach : Approach,
+    /// Strategy used to leave the contact
+    pub retract : Approach,
+    /// Speed factor for the moves to the approach pose and away from the retract pose
+    pub travel_speed_f : Factor,
+    /// Speed factor for the moves between the approach / retract poses and the contact
+    pub contact_speed_f : Factor
+}
+
+impl ApproachRetract {
+    /// Creates a new strategy with the same offset along the tool Z-axis for approach and retract
+    pub fn tool_z(dist : f32, travel_speed_f : Factor, contact_speed_f : Factor) -> Self {
+        Self {
+            approach: Approach::ToolZ(dist),
+            retract: Approach::ToolZ(dist),
+            travel_speed_f,
+            contact_speed_f
+        }
+    }
+
+    /// Creates a new strategy moving directly to and away from the contact
+    pub fn direct(speed_f : Factor) -> Self {
+        Self {
+            approach: Approach::Direct,
+            retract: Approach::Direct,
+            travel_speed_f: speed_f,
+            contact_speed_f: speed_f
+        }
+    }
+
+    /// Moves the robot to the contact pose, through the approach pose if the strategy has one
+    pub async fn approach_to<R, G, T, D, const C : usize>(&self, rob : &mut R, desc : &mut D, contact : &Position) 
+        -> Result<(), crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        match self.approach.pose(contact) {
+            Some(pose) => {
+                rob.move_p_sync(desc, pose, self.travel_speed_f).await?;
+                rob.move_p_sync(desc, contact.clone(), self.contact_speed_f).await
+            },
+            None => rob.move_p_sync(desc, contact.clone(), self.travel_speed_f).await
+        }
+    }
+
+    /// Moves the robot away from the contact pose to the retract pose, does nothing if the strategy has none
+    pub async fn retract_from<R, G, T, D, const C : usize>(&self, rob : &mut R, desc : &mut D, contact : &Position) 
+        -> Result<(), crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        match self.retract.pose(contact) {
+            Some(pose) => rob.move_p_sync(desc, pose, self.contact_speed_f).await,
+            None => Ok(())
+        }
+    }
+
+    /// Expands a contact pose into program steps: approach, contact, the given `actions` at the contact and retract
+    pub fn expand<const C : usize>(&self, contact : &Position, actions : Vec<Step<C>>) -> Vec<Step<C>> {
+        let mut steps = Vec::new();
+        let approach = self.approach.pose(contact);
+        let retract = self.retract.pose(contact);
+
+        if let Some(pose) = approach {
+            steps.push(Step::MoveP { pos: pose, speed_f: self.travel_speed_f });
+            steps.push(Step::MoveP { pos: contact.clone(), speed_f: self.contact_speed_f });
+        } else {
+            steps.push(Step::MoveP { pos: contact.clone(), speed_f: self.travel_speed_f });
+        }
+
+        steps.extend(actions);
+
+        if let Some(pose) = retract {
+            steps.push(Step::MoveP { pos: pose, speed_f: self.contact_speed_f });
+        }
+
+        steps
+    }
+}
+
+impl<const C : usize> Program<C> {
+    /// Sets the approach and retract strategy inserted around all contact poses added afterwards
+    pub fn approach(mut self, strategy : ApproachRetract) -> Self {
+        self.approach = Some(strategy);
+        self
+    }
+
+    /// Adds a contact pose with the approach and retract moves of the program's strategy around it (see 
+    /// `Program::approach()`), `actions` are run at the contact (e.g. `Step::ToolOn` to grip). Without strategy the 
+    /// contact is moved to directly
+    pub fn contact(self, contact : &Position, actions : Vec<Step<C>>) -> Self {
+        let strategy = self.approach.clone().unwrap_or_else(|| ApproachRetract::direct(Factor::MAX));
+        self.contact_with(contact, &strategy, actions)
+    }
+
+    /// Same as `contact()`, but with the given strategy instead of the program's one
+    pub fn contact_with(mut self, contact : &Position, strategy : &ApproachRetract, actions : Vec<Step<C>>) -> Self {
+        self.steps.extend(strategy.expand(contact, actions));
+        self
+    }
+
+    /// Adds a pick at the given pose, the tool is activated at the contact (see `contact()`)
+    pub fn pick(self, contact : &Position) -> Self {
+        self.contact(contact, vec![ Step::ToolOn ])
+    }
+
+    /// Adds a place at the given pose, the tool is deactivated at the contact (see `contact()`)
+    pub fn place(self, contact : &Position) -> Self {
+        self.contact(contact, vec![ Step::ToolOff ])
+    }
+}
@@ -0,0 +1,68 @@
+use core::time::Duration;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Station, SyError};
+
+/// Interval in which inputs are polled while waiting for a condition
+const POLL_INTERVAL : Duration = Duration::from_millis(10);
+
+/// A condition on the I/O of the station, attached to movements with `Step::Conditioned`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    /// Waits until the named input has the given state, fails after the timeout
+    WaitInput { name : String, state : bool, timeout : Duration },
+    /// Requires the named input to have the given state, fails immediately otherwise
+    RequireInput { name : String, state : bool },
+    /// Sets the named output
+    SetOutput { name : String, state : bool }
+}
+
+impl Condition {
+    /// Waits until the named input is set, see `Condition::WaitInput`
+    pub fn wait_until<S : Into<String>>(name : S, timeout : Duration) -> Self {
+        Self::WaitInput { name: name.into(), state: true, timeout }
+    }
+
+    /// Sets the named output, see `Condition::SetOutput`
+    pub fn set<S : Into<String>>(name : S, state : bool) -> Self {
+        Self::SetOutput { name: name.into(), state }
+    }
+
+    /// Applies the condition to the station
+    pub async fn apply<S, G, T, const C : usize>(&self, stat : &mut S) -> Result<(), crate::Error>
+    where
+        S : Station<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        match self {
+            Self::WaitInput { name, state, timeout } => {
+                let res = tokio::time::timeout(*timeout, async {
+                    loop {
+                        if stat.input(name)? == *state {
+                            return Ok::<(), crate::Error>(());
+                        }
+
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                    }
+                }).await;
+
+                match res {
+                    Ok(res) => res,
+                    Err(_) => Err(SyError::safety(format!("Timeout while waiting for input '{}' to be {}! (Timeout: {:?})",
+                        name, state, timeout)).into())
+                }
+            },
+            Self::RequireInput { name, state } => {
+                if stat.input(name)? == *state {
+                    Ok(())
+                } else {
+                    Err(SyError::safety(format!("Input '{}' is not {}!", name, state)).into())
+                }
+            },
+            Self::SetOutput { name, state } => stat.set_output(name, *state)
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+
+use crate::SyError;
+use crate::prog::{Program, Step};
+
+/// Declaration of a program parameter
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ParamDecl {
+    /// Name of the parameter
+    pub name : String,
+    /// Default value, the parameter is required if `None`
+    pub default : Option<f32>,
+    /// Minimum value allowed
+    pub min : Option<f32>,
+    /// Maximum value allowed
+    pub max : Option<f32>
+}
+
+impl ParamDecl {
+    /// Creates a new required parameter
+    pub fn new<S : Into<String>>(name : S) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the default value of the parameter, making it optional
+    pub fn default_value(mut self, value : f32) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Sets the range of values allowed
+    pub fn range(mut self, min : f32, max : f32) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+}
+
+/// Arguments supplied to a program at job start
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Args {
+    /// Values of the arguments by parameter name
+    pub values : HashMap<String, f32>
+}
+
+impl Args {
+    /// Creates a new empty set of arguments
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an argument
+    pub fn arg<S : Into<String>>(mut self, name : S, value : f32) -> Self {
+        self.values.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the value of the given parameter
+    pub fn get(&self, name : &str) -> Option<f32> {
+        self.values.get(name).copied()
+    }
+
+    /// Returns the value of the given parameter, or an error if it has not been supplied
+    pub fn req(&self, name : &str) -> Result<f32, crate::Error> {
+        self.get(name).ok_or_else(|| SyError::config(format!("Missing program argument '{}'!", name)).into())
+    }
+
+    /// Checks the arguments against the given declarations, filling in the default values. Fails for missing, unknown 
+    /// and out of range arguments
+    pub fn bind(&self, params : &[ParamDecl]) -> Result<Args, crate::Error> {
+        if let Some(name) = self.values.keys().find(|name| !params.iter().any(|p| &p.name == *name)) {
+            return Err(SyError::config(format!("Unknown program argument '{}'!", name)).into());
+        }
+
+        let mut args = Args::new();
+
+        for param in params {
+            let value = self.get(&param.name).or(param.default)
+                .ok_or_else(|| SyError::config(format!("Missing program argument '{}'!", param.name)))?;
+
+            if param.min.map_or(false, |min| value < min) | param.max.map_or(false, |max| value > max) {
+                return Err(SyError::config(format!("Program argument '{}' is out of range! (Value: {}, Min: {:?}, Max: {:?})", 
+                    param.name, value, param.min, param.max)).into());
+            }
+
+            args.values.insert(param.name.clone(), value);
+        }
+
+        Ok(args)
+    }
+}
+
+/// Generates the steps of a program from its arguments
+pub type StepGen<const C : usize> = Arc<dyn Fn(&Args) -> Result<Vec<Step<C>>, crate::Error> + Send + Sync>;
+
+/// A stored program declaring parameters (counts, offsets, speeds), instantiated with the arguments supplied at job 
+/// start so one program can handle a family of parts
+#[derive(Clone)]
+pub struct ProgramTemplate<const C : usize> {
+    /// Name of the program
+    pub name : String,
+    /// The parameters declared
+    pub params : Vec<ParamDecl>,
+
+    gen : StepGen<C>
+}
+
+impl<const C : usize> ProgramTemplate<C> {
+    /// Creates a new template, the steps are generated by `gen` for the bound arguments
+    pub fn new<S, F>(name : S, gen : F) -> Self 
+    where
+        S : Into<String>,
+        F : Fn(&Args) -> Result<Vec<Step<C>>, crate::Error> + Send + Sync + 'static
+    {
+        Self {
+            name: name.into(),
+            params: Vec::new(),
+            gen: Arc::new(gen)
+        }
+    }
+
+    /// Declares a parameter
+    pub fn param(mut self, param : ParamDecl) -> Self {
+        self.params.push(param);
+        self
+    }
+
+    /// Binds the arguments and generates the program
+    pub fn instantiate(&self, args : &Args) -> Result<Program<C>, crate::Error> {
+        let args = args.bind(&self.params)?;
+        let steps = (self.gen)(&args)?;
+
+        Ok(Program {
+            name: self.name.clone(),
+            steps,
+            args,
+            policies: Default::default(),
+            approach: None
+        })
+    }
+}
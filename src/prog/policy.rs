@@ -0,0 +1,56 @@
+use serde::{Serialize, Deserialize};
+
+use crate::prog::Step;
+
+/// Classes of operations of a program, each class can be assigned its own `ErrorPolicy`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpClass {
+    /// Movements of the robot
+    Motion,
+    /// Tool changes and activations
+    Tool,
+    /// Outputs of the station
+    Io
+}
+
+/// What the program executor does when a step fails
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorPolicy {
+    /// Stop the program and return the error
+    #[default]
+    Abort,
+    /// Run the step again up to the given number of times before aborting. Relative movements are repeated from the 
+    /// position the robot stopped at, so this policy is mostly useful for absolute movements
+    Retry(usize),
+    /// Skip the failed step and continue with the next one
+    Skip,
+    /// Pause and prompt the operator, who can either retry the step or abort
+    Pause
+}
+
+/// The error policies of a program for each class of operations
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ErrorPolicies {
+    /// Policy for movements
+    pub motion : ErrorPolicy,
+    /// Policy for tool operations
+    pub tool : ErrorPolicy,
+    /// Policy for station outputs
+    pub io : ErrorPolicy
+}
+
+impl ErrorPolicies {
+    /// Returns the policy for the given class
+    pub fn for_class(&self, class : OpClass) -> ErrorPolicy {
+        match class {
+            OpClass::Motion => self.motion,
+            OpClass::Tool => self.tool,
+            OpClass::Io => self.io
+        }
+    }
+
+    /// Returns the policy for the given step, steps without a class always abort
+    pub fn for_step<const C : usize>(&self, step : &Step<C>) -> ErrorPolicy {
+        step.class().map_or(ErrorPolicy::Abort, |class| self.for_class(class))
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Serialize, Deserialize};
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, Robot, Station};
+use crate::prog::{Program, Step};
+use crate::stat::{JobRecord, JobResult};
+
+/// Classes of problems found by a simulation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticKind {
+    /// A position cannot be reached by the kinematics
+    Unreachable,
+    /// A joint limit would be violated
+    Limit,
+    /// The robot would pass close to a singularity
+    Singularity,
+    /// The robot would collide
+    Collision
+}
+
+/// A problem found by a simulation at a step of a program
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Index of the step
+    pub step : usize,
+    /// Class of the problem
+    pub kind : DiagnosticKind,
+    /// Description of the problem
+    pub msg : String
+}
+
+impl core::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Step {}: {:?} ({})", self.step, self.kind, self.msg)
+    }
+}
+
+/// Checks performed by a simulation in addition to reachability and joint limits
+pub struct SimChecks<'a, const C : usize> {
+    /// Minimum value of the manipulability (`sqrt(det(J * J^T))`) accepted, `None` to skip the singularity check
+    pub manipulability_min : Option<f32>,
+    /// Collision check of a pose, returns a description of the collision if there is one
+    pub collision : Option<&'a dyn Fn(&[Phi; C]) -> Option<String>>,
+    /// Number of poses checked for limits and collisions along each movement, the last one being the target
+    pub samples : usize
+}
+
+impl<'a, const C : usize> SimChecks<'a, C> {
+    /// Default number of poses checked along each movement
+    pub const DEFAULT_SAMPLES : usize = 10;
+}
+
+impl<'a, const C : usize> Default for SimChecks<'a, C> {
+    fn default() -> Self {
+        Self {
+            manipulability_min: None,
+            collision: None,
+            samples: Self::DEFAULT_SAMPLES
+        }
+    }
+}
+
+impl<const C : usize> Program<C> {
+    /// Simulates the program without moving the robot, returning all problems found. Limits and collisions are 
+    /// checked at `checks.samples` poses along every movement, singularities at its target. The kinematic of the 
+    /// descriptor is restored afterwards
+    pub fn simulate<R, G, T, D>(&self, rob : &R, desc : &mut D, checks : &SimChecks<C>) -> Vec<Diagnostic>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        let mut diags = Vec::new();
+        let mut phis = rob.phis();
+
+        for (i, step) in self.steps.iter().enumerate() {
+            let mut diag = |kind, msg : String| diags.push(Diagnostic { step: i, kind, msg });
+
+            let target = match step.target(rob, desc, phis) {
+                Some(Ok(target)) => target,
+                Some(Err(err)) => {
+                    diag(DiagnosticKind::Unreachable, err.to_string());
+                    continue;
+                },
+                None => continue
+            };
+
+            // Joint movements are linear in gammas, the poses in between are checked as well, reporting only the 
+            // first problem of each kind per step
+            let gamma_0 = rob.gammas_from_phis(phis);
+            let gamma_t = rob.gammas_from_phis(target);
+            let samples = checks.samples.max(1);
+
+            let mut limit_found = false;
+            let mut collision_found = false;
+
+            for s in 1 ..= samples {
+                let f = s as f32 / samples as f32;
+                let sample = if s == samples { target } else {
+                    rob.phis_from_gammas(core::array::from_fn(|n| Gamma(gamma_0[n].0 + (gamma_t[n].0 - gamma_0[n].0) * f)))
+                };
+
+                if !limit_found {
+                    if let Err(err) = rob.valid_phis(&sample) {
+                        diag(DiagnosticKind::Limit, format!("{} (at {:.0}% of the movement)", err, f * 100.0));
+                        limit_found = true;
+                    }
+                }
+
+                if !collision_found {
+                    if let Some(msg) = checks.collision.and_then(|check| check(&sample)) {
+                        diag(DiagnosticKind::Collision, format!("{} (at {:.0}% of the movement)", msg, f * 100.0));
+                        collision_found = true;
+                    }
+                }
+            }
+
+            phis = target;
+
+            if let Some(min) = checks.manipulability_min {
+                if let Ok(manipulability) = desc.manipulability(&phis) {
+                    if manipulability < min {
+                        diag(DiagnosticKind::Singularity, format!("Manipulability {} is below {}", manipulability, min));
+                    }
+                }
+            }
+        }
+
+        diags
+    }
+
+    /// Runs the program on the hardware only if the simulation finds no problems. The record of the job has the 
+    /// diagnostics of the simulation attached
+    pub async fn run_gated<S, R, G, T, D>(&self, rob : &mut R, desc : &mut D, stat : &mut S, checks : &SimChecks<'_, C>, 
+        operator : Option<String>) -> JobRecord
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        let start = std::time::SystemTime::now();
+        let diags = self.simulate(rob, desc, checks);
+
+        let result = if diags.is_empty() {
+            match self.run(rob, desc, stat).await {
+                Ok(()) => JobResult::Completed,
+                Err(err) => JobResult::Failed(err.to_string())
+            }
+        } else {
+            JobResult::Failed(format!("The simulation found {} problem(s), the job has not been dispatched", diags.len()))
+        };
+
+        let mut record = JobRecord::finish(&self.name, start, result, operator);
+        record.diagnostics = diags.iter().map(|d| d.to_string()).collect();
+        record
+    }
+}
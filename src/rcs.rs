@@ -4,15 +4,29 @@ use core::{cell::RefCell, fmt::Debug, ops::{Deref, DerefMut}};
 use std::collections::HashMap;
 
 use alloc::rc::Rc;
-use glam::{Vec3, Mat3};
+use glam::{Vec3, Mat3, Quat};
 use serde::{Serialize, Deserialize};
 // use serde::de::DeserializeOwned;
 
+use crate::SyError;
+
 // Submodules
+    mod arc;
+    pub use arc::{arc_points, ArcCenter, Plane};
+
     mod des;
 
+    mod interp;
+    pub use interp::{Circular, Interpolator, Interpolators, Linear, Spline};
+
     /// Mathematical operations of the coordinate system
     pub mod math;
+
+    mod tree;
+    pub use tree::{FrameTree, ROOT_FRAME};
+
+    mod user_frame;
+    pub use user_frame::{frame_from_points, work_offset_frame, MIN_TEACH_DIST, WORK_OFFSETS};
 // 
 
 pub trait Point : Debug {
@@ -77,6 +91,21 @@ pub trait Point : Debug {
             Self { pos, ori }
         }
 
+        /// Creates a full 6-DOF pose with the orientation given as quaternion
+        pub fn new_quat(pos : Vec3, ori : Quat) -> Self {
+            Self { pos, ori: Mat3::from_quat(ori.normalize()) }
+        }
+
+        /// Returns the orientation as quaternion
+        pub fn quat(&self) -> Quat {
+            Quat::from_mat3(&self.ori).normalize()
+        }
+
+        /// Sets the orientation given as quaternion
+        pub fn set_quat(&mut self, ori : Quat) {
+            self.ori = Mat3::from_quat(ori.normalize());
+        }
+
         pub fn to_wo(self) -> WorldObj {
             WorldObj::from_pos(self)
         }
@@ -172,6 +201,10 @@ impl PointRef {
         *self.borrow().pos()
     }
 
+    pub fn ori(&self) -> Mat3 {
+        *self.borrow().ori()
+    }
+
     pub fn clone_no_ref(&self) -> PointRef {
         let p = self.borrow(); 
 
@@ -295,7 +328,7 @@ impl WorldObj {
         if let Some(p) = self.point(path_s.clone()) {
             Ok(p)
         } else {
-            Err(format!("The system requires a point with path '{}'", &path_s).into())
+            Err(SyError::config(format!("The system requires a point with path '{}'", &path_s)).into())
         }
     }
 
@@ -307,10 +340,47 @@ impl WorldObj {
         if let Some(p) = self.point_path(path) {
             Ok(p)
         } else {
-            Err(format!("The system requires a point with path ({:?})", path).into())
+            Err(SyError::config(format!("The system requires a point with path ({:?})", path)).into())
         }
     }
 
+    /// Returns the global pose (position and orientation in the coordinate system of this object) of the point with the 
+    /// given path, combining the poses of all the objects along the path
+    pub fn global_pose<S : Into<String>>(&self, path : S) -> Option<Position> {
+        let path_s = path.into();
+        let split : Vec<&str> = path_s.split('/').collect();
+
+        let mut pos = Vec3::ZERO;
+        let mut ori = Mat3::IDENTITY;
+
+        for i in 1 ..= split.len() {
+            let point = self.point_path(&split[.. i])?;
+            let p = point.borrow();
+
+            pos += ori * *p.pos();
+            ori = ori * *p.ori();
+        }
+
+        Some(Position::new_ori(pos, ori))
+    }
+
+    /// Returns the paths of all the points in this object and its subobjects (e.g. `"x/y"`)
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+
+        for (name, point) in &self.sub {
+            paths.push(name.clone());
+
+            if let Some(wo) = point.borrow().as_wo() {
+                for sub_path in wo.paths() {
+                    paths.push(format!("{}/{}", name, sub_path));
+                }
+            }
+        }
+
+        paths
+    }
+
     fn resolve_path_step(&self, split : &[&str], mut index : usize) -> Option<PointRef> {
         if index > split.len() {
             return None;
@@ -0,0 +1,119 @@
+use core::f32::consts::PI;
+
+use glam::Vec3;
+
+use crate::SyError;
+
+/// The plane of circular movements
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Plane {
+    /// XY-plane (`G17`)
+    #[default]
+    XY,
+    /// XZ-plane (`G18`)
+    XZ,
+    /// YZ-plane (`G19`)
+    YZ
+}
+
+impl Plane {
+    /// Returns the plane selected by the given G-code number (`17`, `18` or `19`)
+    pub fn from_gcode(code : u32) -> Option<Self> {
+        match code {
+            17 => Some(Self::XY),
+            18 => Some(Self::XZ),
+            19 => Some(Self::YZ),
+            _ => None
+        }
+    }
+
+    /// Returns the indices of the two axes spanning the plane and the index of the normal axis
+    pub fn axes(self) -> (usize, usize, usize) {
+        match self {
+            Self::XY => (0, 1, 2),
+            // G18 arcs are defined looking along negative Y, so Z is the first axis
+            Self::XZ => (2, 0, 1),
+            Self::YZ => (1, 2, 0)
+        }
+    }
+}
+
+/// The definition of the arc center
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArcCenter {
+    /// Offset of the center from the start point (`I`, `J` and `K` words)
+    Offset(Vec3),
+    /// Radius of the arc (`R` word), negative values select the arc larger than 180°
+    Radius(f32)
+}
+
+/// Splits a circular movement (`G2` when `clockwise`, `G3` otherwise) into points with a distance of at most 
+/// `seg_len` along the arc. The start point is not included. A movement along the normal axis of the plane is 
+/// interpolated linearly (helix)
+pub fn arc_points(start : Vec3, end : Vec3, center : ArcCenter, clockwise : bool, plane : Plane, seg_len : f32) 
+-> Result<Vec<Vec3>, crate::Error> {
+    if seg_len <= 0.0 {
+        return Err(SyError::config("The segment length of an arc must be positive!").into());
+    }
+
+    let (a, b, n) = plane.axes();
+    let (s, e) = (start.to_array(), end.to_array());
+
+    let (ca, cb) = match center {
+        ArcCenter::Offset(offset) => {
+            let o = offset.to_array();
+            let (ca, cb) = (s[a] + o[a], s[b] + o[b]);
+
+            let r_start = ((s[a] - ca).powi(2) + (s[b] - cb).powi(2)).sqrt();
+            let r_end = ((e[a] - ca).powi(2) + (e[b] - cb).powi(2)).sqrt();
+
+            if (r_start - r_end).abs() > 0.01 * r_start.max(1.0) {
+                return Err(SyError::kinematics(format!("The start and end point of the arc have different radii! (Start: {}, End: {})", 
+                    r_start, r_end)).into());
+            }
+
+            (ca, cb)
+        },
+        ArcCenter::Radius(r) => {
+            let (da, db) = (e[a] - s[a], e[b] - s[b]);
+            let chord = (da * da + db * db).sqrt();
+
+            if (chord == 0.0) | (chord > 2.0 * r.abs() + 1e-4) {
+                return Err(SyError::kinematics(format!("No arc with radius {} between the points! (Chord: {})", 
+                    r, chord)).into());
+            }
+
+            // Distance of the center from the middle of the chord, the side depends on the direction and the arc size
+            let h = (r * r - chord * chord / 4.0).max(0.0).sqrt();
+            let side = if clockwise == (r > 0.0) { -1.0 } else { 1.0 };
+
+            (s[a] + da / 2.0 - side * h * db / chord, s[b] + db / 2.0 + side * h * da / chord)
+        }
+    };
+
+    let radius = ((s[a] - ca).powi(2) + (s[b] - cb).powi(2)).sqrt();
+    let ang_s = (s[b] - cb).atan2(s[a] - ca);
+    let ang_e = (e[b] - cb).atan2(e[a] - ca);
+
+    let mut sweep = ang_e - ang_s;
+    if clockwise {
+        if sweep >= 0.0 { sweep -= 2.0 * PI; }
+    } else {
+        if sweep <= 0.0 { sweep += 2.0 * PI; }
+    }
+
+    let length = (sweep.abs() * radius).hypot(e[n] - s[n]);
+    let count = ((length / seg_len).ceil() as usize).max(1);
+
+    Ok((1 ..= count).map(|i| {
+        let t = i as f32 / count as f32;
+        let ang = ang_s + sweep * t;
+
+        let mut p = [0.0; 3];
+        p[a] = ca + radius * ang.cos();
+        p[b] = cb + radius * ang.sin();
+        p[n] = s[n] + (e[n] - s[n]) * t;
+
+        if i == count { end } else { Vec3::from_array(p) }
+    }).collect())
+}
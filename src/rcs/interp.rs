@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use glam::{Mat3, Quat, Vec3};
+
+use crate::SyError;
+use crate::rcs::{arc_points, ArcCenter, Plane, Point, Position};
+use crate::rcs::math::split_linear_pose;
+
+/// An interpolator splits a movement between two poses into waypoints, used by `Robot::move_path` and custom
+/// planners. Implement this trait to add custom interpolations (e.g. polar interpolation for arms)
+pub trait Interpolator {
+    /// Returns the waypoints of the movement from `start` to `end` with a distance of at most `seg_len` between
+    /// them. The start pose is not included, the last waypoint must be the `end` pose
+    fn interpolate(&self, start : &Position, end : &Position, seg_len : f32) -> Result<Vec<Position>, crate::Error>;
+}
+
+impl<F : Fn(&Position, &Position, f32) -> Result<Vec<Position>, crate::Error>> Interpolator for F {
+    fn interpolate(&self, start : &Position, end : &Position, seg_len : f32) -> Result<Vec<Position>, crate::Error> {
+        self(start, end, seg_len)
+    }
+}
+
+fn check_seg_len(seg_len : f32) -> Result<(), crate::Error> {
+    if seg_len > 0.0 {
+        Ok(())
+    } else {
+        Err(SyError::config(format!("The segment length of an interpolation must be positive! (Length: {})", seg_len)).into())
+    }
+}
+
+/// Sweeps the orientation from `start` to `end` (spherical) along the given points, `t` being the progress of each point
+fn sweep_ori(start : &Position, end : &Position, points : Vec<Vec3>) -> Vec<Position> {
+    let quat_0 = Quat::from_mat3(start.ori());
+    let quat_t = Quat::from_mat3(end.ori());
+    let n = points.len();
+
+    points.into_iter().enumerate().map(|(i, pos)| {
+        let t = (i + 1) as f32 / n as f32;
+        Position::new_ori(pos, Mat3::from_quat(quat_0.slerp(quat_t, t)))
+    }).collect()
+}
+
+/// Straight movement of the TCP, the orientation is interpolated spherically
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Linear;
+
+impl Interpolator for Linear {
+    fn interpolate(&self, start : &Position, end : &Position, seg_len : f32) -> Result<Vec<Position>, crate::Error> {
+        Ok(split_linear_pose(start, end, seg_len)?.into_iter().skip(1).collect())
+    }
+}
+
+/// Circular movement of the TCP in a plane, see `arc_points`
+#[derive(Clone, Copy, Debug)]
+pub struct Circular {
+    /// The definition of the arc center
+    pub center : ArcCenter,
+    /// Wheiter the arc is driven clockwise
+    pub clockwise : bool,
+    /// The plane of the arc
+    pub plane : Plane
+}
+
+impl Interpolator for Circular {
+    fn interpolate(&self, start : &Position, end : &Position, seg_len : f32) -> Result<Vec<Position>, crate::Error> {
+        let points = arc_points(*start.pos(), *end.pos(), self.center, self.clockwise, self.plane, seg_len)?;
+        Ok(sweep_ori(start, end, points))
+    }
+}
+
+/// Catmull-Rom spline passing through all the `via` points between start and end
+#[derive(Clone, Debug, Default)]
+pub struct Spline {
+    /// The points the spline passes through
+    pub via : Vec<Vec3>
+}
+
+impl Spline {
+    /// Creates a new spline through the given points
+    pub fn new(via : Vec<Vec3>) -> Self {
+        Self { via }
+    }
+}
+
+impl Interpolator for Spline {
+    fn interpolate(&self, start : &Position, end : &Position, seg_len : f32) -> Result<Vec<Position>, crate::Error> {
+        check_seg_len(seg_len)?;
+
+        let mut knots = Vec::with_capacity(self.via.len() + 2);
+        knots.push(*start.pos());
+        knots.extend_from_slice(&self.via);
+        knots.push(*end.pos());
+
+        let mut points = Vec::new();
+
+        for i in 0 .. (knots.len() - 1) {
+            // The end knots are mirrored to get a natural tangent
+            let p0 = if i == 0 { 2.0 * knots[0] - knots[1] } else { knots[i - 1] };
+            let (p1, p2) = (knots[i], knots[i + 1]);
+            let p3 = knots.get(i + 2).copied().unwrap_or(2.0 * p2 - p1);
+
+            let count = ((p1.distance(p2) / seg_len).ceil() as usize).max(1);
+
+            for n in 1 ..= count {
+                let t = n as f32 / count as f32;
+                let (t2, t3) = (t * t, t * t * t);
+
+                points.push(0.5 * (
+                    2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                    + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3
+                ));
+            }
+        }
+
+        // Remove rounding errors at the end
+        if let Some(last) = points.last_mut() {
+            *last = *end.pos();
+        }
+
+        Ok(sweep_ori(start, end, points))
+    }
+}
+
+/// A set of named interpolators, selectable by name e.g. by interpreters
+pub struct Interpolators {
+    interps : HashMap<String, Box<dyn Interpolator>>
+}
+
+impl Interpolators {
+    /// Creates a new set only containing the `Linear` interpolator as "linear"
+    pub fn new() -> Self {
+        let mut interps = Self { interps: HashMap::new() };
+        interps.register("linear", Box::new(Linear));
+        interps
+    }
+
+    /// Registers an interpolator under the given name, replacing the previous one
+    pub fn register<S : Into<String>>(&mut self, name : S, interp : Box<dyn Interpolator>) {
+        self.interps.insert(name.into(), interp);
+    }
+
+    /// Returns the interpolator with the given name
+    pub fn get(&self, name : &str) -> Result<&dyn Interpolator, crate::Error> {
+        self.interps.get(name).map(|i| i.as_ref())
+            .ok_or_else(|| SyError::config(format!("No interpolator named '{}' has been registered!", name)).into())
+    }
+}
+
+impl Default for Interpolators {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,9 +1,10 @@
 use core::f32::consts::PI;
 
-use glam::Vec3;
+use glam::{Mat3, Quat, Vec3};
 use syunit::*;
 
-use crate::rcs::Point;
+use crate::SyError;
+use crate::rcs::{Point, Position};
 
 pub fn sub_phis<const C : usize>(a : [Phi; C], b : [Phi; C]) -> [Delta; C] {
     let mut deltas = [Delta::ZERO; C];
@@ -13,6 +14,89 @@ pub fn sub_phis<const C : usize>(a : [Phi; C], b : [Phi; C]) -> [Delta; C] {
     deltas
 }
 
+/// Error returned by the analytic IK if a target lies outside of the range of the segments
+#[derive(Clone, Debug)]
+pub struct OutOfReach {
+    /// The target that could not be reached
+    pub target : Vec3,
+    /// The maximum reach of the segments
+    pub max_reach : f32
+}
+
+impl core::fmt::Display for OutOfReach {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The target is out of reach! (Target: {:?}, Distance: {}, Max reach: {})", 
+            self.target.to_array(), self.target.length(), self.max_reach)
+    }
+}
+
+impl std::error::Error for OutOfReach { }
+
+/// How the analytic IK handles targets out of reach
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReachMode {
+    /// Returns an `OutOfReach` error
+    #[default]
+    Strict,
+    /// Moves to the closest reachable point instead, stretching or folding the segments as far as possible
+    Closest
+}
+
+/// The rotational degrees of freedom of the TCP controlled by a descriptor
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OriDof {
+    /// Position only, the orientation of the TCP is fixed (e.g. gantries)
+    #[default]
+    None,
+    /// Rotation around the Z-axis (tool axis) only, e.g. SCARA arms or gantries with a rotary tool
+    Twist,
+    /// Full orientation
+    Full
+}
+
+/// Error returned if a target orientation cannot be reached with the rotational degrees of freedom of the robot
+#[derive(Clone, Debug)]
+pub struct OrientationError {
+    /// The orientation requested
+    pub requested : Quat,
+    /// The degrees of freedom of the robot
+    pub dof : OriDof,
+    /// The angle between the requested orientation and the closest reachable one in radians
+    pub deviation : f32
+}
+
+impl core::fmt::Display for OrientationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The orientation cannot be reached with the degrees of freedom of the robot! (DOF: {:?}, Deviation: {} rad)", 
+            self.dof, self.deviation)
+    }
+}
+
+impl std::error::Error for OrientationError { }
+
+/// Splits the rotation into a swing (tilting the Z-axis) and a twist around the Z-axis, so `ori = swing * twist`. 
+/// Returns the swing and the angle of the twist
+pub fn swing_twist(ori : Quat) -> (Quat, f32) {
+    let twist = Quat::from_xyzw(0.0, 0.0, ori.z, ori.w);
+
+    // Rotations by half a turn around an axis in the XY-plane have no defined twist
+    if twist.length() < 1e-6 {
+        return (ori, 0.0);
+    }
+
+    let twist = twist.normalize();
+    (ori * twist.conjugate(), 2.0 * ori.z.atan2(ori.w))
+}
+
+/// Returns the angle between the orientation given and the closest orientation reachable with the degrees of freedom
+pub fn ori_deviation(ori : Quat, dof : OriDof) -> f32 {
+    match dof {
+        OriDof::None => ori.angle_between(Quat::IDENTITY),
+        OriDof::Twist => swing_twist(ori).0.angle_between(Quat::IDENTITY),
+        OriDof::Full => 0.0
+    }
+}
+
 pub fn law_of_cosines(a : f32, b : f32, c : f32) -> f32 {
     ((a.powi(2) + b.powi(2) - c.powi(2)) / 2.0 / a / b).acos()
 }
@@ -27,28 +111,113 @@ pub fn comple_triangle(alpha : f32, gamma : f32) -> f32 {
     PI - alpha - gamma
 }
 
+/// Calculates the angles of a triangle from its sides, all angles use the law of cosines, as `asin` cannot return 
+/// obtuse angles. Returns NaNs if the sides cannot form a triangle, see `calc_triangle_checked()`
 pub fn calc_triangle(a : f32, b : f32, c : f32) -> (f32, f32, f32) {
     let gamma = law_of_cosines(a, b, c);
-    let alpha = law_of_sines(a, c, gamma);
+    let alpha = law_of_cosines(b, c, a);
     let beta = comple_triangle(alpha, gamma);
 
     ( alpha, beta, gamma )
 }
 
-pub fn calc_triangle_vec(c_p : Vec3, b_p : Vec3) -> (f32, f32, f32) {
+/// Calculates the triangle like `calc_triangle`, but validates that the side `c` can be formed by the sides `a` and `b` 
+/// before calling `acos`, so no NaNs are produced. `target` is the target of the IK (relative to the base of the 
+/// segments) and only used for the error
+pub fn calc_triangle_checked(a : f32, b : f32, c : f32, target : Vec3, mode : ReachMode) -> Result<(f32, f32, f32), OutOfReach> {
+    let max_reach = a + b;
+    let min_reach = (a - b).abs();
+
+    let c = if (min_reach <= c) & (c <= max_reach) {
+        c
+    } else {
+        match mode {
+            ReachMode::Strict => return Err(OutOfReach { target, max_reach }),
+            ReachMode::Closest => if c.is_finite() {
+                c.clamp(min_reach, max_reach) 
+            } else {
+                return Err(OutOfReach { target, max_reach })
+            }
+        }
+    };
+
+    // Clamp to avoid NaNs caused by rounding errors at the edges of the range 
+    let cosines = |a : f32, b : f32, c : f32| ((a.powi(2) + b.powi(2) - c.powi(2)) / 2.0 / a / b).clamp(-1.0, 1.0).acos();
+
+    let gamma = cosines(a, b, c);
+    let alpha = if c > 0.0 { cosines(b, c, a) } else { 0.0 };
+    let beta = comple_triangle(alpha, gamma);
+
+    Ok(( alpha, beta, gamma ))
+}
+
+/// Returns the point closest to `target` that lies within the shell between `min_reach` and `max_reach` around 
+/// `origin`
+pub fn closest_reachable(origin : Vec3, target : Vec3, min_reach : f32, max_reach : f32) -> Vec3 {
+    let delta = target - origin;
+    let dist = delta.length();
+
+    if dist == 0.0 {
+        return origin + Vec3::X * min_reach;
+    }
+
+    origin + delta / dist * dist.clamp(min_reach, max_reach)
+}
+
+/// Searches the reachable point closest to `target` within `tolerance`, checking the directions towards `toward` (e.g. 
+/// the current TCP) and along the axes. Each direction is searched by bisection, so reachability is assumed to be 
+/// continuous along it. Returns `None` if no reachable point has been found
+pub fn snap_to_reachable<F : Fn(Vec3) -> bool>(target : Vec3, toward : Vec3, tolerance : f32, reachable : F) -> Option<Vec3> {
+    const ITERATIONS : usize = 16;
+
+    let mut dirs = vec![ Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y, Vec3::Z, Vec3::NEG_Z ];
+    if let Some(dir) = (toward - target).try_normalize() {
+        dirs.insert(0, dir);
+    }
+
+    let mut best : Option<(f32, Vec3)> = None;
+
+    for dir in dirs {
+        if !reachable(target + dir * tolerance) {
+            continue;
+        }
+
+        let (mut lo, mut hi) = (0.0, tolerance);
+        for _ in 0 .. ITERATIONS {
+            let mid = (lo + hi) / 2.0;
+
+            if reachable(target + dir * mid) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        if best.map_or(true, |(dist, _)| hi < dist) {
+            best = Some((hi, target + dir * hi));
+        }
+    }
+
+    best.map(|(_, pos)| pos)
+}
+
+/// Calculates the triangle formed by the two vectors, see `calc_triangle_checked()`
+pub fn calc_triangle_vec(c_p : Vec3, b_p : Vec3) -> Result<(f32, f32, f32), OutOfReach> {
     let b = c_p.length();
     let a = b_p.length();
-    let c = (b_p + c_p).length();
+    let target = b_p + c_p;
 
-    calc_triangle(a, b, c)
+    calc_triangle_checked(a, b, target.length(), target, ReachMode::Strict)
 }
 
-pub fn calc_triangle_pos(c_p : &dyn Point, b_p : &dyn Point) -> (f32, f32, f32) {
+/// Calculates the triangle formed by the two points, `b_p` is given in the system of `c_p`, see 
+/// `calc_triangle_checked()`
+pub fn calc_triangle_pos(c_p : &dyn Point, b_p : &dyn Point) -> Result<(f32, f32, f32), OutOfReach> {
     let b = c_p.pos().length();
     let a = b_p.pos().length();
-    let c = c_p.to_higher_system(*b_p.pos()).length();
+    let target = c_p.to_higher_system(*b_p.pos());
 
-    calc_triangle(a, b, c)
+    calc_triangle_checked(a, b, target.length(), target, ReachMode::Strict)
 }
 
 pub fn split_linear(pos_0 : Vec3, delta_pos : Vec3, split_len : f32) -> Vec<Vec3> {
@@ -64,6 +233,30 @@ pub fn split_linear(pos_0 : Vec3, delta_pos : Vec3, split_len : f32) -> Vec<Vec3
     pos_vec
 }
 
+/// Splits a linear movement between two poses, interpolating the orientation (spherical) along the path, 
+/// so the tool orientation sweeps smoothly instead of being applied instantly at the end. The segment length 
+/// `split_len` must be positive
+pub fn split_linear_pose(pose_0 : &Position, pose_t : &Position, split_len : f32) -> Result<Vec<Position>, crate::Error> {
+    if !(split_len > 0.0) {
+        return Err(SyError::kinematics(format!("The split length must be positive! (Length: {})", split_len)).into());
+    }
+
+    let delta_pos = *pose_t.pos() - *pose_0.pos();
+    let n_split = ((delta_pos.length() / split_len).ceil() as usize).max(1);
+
+    let quat_0 = Quat::from_mat3(pose_0.ori());
+    let quat_t = Quat::from_mat3(pose_t.ori());
+
+    Ok((0 ..= n_split).map(|i| {
+        let t = i as f32 / n_split as f32;
+
+        Position::new_ori(
+            *pose_0.pos() + delta_pos * t, 
+            Mat3::from_quat(quat_0.slerp(quat_t, t))
+        )
+    }).collect())
+}
+
 // pub fn convert_to_phis<const C : usize>(robot : &impl ActRobot<C>, deco : &[f32], pos_vec : &Vec<Vec3>) 
 // -> Result<Vec<[Phi; C]>, crate::Error> {
 //     let mut phis_vec = vec![]; 
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use crate::SyError;
+use crate::rcs::{Point, PointRef, Position, WorldObj};
+
+/// Name of the root frame of every `FrameTree`, frames without parent are relative to it
+pub const ROOT_FRAME : &str = "world";
+
+impl Position {
+    /// Chains the poses, `other` is given relative to this pose
+    pub fn compose(&self, other : &Position) -> Position {
+        Position::new_ori(self.pos + self.ori * other.pos, self.ori * other.ori)
+    }
+
+    /// Returns the inverse pose, the pose of the parent system relative to this one
+    pub fn inverse(&self) -> Position {
+        let ori = self.ori.transpose();
+        Position::new_ori(-(ori * self.pos), ori)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Frame {
+    parent : Option<String>,
+    point : PointRef
+}
+
+impl Frame {
+    fn local(&self) -> Position {
+        Position::new_ori(self.point.pos(), self.point.ori())
+    }
+}
+
+/// Tree of named coordinate frames, each frame is given relative to its parent
+///
+/// The local pose of every frame is a `PointRef`, frames created from a `WorldObj` share the points of the object. 
+/// Global poses are calculated lazily on every lookup, so moving a point (e.g. `ExternalAxis::set_frame()`) or a frame 
+/// is picked up by all its descendants immediately. Replacing a point of the object with `WorldObj::add_point()` 
+/// disconnects it from the tree
+#[derive(Clone, Debug, Default)]
+pub struct FrameTree {
+    frames : HashMap<String, Frame>
+}
+
+impl FrameTree {
+    /// Creates a new tree containing only the root frame
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a tree from all the points of a world object, every point becomes a frame named after its path
+    /// (e.g. `"fixture/slot"`) with the containing object as parent. The frames are linked to the points of the object
+    pub fn from_world_obj(wobj : &WorldObj) -> Self {
+        let mut tree = Self::new();
+        let mut paths = wobj.paths();
+
+        // Parents first
+        paths.sort_by_key(|path| path.matches('/').count());
+
+        for path in paths {
+            if let Some(point) = wobj.point(path.as_str()) {
+                let parent = path.rsplit_once('/').map(|(parent, _)| parent.to_owned());
+                tree.frames.insert(path, Frame { parent, point });
+            }
+        }
+
+        tree
+    }
+
+    /// Returns wheiter the tree contains a frame with the given name, the root frame is always contained
+    pub fn contains(&self, name : &str) -> bool {
+        (name == ROOT_FRAME) | self.frames.contains_key(name)
+    }
+
+    /// Returns the names of all frames except the root frame
+    pub fn names(&self) -> Vec<&str> {
+        self.frames.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the parent of a frame, `None` for frames relative to the root
+    pub fn parent(&self, name : &str) -> Option<&str> {
+        self.frames.get(name).and_then(|frame| frame.parent.as_deref())
+    }
+
+    /// Returns the names of the frames with the given parent
+    pub fn children(&self, name : &str) -> Vec<&str> {
+        self.frames.iter()
+            .filter(|(_, frame)| frame.parent.as_deref().unwrap_or(ROOT_FRAME) == name)
+            .map(|(n, _)| n.as_str())
+            .collect()
+    }
+
+    /// Returns wheiter `ancestor` is a (direct or indirect) parent of `name` or the frame itself
+    fn descends_from(&self, name : &str, ancestor : &str) -> bool {
+        let mut current = Some(name);
+
+        while let Some(n) = current {
+            if n == ancestor {
+                return true;
+            }
+
+            current = self.parent(n);
+        }
+
+        ancestor == ROOT_FRAME
+    }
+
+    /// Adds a frame or replaces an existing one, `parent` `None` places the frame relative to the root. Fails if the
+    /// parent does not exist or if the frame would become its own ancestor
+    pub fn add<S : Into<String>>(&mut self, name : S, parent : Option<&str>, local : Position) -> Result<(), crate::Error> {
+        self.link(name, parent, PointRef::new(local))
+    }
+
+    /// Same as `add()`, but the local pose of the frame is linked to the given point (e.g. a point of a `WorldObj`)
+    pub fn link<S : Into<String>>(&mut self, name : S, parent : Option<&str>, point : PointRef) -> Result<(), crate::Error> {
+        let name = name.into();
+
+        if name == ROOT_FRAME {
+            return Err(SyError::config("The root frame cannot be replaced!").into());
+        }
+
+        if let Some(parent) = parent {
+            if !self.contains(parent) {
+                return Err(SyError::config(format!("The parent frame '{}' does not exist!", parent)).into());
+            }
+
+            if self.descends_from(parent, &name) {
+                return Err(SyError::config(format!("Setting the parent of frame '{}' to '{}' creates a cycle!",
+                    name, parent)).into());
+            }
+        }
+
+        self.frames.insert(name, Frame {
+            parent: parent.filter(|p| *p != ROOT_FRAME).map(str::to_owned),
+            point
+        });
+
+        Ok(())
+    }
+
+    /// Removes a frame, fails if other frames are relative to it
+    pub fn remove(&mut self, name : &str) -> Result<Position, crate::Error> {
+        if !self.children(name).is_empty() {
+            return Err(SyError::config(format!("The frame '{}' still has children!", name)).into());
+        }
+
+        self.frames.remove(name).map(|frame| frame.local())
+            .ok_or_else(|| SyError::config(format!("No frame named '{}'!", name)).into())
+    }
+
+    /// Returns the pose of a frame relative to its parent
+    pub fn local(&self, name : &str) -> Option<Position> {
+        self.frames.get(name).map(Frame::local)
+    }
+
+    /// Moves a frame relative to its parent, all the descendants move along. Linked points are moved as well
+    pub fn set_local(&mut self, name : &str, local : Position) -> Result<(), crate::Error> {
+        let frame = self.frames.get(name).ok_or_else(|| SyError::config(format!("No frame named '{}'!", name)))?;
+        let mut point = frame.point.borrow_mut();
+
+        *point.pos_mut() = *local.pos();
+        *point.ori_mut() = *local.ori();
+        Ok(())
+    }
+
+    /// Returns the pose of a frame relative to the root
+    pub fn global(&self, name : &str) -> Result<Position, crate::Error> {
+        if name == ROOT_FRAME {
+            return Ok(Position::zero());
+        }
+
+        let frame = self.frames.get(name).ok_or_else(|| SyError::config(format!("No frame named '{}'!", name)))?;
+        Ok(self.global(frame.parent.as_deref().unwrap_or(ROOT_FRAME))?.compose(&frame.local()))
+    }
+
+    /// Returns the pose of frame `from` relative to frame `to`
+    pub fn transform(&self, from : &str, to : &str) -> Result<Position, crate::Error> {
+        Ok(self.global(to)?.inverse().compose(&self.global(from)?))
+    }
+
+    /// Converts a point given in frame `from` into frame `to`
+    pub fn convert(&self, point : Vec3, from : &str, to : &str) -> Result<Vec3, crate::Error> {
+        Ok(self.transform(from, to)?.to_higher_system(point))
+    }
+}
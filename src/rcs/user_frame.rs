@@ -0,0 +1,70 @@
+use glam::{Mat3, Vec3};
+
+use crate::SyError;
+use crate::rcs::{Point, PointRef, Position, WorldObj};
+
+/// Names of the frames selected by the work offsets `G54` to `G59`
+pub const WORK_OFFSETS : [&str; 6] = [ "G54", "G55", "G56", "G57", "G58", "G59" ];
+
+/// Minimum distance between the taught points, closer points cannot define a frame reliably
+pub const MIN_TEACH_DIST : f32 = 1.0;
+
+/// Returns the name of the frame selected by the given gcode number (`54` to `59`)
+pub fn work_offset_frame(code : u16) -> Option<&'static str> {
+    code.checked_sub(54).and_then(|i| WORK_OFFSETS.get(i as usize).copied())
+}
+
+/// Defines a user frame (e.g. of a fixture) with the 3-point method
+///
+/// - `origin` becomes the origin of the frame
+/// - `x_point` lies on the positive X-axis
+/// - `xy_point` lies anywhere in the XY-plane on the side of the positive Y-axis
+///
+/// Fails if the points are too close to each other or in a line
+pub fn frame_from_points(origin : Vec3, x_point : Vec3, xy_point : Vec3) -> Result<Position, crate::Error> {
+    let x_vec = x_point - origin;
+    let xy_vec = xy_point - origin;
+
+    if (x_vec.length() < MIN_TEACH_DIST) | (xy_vec.length() < MIN_TEACH_DIST) {
+        return Err(SyError::kinematics(format!("The taught points are too close to the origin! (Min distance: {})",
+            MIN_TEACH_DIST)).into());
+    }
+
+    let x = x_vec.normalize();
+    let z = x.cross(xy_vec);
+
+    // The sine of the angle between the vectors
+    if z.length() < 1e-3 * xy_vec.length() {
+        return Err(SyError::kinematics("The taught points lie in a line, the XY-plane is undefined!").into());
+    }
+
+    let z = z.normalize();
+    let y = z.cross(x);
+
+    Ok(Position::new_ori(origin, Mat3::from_cols(x, y, z)))
+}
+
+impl WorldObj {
+    /// Stores the frame with the given name, an existing frame is moved to the new pose, keeping its subpoints
+    pub fn set_frame<S : Into<String>>(&mut self, name : S, pose : Position) {
+        let name = name.into();
+
+        if let Some(point) = self.sub.get(&name) {
+            let mut point = point.borrow_mut();
+            *point.pos_mut() = *pose.pos();
+            *point.ori_mut() = *pose.ori();
+        } else {
+            self.add_point(name, PointRef::new(pose.to_wo()));
+        }
+    }
+
+    /// Defines a user frame with the 3-point method (see `frame_from_points()`) and stores it with the given name,
+    /// use the names in `WORK_OFFSETS` to make it selectable by `G54` to `G59`
+    pub fn teach_frame<S : Into<String>>(&mut self, name : S, origin : Vec3, x_point : Vec3, xy_point : Vec3)
+        -> Result<Position, crate::Error>
+    {
+        let pose = frame_from_points(origin, x_point, xy_point)?;
+        self.set_frame(name, pose.clone());
+        Ok(pose)
+    }
+}
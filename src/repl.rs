@@ -0,0 +1,115 @@
+use std::io::{BufRead, Write};
+
+use serde::Serialize;
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Interpreter, Robot};
+
+/// Default G/M codes offered for tab-completion
+pub const DEFAULT_CODES : [&str; 12] = [ "G0", "G1", "G2", "G3", "G4", "G17", "G18", "G19", "G28", "M3", "M5", "M30" ];
+
+/// An interactive REPL (Read-Eval-Print-Loop) front end for interpreters, used for bench commissioning
+/// 
+/// Lines starting with `:` are REPL commands
+/// - `:history` prints the history of the session
+/// - `:quit` exits the loop
+pub struct Repl {
+    history : Vec<String>,
+
+    /// Codes known by the interpreter, used for tab-completion
+    pub codes : Vec<String>,
+    /// The prompt printed before each line
+    pub prompt : String
+}
+
+impl Repl {
+    /// Creates a new REPL with the default codes
+    pub fn new() -> Self {
+        Self {
+            history: Vec::new(),
+            codes: DEFAULT_CODES.iter().map(|c| c.to_string()).collect(),
+            prompt: String::from("> ")
+        }
+    }
+
+    /// Returns the history of all evaluated lines
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Returns all possible completions for the last word of the given `line`, including known codes and 
+    /// the named positions of the descriptor
+    pub fn complete<D : Descriptor<C>, const C : usize>(&self, desc : &D, line : &str) -> Vec<String> {
+        let word = line.rsplit(' ').next().unwrap_or("");
+        let mut candidates : Vec<String> = self.codes.iter()
+            .chain(desc.world_obj().paths().iter())
+            .filter(|c| c.starts_with(word))
+            .cloned()
+            .collect();
+        
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+
+    /// Evaluates a single line and returns the results as pretty-printed JSON
+    pub fn eval<I, G, R, D, S, T, O, const C : usize>(&mut self, intpr : &I, rob : &mut R, desc : &mut D, stat : &mut S, line : &str) 
+        -> Result<String, crate::Error> 
+    where
+        I : Interpreter<G, R, D, S, T, O, C>,
+        G : SyncActuatorGroup<T, C>,
+        R : Robot<G, T, C>,
+        D : Descriptor<C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        O : Serialize
+    {
+        self.history.push(line.to_owned());
+        let res = intpr.interpret(rob, desc, stat, line);
+        Ok(serde_json::to_string_pretty(&res)?)
+    }
+
+    /// Runs the loop, reading lines from `input` and writing the results to `output` until `:quit` or the end of the input
+    pub fn run<I, G, R, D, S, T, O, const C : usize>(&mut self, intpr : &I, rob : &mut R, desc : &mut D, stat : &mut S, 
+        mut input : impl BufRead, mut output : impl Write) -> Result<(), crate::Error> 
+    where
+        I : Interpreter<G, R, D, S, T, O, C>,
+        G : SyncActuatorGroup<T, C>,
+        R : Robot<G, T, C>,
+        D : Descriptor<C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        O : Serialize
+    {
+        let mut line = String::new();
+
+        loop {
+            write!(output, "{}", self.prompt)?;
+            output.flush()?;
+
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+
+            match line.trim() {
+                "" => continue,
+                ":quit" => return Ok(()),
+                ":history" => {
+                    for (i, entry) in self.history.iter().enumerate() {
+                        writeln!(output, "{:>4} {}", i, entry)?;
+                    }
+                },
+                code => {
+                    let res = self.eval(intpr, rob, desc, stat, code)?;
+                    writeln!(output, "{}", res)?;
+                }
+            }
+        }
+    }
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,5 @@
 use core::fmt::Debug;
+use core::future::Future;
 
 use glam::Vec3;
 use syact::math::movements::DefinedActuator;
@@ -7,26 +8,123 @@ use syunit::*;
 use tokio::task::JoinSet;
 
 // use crate::pkg::info::AngConf;
-use crate::{Descriptor, PushRemote};
-use crate::config::AngleConfig;
-use crate::rcs::Position;
+use crate::{Descriptor, Identity, PushMsg, PushRemote, Station, SyError};
+use crate::catalog::{ERR_INVALID_PHIS, ERR_MASKED_AXIS, ERR_NOT_HOMED};
+use crate::config::{AngleConfig, AxisCoupling};
+use crate::desc::SingularityGuard;
+use crate::cancel::CancelToken;
+use crate::estop::EStop;
+use crate::plan::RrtConnect;
+use crate::events::{EventBus, StateEvent, Subscription};
+use crate::rcs::{Interpolator, Point, Position};
+use crate::rcs::math::{snap_to_reachable, sub_phis};
+use crate::stat::{FrameTracking, ToolUsageTracker};
 
 // ####################
 // #    SUBMODULES    #
 // ####################
+    pub mod backend;
+    pub use backend::{BackendAxis, BackendConfig, BackendGroup, BackendKind, BackendRegistry, MotionBackend};
+
+    pub mod driver;
+    pub use driver::{DriverLink, DriverParams};
+
+    pub mod filter;
+    pub use filter::{FilterKind, PhiFilter};
+
+    pub mod gantry;
+    pub use gantry::{Gantry, GantryFault, GantrySide};
+
+    pub mod jog;
+    pub use jog::CartesianJog;
+
+    pub mod park;
+    pub use park::{CollisionCheck, Park, ParkStrategy};
+
+    pub mod soft_limits;
+    pub use soft_limits::{LimitZone, SoftLimit, SoftLimits};
+
+    pub mod speed;
+    pub use speed::SpeedOverride;
+
+    pub mod startup;
+    pub use startup::{PowerStage, StartupSequence};
+
     pub mod stepper;
-    pub use stepper::StepperRobot;
+    pub use stepper::{StepperRobot, StepperRobotBuilder};
 
     pub mod tool;
     pub use tool::Tool;
+
+    pub mod tuning;
+    pub use tuning::{AxisTuning, TuningSet};
 // 
 
 // ##############
 // #    VARS    #
 // ##############
+    /// The maximum movement of a masked axis accepted, covers rounding errors of the IK
+    const MASK_TOLERANCE : f32 = 1e-3;
+
+    /// The minimum speed factor movements are driven with after all scalings, so they never stall completely
+    const MIN_SPEED_F : f32 = 0.01;
+
+    /// Scales the speed factor given, the result is limited to `MIN_SPEED_F` and `Factor::MAX`
+    pub(crate) fn scale_speed_f(speed_f : Factor, scale : f32) -> Factor {
+        Factor::new((speed_f.0 * scale).clamp(MIN_SPEED_F, 1.0))
+    }
+
+    /// Runs the future until it finishes, the emergency stop is triggered or the token is cancelled (see `EStop::guard()` 
+    /// and `CancelToken::guard()`), in the latter cases the future is dropped and an error is returned
+    pub(crate) async fn guarded<O, F : Future<Output = Result<O, crate::Error>>>(estop : Option<&EStop>, 
+        cancel : Option<&CancelToken>, fut : F) -> Result<O, crate::Error> {
+        match (estop, cancel) {
+            (Some(estop), Some(cancel)) => estop.guard(cancel.guard(fut)).await,
+            (Some(estop), None) => estop.guard(fut).await,
+            (None, Some(cancel)) => cancel.guard(fut).await,
+            (None, None) => fut.await
+        }
+    }
+
+    /// Temporary settings of a running program, see `Robot::push_scope()`
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ProgramScope {
+        /// Coordinate offset (e.g. `G92`), added to all absolute cartesian targets (see `Robot::resolve_target()`)
+        pub offset : Vec3,
+        /// Limit of the TCP speed of all cartesian movements (see `Robot::limit_speed()`), joint movements without 
+        /// descriptor (`Robot::move_j()`) have no TCP speed and are not limited
+        pub speed_limit : Option<Velocity>
+    }
+
     #[derive(Clone, Debug)]
     pub struct Vars<const C : usize> {
         pub phis : [Phi; C],
+
+        /// Wheiter or not the robot has been homed since power-up
+        pub homed : bool,
+        /// Wheiter or not the robot requires homing before absolute movements
+        pub require_homing : bool,
+        /// Soft limits with margin zones, path movements are slowed down inside the margins, joint movements only 
+        /// publish warnings
+        pub soft_limits : Option<SoftLimits<C>>,
+        /// Singularity thresholds, path movements are slowed down close to singular configurations and rejected if they
+        /// pass through them
+        pub singularity : Option<SingularityGuard>,
+        /// Axes masked out (e.g. after a motor fault), they are frozen at their last `Gamma` value and movements 
+        /// requiring them are rejected
+        pub masked : [bool; C],
+        /// Maximum distance cartesian targets out of reach are snapped to the nearest reachable position, `None` 
+        /// rejects all targets out of reach, see `Robot::phis_for_target()`
+        pub snap_tolerance : Option<f32>,
+        /// The temporary settings of the running program
+        pub scope : ProgramScope,
+        /// The settings of the enclosing scopes, restored when the running program ends
+        pub scopes : Vec<ProgramScope>,
+        /// Moving frame (e.g. a conveyor, see `ExternalAxis::tracking()`) cartesian targets are given relative to, 
+        /// see `Robot::phis_for_tracked()`
+        pub tracking : Option<FrameTracking>,
+        /// The identity of the robot, added to all messages pushed to the remotes (see `Robot::push_msg()`)
+        pub identity : Option<Identity>
     }
 
     impl<const C : usize> Vars<C> {
@@ -46,12 +144,48 @@ use crate::rcs::Position;
     impl<const C : usize> Default for Vars<C> {
         fn default() -> Self {
             Self {
-                phis: [Phi::default(); C]
+                phis: [Phi::default(); C],
+
+                homed: false,
+                require_homing: false,
+                soft_limits: None,
+                singularity: None,
+                masked: [false; C],
+                snap_tolerance: None,
+                scope: ProgramScope::default(),
+                scopes: Vec::new(),
+                tracking: None,
+                identity: None
             }
         }
     }
 // 
 
+// ################
+// #    ERRORS    #
+// ################
+    /// Error returned by verified movements, listing all axes outside of the tolerance given
+    #[derive(Clone, Debug)]
+    pub struct VerifyError {
+        /// Axes outside of the tolerance as tuples of (index, expected, actual)
+        pub axes : Vec<(usize, Gamma, Gamma)>,
+        /// The tolerance used
+        pub tolerance : f32
+    }
+
+    impl core::fmt::Display for VerifyError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "Axes outside of tolerance ({}):", self.tolerance)?;
+            for (index, expected, actual) in &self.axes {
+                write!(f, " [{}: expected {}, actual {}]", index, expected.0, actual.0)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl std::error::Error for VerifyError { }
+// 
+
 // ###############
 // #    ROBOT    #
 // ###############
@@ -82,18 +216,38 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
         /// Returns the robots angle configuration
         fn ang_confs(&self) -> &[AngleConfig; C];
 
+        /// Returns a mutable reference to the robots angle configuration, e.g. for writing back calibration offsets
+        fn ang_confs_mut(&mut self) -> &mut [AngleConfig; C];
+
+        /// Returns the mechanical couplings between the axes of the robot, applied in all `Gamma` / `Phi` conversions
+        fn couplings(&self) -> &[AxisCoupling] {
+            &[]
+        }
+
         /// Returns a reference to the component group of the robot
         fn comps(&self) -> &G;
 
         /// Returns a mutable reference to the component group of the robot 
         fn comps_mut(&mut self) -> &mut G;
+
+        /// Returns the external backends driving some of the components, `None` if all components are driven by `syact`
+        fn backends(&self) -> Option<&BackendGroup<C>> {
+            None
+        }
+
+        /// Returns a mutable reference to the external backends of the robot, see `backends()`
+        fn backends_mut(&mut self) -> Option<&mut BackendGroup<C>> {
+            None
+        }
     // 
 
     // Gamma & Phi - Distances
-        /// All the angles used by the controls to represent the components extension/drive distance
+        /// All the angles used by the controls to represent the components extension/drive distance, components 
+        /// driven by external backends report the positions tracked by their backends
         #[inline]
         fn gammas(&self) -> [Gamma; C] {
-            self.comps().gammas()
+            let gammas = self.comps().gammas();
+            self.backends().map_or(gammas, |backends| backends.merge_gammas(gammas))
         }
 
         /// Converts all angles (by adding offset and sometimes mirroring the value)
@@ -105,6 +259,10 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
                 gammas[i] = infos[i].gamma_from_phi(phis[i]);
             }
 
+            for coupling in self.couplings() {
+                gammas[coupling.target] = Gamma(gammas[coupling.target].0 + coupling.factor * phis[coupling.source].0);
+            }
+
             gammas
         }
         
@@ -123,6 +281,17 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
                 phis[i] = infos[i].phi_from_gamma(gammas[i]);
             }
 
+            // Couplings are ordered, so the source phi is always final. Multiple couplings can move the same target,
+            // their contributions are summed up
+            let mut coupled = [0.0f32; C];
+
+            for coupling in self.couplings() {
+                coupled[coupling.target] += coupling.factor * phis[coupling.source].0;
+                phis[coupling.target] = infos[coupling.target].phi_from_gamma(
+                    Gamma(gammas[coupling.target].0 - coupled[coupling.target])
+                );
+            }
+
             phis
         }
 
@@ -133,90 +302,602 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
             ) {
                 Ok(())
             } else {
-                Err("The given phis are invalid!".into())
+                let phis = phis.map(|p| p.0);
+                Err(SyError::kinematics("The given phis are invalid!")
+                    .with_detail(ERR_INVALID_PHIS).with_param("phis", format!("{:?}", phis)).into())
+            }
+        }
+
+        /// Returns the `Phi` values required to reach the cartesian target. Targets marginally out of reach (of the 
+        /// descriptor or the limits of the robot) are snapped to the nearest reachable position within 
+        /// `Vars::snap_tolerance`, the correction applied is published as `StateEvent::TargetSnapped`. Returns the 
+        /// position actually used together with its `Phi` values
+        fn phis_for_target<D : Descriptor<C>>(&self, desc : &D, target : Position) -> Result<(Position, [Phi; C]), crate::Error> {
+            // Descriptors may return NaN values for unreachable targets instead of an error
+            let finite = |phis : [Phi; C]| -> Result<[Phi; C], crate::Error> {
+                if phis.iter().all(|phi| phi.0.is_finite()) {
+                    Ok(phis)
+                } else {
+                    Err(SyError::kinematics("The descriptor returned invalid (NaN) values for the target!").into())
+                }
+            };
+
+            let err = match desc.phis_for_pos(target.clone()).and_then(finite).and_then(|phis| self.valid_phis(&phis).map(|_| phis)) {
+                Ok(phis) => return Ok((target, phis)),
+                Err(err) => err
+            };
+
+            let Some(tolerance) = self.vars().snap_tolerance else {
+                return Err(err);
+            };
+
+            let with_pos = |pos : Vec3| Position::new_ori(pos, *target.ori());
+            let reachable = |pos : Vec3| desc.phis_for_pos(with_pos(pos)).and_then(finite)
+                .map_or(false, |phis| self.valid_phis(&phis).is_ok());
+
+            let snapped = snap_to_reachable(*target.pos(), desc.tcp().pos(), tolerance, reachable)
+                .ok_or_else(|| SyError::kinematics(format!("{} (No reachable position within the snap tolerance of {})", 
+                    err, tolerance)))?;
+
+            let correction = snapped - *target.pos();
+            self.events().publish(StateEvent::TargetSnapped { correction });
+
+            let pos = with_pos(snapped);
+            let phis = desc.phis_for_pos(pos.clone())?;
+            Ok((pos, phis))
+        }
+
+        /// Resolves an absolute cartesian target of the running program, adding the offset of the program scope
+        fn resolve_target(&self, target : Position) -> Position {
+            let mut target = target;
+            target.shift(self.vars().scope.offset);
+            target
+        }
+
+        /// Returns the pose of the TCP in the system targets are given in, relative to the tracked frame if there is one 
+        /// (see `Vars::tracking`)
+        fn tcp_pose<D : Descriptor<C>>(&self, desc : &D) -> Position {
+            let pose = Position::new_ori(desc.tcp().pos(), desc.tcp().ori());
+
+            match &self.vars().tracking {
+                Some(tracking) => tracking.untrack(&pose),
+                None => pose
             }
         }
+
+        /// Same as `phis_for_target()`, but the target is given relative to the tracked frame if there is one. As the 
+        /// frame moves on during the movement, the target is resolved at the time it is reached with the `speed_f` 
+        /// given. Targets on tracked frames are not snapped
+        fn phis_for_tracked<D : Descriptor<C>>(&self, desc : &D, target : Position, speed_f : Factor) 
+        -> Result<(Position, [Phi; C]), crate::Error> {
+            let Some(tracking) = &self.vars().tracking else {
+                return self.phis_for_target(desc, target);
+            };
+
+            let resolve = |dt : f32| -> Result<(Position, [Phi; C]), crate::Error> {
+                let pose = tracking.track(&target, dt);
+                let phis = desc.phis_for_pos(pose.clone())?;
+                self.valid_phis(&phis)?;
+                Ok((pose, phis))
+            };
+
+            let (_, phis) = resolve(0.0)?;
+            resolve(self.time_for(&sub_unit_arrays(self.gammas_from_phis(phis), self.gammas()), speed_f))
+        }
+
+        /// Limits the TCP speed given to the speed limit of the program scope
+        fn limit_speed(&self, speed : Velocity) -> Velocity {
+            self.vars().scope.speed_limit.map_or(speed, |limit| Velocity(speed.0.min(limit.0)))
+        }
+
+        /// Limits the speed factor of a joint movement to the `phis` given with a TCP `distance` to the speed limit of 
+        /// the program scope (see `limit_speed()`)
+        fn limit_speed_f(&self, speed_f : Factor, phis : &[Phi; C], distance : f32) -> Factor {
+            let Some(limit) = self.vars().scope.speed_limit else {
+                return speed_f;
+            };
+
+            let deltas = sub_unit_arrays(self.gammas_from_phis(*phis), self.gammas());
+            let limit_f = self.speed_f_for_time(&deltas, distance / limit.0);
+
+            if limit_f.0 < speed_f.0 { limit_f } else { speed_f }
+        }
+
+        /// Checks the given `Phi` values against the soft limits of the robot, publishing a `StateEvent::LimitWarning` 
+        /// if they are inside of a margin zone. Returns the speed factor to apply (`1.0` without soft limits)
+        fn soft_limit_factor(&self, phis : &[Phi; C]) -> f32 {
+            let Some(limits) = self.vars().soft_limits.as_ref() else {
+                return 1.0;
+            };
+
+            if let LimitZone::Margin { axis, distance } = limits.zone(phis) {
+                self.events().publish(StateEvent::LimitWarning { axis, distance });
+            }
+
+            limits.factor(phis)
+        }
+
+        /// Scales the speed factor given by `scale` (e.g. the soft limit factor) and the speed override of the robot, 
+        /// the result is limited to `MIN_SPEED_F` and `Factor::MAX`. Applied once to every drive by `drive_j()`
+        fn scaled_speed_f(&self, speed_f : Factor, scale : f32) -> Factor {
+            scale_speed_f(speed_f, scale * self.speed_override().map_or(1.0, |o| o.get()))
+        }
     // 
 
     // Synchronous movements
         /// # `move_j` - Joints movement / PTP Movement
         /// 
-        /// TODO: Docs
+        /// Drives the components by the given deltas with `rapid_j()`, so the movement is guarded by the emergency stop 
+        /// and the cancel token of the robot and the laser tool equipped is gated
         async fn move_j_sync(&mut self, deltas : [Delta; C], speed_f : Factor) -> Result<(), crate::Error> {
-            let futures = self.comps_mut().drive_rel(deltas, [speed_f; C]);
-            for future in futures.into_iter() {
-                future.await?;
-            }
-            Ok(())
+            self.rapid_j(deltas, speed_f).await
         }
 
+        /// Absolute version of `move_j_sync()`, requires the robot to be homed (see `check_homed()`)
         async fn move_abs_j_sync(&mut self, phis : [Phi; C], speed_f : Factor) -> Result<(), crate::Error> {
-            let gammas = self.gammas_from_phis(phis);
-            let futures = self.comps_mut().drive_abs(gammas, [speed_f; C]);
-            for future in futures.into_iter() {
-                future.await?;
-            }
-            Ok(())
+            self.check_homed()?;
+
+            let deltas = sub_unit_arrays(self.gammas_from_phis(phis), self.gammas());
+            self.rapid_j(deltas, speed_f).await
         }
 
-        async fn move_p_sync<D : Descriptor<C>>(&mut self, desc : &mut D, p : Position, speed_f : Factor) -> Result<(), crate::Error> {
-            let phis = desc.phis_for_pos(p)?;
+        async fn move_p_sync<D : Descriptor<C>>(&mut self, desc : &mut D, p : Position, speed_f : Factor) -> Result<(), crate::Error> 
+        where Self: Sized {
+            let (target, phis) = self.phis_for_tracked(desc, self.resolve_target(p), speed_f)?;
+            let distance = desc.tcp().pos().distance(*target.pos());
+            let speed_f = self.limit_speed_f(speed_f, &phis, distance);
+
             self.move_abs_j_sync(
                 phis,
                 speed_f
-            ).await
+            ).await?;
+
+            // The TCP of the descriptor is updated, so relative movements start at the position reached
+            desc.update(self, &phis)?;
+            self.track_travel(distance);
+            Ok(())
         }
     // 
     
     // Asnychronous movement (complex movement)
         async fn move_j(&mut self, deltas : [Delta; C], gen_speed_f : Factor) -> Result<(), crate::Error> {
+            self.rapid_j(deltas, gen_speed_f).await
+        }
+
+        /// Drives the deltas given with `drive_j()` as a rapid movement, the laser tool equipped is gated (turned off) 
+        /// for the duration of the drive. All joint and point to point movements (including `G0`) are rapids, path 
+        /// movements are not. The laser stops firing if the drive fails, as the beam would otherwise be restored at an 
+        /// unknown position
+        async fn rapid_j(&mut self, deltas : [Delta; C], gen_speed_f : Factor) -> Result<(), crate::Error> {
+            if let Some(laser) = self.get_tool_mut().and_then(|t| t.laser_tool_mut()) {
+                laser.gate(true)?;
+            }
+
+            let res = self.drive_j(deltas, gen_speed_f).await;
+
+            if let Some(laser) = self.get_tool_mut().and_then(|t| t.laser_tool_mut()) {
+                if res.is_err() {
+                    laser.cease()?;
+                }
+
+                laser.gate(false)?;
+            }
+
+            res
+        }
+
+        /// Drives all the components by the given deltas, synchronized to arrive at the same time. Unlike `move_j` 
+        /// the tool is not touched, used for the segments of path movements. All movements of the robot are driven by 
+        /// this method, which applies the speed override (see `scaled_speed_f()`). Components with external backends are 
+        /// driven by their backends (see `backends()`), which requires the maximum velocities of the robot
+        async fn drive_j(&mut self, deltas : [Delta; C], gen_speed_f : Factor) -> Result<(), crate::Error> {
+            self.check_masked(&deltas)?;
+
+            let estop = self.estop().cloned();
+            if let Some(estop) = &estop {
+                estop.check()?;
+            }
+
+            let cancel = self.cancel_token().cloned();
+            if let Some(cancel) = &cancel {
+                cancel.check()?;
+            }
+
+            // Drives are held while the speed override is at zero (e.g. while an execution is paused, see 
+            // `ExecControl`), so paths and arcs stop at the next segment
+            if let Some(speed) = self.speed_override().cloned() {
+                guarded(estop.as_ref(), cancel.as_ref(), async { speed.resumed().await; Ok(()) }).await?;
+            }
+
+            // Masked axes are never driven, not even by rounding errors
+            let mut deltas = deltas;
+            for i in 0 .. C {
+                if self.vars().masked[i] {
+                    deltas[i] = Delta::ZERO;
+                }
+            }
+
             let gamma_0 = self.gammas();
             let gamma_t = add_unit_arrays(gamma_0, deltas);
-            let speed_f = syact::math::movements::ptp_speed_factors(
-                self.comps_mut(), gamma_0, gamma_t, gen_speed_f
-            );
+
+            // Movements ending inside of a soft limit margin are slowed down, the speed override applies to all drives
+            let limit_f = self.soft_limit_factor(&self.phis_from_gammas(gamma_t));
+            let gen_speed_f = self.scaled_speed_f(gen_speed_f, limit_f);
+
+            // Components driven by external backends are taken out of the `syact` drives
+            let mut comp_deltas = deltas;
+            let mut comp_gamma_t = gamma_t;
+            let mut external = false;
+
+            if let Some(backends) = self.backends() {
+                for i in 0 .. C {
+                    if backends.is_external(i) {
+                        comp_deltas[i] = Delta::ZERO;
+                        comp_gamma_t[i] = gamma_0[i];
+                        external = true;
+                    }
+                }
+            }
+
+            let omega_max = if external {
+                Some(self.omega_max().ok_or_else(|| SyError::config(
+                    "Components with external backends require the maximum velocities of the robot, see `Robot::set_omega_max()`!"))?)
+            } else {
+                None
+            };
+
+            // With external components all axes are timed by one common duration, as the `syact` drives and the 
+            // backends would otherwise arrive at different times
+            let time = omega_max.map(|_| self.time_for(&deltas, gen_speed_f));
+
+            let speed_f = match (omega_max, time) {
+                (Some(omega_max), Some(time)) if time > 0.0 => core::array::from_fn(|i| 
+                    scale_speed_f(Factor::MAX, comp_deltas[i].0.abs() / time / omega_max[i].0.max(f32::EPSILON))
+                ),
+                _ => syact::math::movements::ptp_speed_factors(
+                    self.comps_mut(), gamma_0, comp_gamma_t, gen_speed_f
+                )
+            };
 
             let mut set = JoinSet::new();
 
-            for fut in <G as SyncActuatorGroup<T, C>>::drive_rel(self.comps_mut(), deltas, speed_f) {
+            for fut in <G as SyncActuatorGroup<T, C>>::drive_rel(self.comps_mut(), comp_deltas, speed_f) {
                 set.spawn(fut);
             }
 
-            while let Some(res) = set.join_next().await {
-                res??;
+            let backends = self.backends_mut();
+
+            let drives = async move {
+                // The drives of the `syact` components are already running in the set, dropping this future stops 
+                // the external components as well
+                if let (Some(backends), Some(time)) = (backends, time) {
+                    backends.drive_abs_within(gamma_t, time, None).await?;
+                }
+
+                while let Some(res) = set.join_next().await {
+                    res?.map_err(crate::Error::from_syact)?;
+                }
+
+                Ok::<(), crate::Error>(())
+            };
+
+            // Dropping the set aborts all the drives of the components
+            let res = guarded(estop.as_ref(), cancel.as_ref(), drives).await;
+
+            let aborted = estop.as_ref().map_or(false, EStop::is_triggered) 
+                | cancel.as_ref().map_or(false, CancelToken::is_cancelled);
+
+            if let Err(err) = &res {
+                if aborted {
+                    // The components are left wherever their drives have been aborted, they are halted explicitly 
+                    // and the positions are re-synced before the error is returned
+                    let synced = self.halt().and_then(|_| self.update());
+                    self.events().publish(StateEvent::Error(err.to_string()));
+
+                    if let Err(sync_err) = synced {
+                        return Err(SyError::safety(format!("{} (Halting failed: {})", err, sync_err)).into());
+                    }
+                }
             }
 
-            Ok(())
+            res
         }
 
         async fn move_abs_j(&mut self, phis : [Phi; C], gen_speed_f : Factor) -> Result<(), crate::Error> {
+            self.check_homed()?;
+
             let gamma_0 = self.gammas();
             let gamma_t = self.gammas_from_phis(phis);
             let deltas = sub_unit_arrays(gamma_t, gamma_0);
             self.move_j(deltas, gen_speed_f).await
         }
 
+        /// Limits the deltas of a jog step with the soft limits of the robot, so the jog stops exactly at the limits 
+        /// (see `SoftLimits::limit_jog()`), the deceleration in front of them is done by `drive_j()` with the soft limit 
+        /// factor. Returns the limited deltas and the axis whose limit is reached with them, if any
+        fn limit_jog(&self, deltas : [Delta; C]) -> ([Delta; C], Option<usize>) {
+            let Some(limits) = self.vars().soft_limits.as_ref() else {
+                return (deltas, None);
+            };
+
+            let phis = self.phis();
+            let mut gammas = self.gammas();
+            for i in 0 .. C {
+                gammas[i] = Gamma(gammas[i].0 + deltas[i].0);
+            }
+
+            let phi_deltas = sub_phis(self.phis_from_gammas(gammas), phis);
+            let (limited, stop) = limits.limit_jog(&phis, &phi_deltas);
+
+            // All deltas are scaled by the same factor, the largest one gives the most accurate ratio
+            let Some(axis) = (0 .. C).max_by(|a, b| phi_deltas[*a].0.abs().total_cmp(&phi_deltas[*b].0.abs())) else {
+                return (deltas, None);
+            };
+
+            if phi_deltas[axis].0 == 0.0 {
+                return (deltas, None);
+            }
+
+            let scale = limited[axis].0 / phi_deltas[axis].0;
+            let mut deltas = deltas;
+            for delta in deltas.iter_mut() {
+                delta.0 *= scale;
+            }
+
+            (deltas, stop)
+        }
+
+        /// Jogs a single axis by the `delta` given, the jog decelerates in front of the soft limits and stops exactly 
+        /// at them instead of being rejected, publishing a `StateEvent::JogLimit` (see `limit_jog()`)
+        async fn jog_j(&mut self, axis : usize, delta : Delta, speed_f : Factor) -> Result<(), crate::Error> {
+            if axis >= C {
+                return Err(SyError::config(format!("Invalid axis index! (Index: {}, Axes: {})", axis, C)).into());
+            }
+
+            let mut deltas = [Delta::ZERO; C];
+            deltas[axis] = delta;
+
+            let (deltas, stop) = self.limit_jog(deltas);
+            self.move_j(deltas, speed_f).await?;
+
+            if let Some(axis) = stop {
+                self.events().publish(StateEvent::JogLimit { axis });
+            }
+
+            Ok(())
+        }
+
+        /// Masks or unmasks the given axis, masked axes are frozen at their current `Gamma` value so the rest of the 
+        /// robot can still be moved, e.g. for recovery after a motor fault
+        fn set_masked(&mut self, axis : usize, masked : bool) -> Result<(), crate::Error> {
+            if axis >= C {
+                return Err(SyError::config(format!("Invalid axis index! (Axis: {}, Axes: {})", axis, C)).into());
+            }
+
+            self.vars_mut().masked[axis] = masked;
+            Ok(())
+        }
+
+        /// Returns an error if any masked axis would be moved by the given deltas
+        fn check_masked(&self, deltas : &[Delta; C]) -> Result<(), crate::Error> {
+            for i in 0 .. C {
+                if self.vars().masked[i] & (deltas[i].0.abs() > MASK_TOLERANCE) {
+                    return Err(SyError::safety(format!("The movement requires the masked axis {}! (Delta: {})", i, deltas[i].0))
+                        .with_detail(ERR_MASKED_AXIS).with_param("axis", i).with_param("delta", deltas[i].0).into());
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Returns an error if any axis is masked, used by operations requiring all the axes (e.g. homing, see 
+        /// `Station::home_checked()`)
+        fn check_unmasked(&self) -> Result<(), crate::Error> {
+            match self.vars().masked.iter().position(|m| *m) {
+                Some(axis) => Err(SyError::safety(format!("The operation requires all axes, but axis {} is masked!", 
+                    axis)).into()),
+                None => Ok(())
+            }
+        }
+
+        /// Collapses the ranges of all masked axes to their current `Phi` value, so planners sampling the joint space 
+        /// (e.g. `RrtConnect`, `Envelope`) keep them frozen
+        fn freeze_masked(&self, mut limits : [(Phi, Phi); C]) -> [(Phi, Phi); C] {
+            let phis = self.phis();
+
+            for i in 0 .. C {
+                if self.vars().masked[i] {
+                    limits[i] = (phis[i], phis[i]);
+                }
+            }
+
+            limits
+        }
+
+        /// Starts a new program scope, offsets and speed limits set by the program until `pop_scope()` is called are 
+        /// temporary. Called by the program runners (e.g. `Interpreter::run_program()`) before the first line
+        fn push_scope(&mut self) {
+            let vars = self.vars_mut();
+            vars.scopes.push(vars.scope);
+        }
+
+        /// Ends the current program scope, restoring the offset and speed limit active before `push_scope()`. Called by
+        /// the program runners when the program ends or aborts
+        fn pop_scope(&mut self) {
+            let vars = self.vars_mut();
+            vars.scope = vars.scopes.pop().unwrap_or_default();
+        }
+
+        /// Returns an error if the robot requires homing before absolute movements and has not been homed yet
+        fn check_homed(&self) -> Result<(), crate::Error> {
+            if self.vars().require_homing & !self.vars().homed {
+                Err(SyError::safety("The robot has to be homed before absolute movements!").with_detail(ERR_NOT_HOMED).into())
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Marks the robot as homed, should be called by `Station::home()` after a successful homing
+        fn set_homed(&mut self) {
+            self.vars_mut().homed = true;
+        }
+
+        /// Drives to the given absolute `gammas` and re-reads the actual positions afterwards, returning a [VerifyError] 
+        /// if any axis is outside of the `tolerance` given. Used for quality-critical placement steps
+        async fn drive_abs_verified(&mut self, gammas : [Gamma; C], gen_speed_f : Factor, tolerance : f32) -> Result<(), crate::Error> {
+            self.check_homed()?;
+
+            let deltas = sub_unit_arrays(gammas, self.gammas());
+            self.move_j(deltas, gen_speed_f).await?;
+
+            let actual = self.gammas();
+            let axes : Vec<(usize, Gamma, Gamma)> = (0 .. C)
+                .filter(|i| (actual[*i].0 - gammas[*i].0).abs() > tolerance)
+                .map(|i| (i, gammas[i], actual[i]))
+                .collect();
+
+            if axes.is_empty() {
+                Ok(())
+            } else {
+                Err(VerifyError { axes, tolerance }.into())
+            }
+        }
+
+        /// Verified version of `move_abs_j`, see `drive_abs_verified`
+        async fn move_abs_j_verified(&mut self, phis : [Phi; C], gen_speed_f : Factor, tolerance : f32) -> Result<(), crate::Error> {
+            let gammas = self.gammas_from_phis(phis);
+            self.drive_abs_verified(gammas, gen_speed_f, tolerance).await
+        }
+
         async fn move_l<D : Descriptor<C>>(&mut self, desc : &mut D, distance : Vec3, accuracy : f32, speed : Velocity) -> Result<(), crate::Error>;
 
+        /// Moves the TCP from its current position to `target` along the waypoints created by the given interpolator, 
+        /// spaced by `accuracy` at most. All waypoints are validated before the robot starts moving, the segments are 
+        /// then driven one after another (without blending) with the TCP `speed` given, see `speed_f_for_time()`. The 
+        /// target is given in program coordinates (see `resolve_target()`), the target and the speed are relative to the 
+        /// tracked frame if there is one (see `Vars::tracking`)
+        async fn move_path<D : Descriptor<C>>(&mut self, desc : &mut D, interp : &dyn Interpolator, target : Position, 
+            accuracy : f32, speed : Velocity) -> Result<(), crate::Error> 
+        where Self: Sized {
+            if (accuracy <= 0.0) | (speed.0 <= 0.0) {
+                return Err(SyError::kinematics(format!("The accuracy and speed of a path movement must be positive! (Accuracy: {}, Speed: {})", 
+                    accuracy, speed.0)).into());
+            }
+
+            // The full pose is kept, so orientation-only movements and closed paths (e.g. full circles) are driven too
+            let start = self.tcp_pose(desc);
+            let target = self.resolve_target(target);
+            let target = match self.vars().tracking {
+                Some(_) => target,
+                None => self.phis_for_target(desc, target)?.0
+            };
+
+            let path = interp.interpolate(&start, &target, accuracy)?;
+            let speed = self.limit_speed(speed);
+
+            // Resolve and validate all waypoints before moving, waypoints on a tracked frame are resolved at the time 
+            // they are reached
+            let mut waypoints = Vec::with_capacity(path.len());
+            let mut last = *start.pos();
+            let mut time = 0.0;
+
+            for (i, pos) in path.into_iter().enumerate() {
+                let seg_time = pos.pos().distance(last) / speed.0;
+                last = *pos.pos();
+                time += seg_time;
+
+                let pos = match &self.vars().tracking {
+                    Some(tracking) => tracking.track(&pos, time),
+                    None => pos
+                };
+
+                let phis = desc.phis_for_pos(pos)?;
+
+                // Descriptors may return NaN values for unreachable waypoints instead of an error
+                if !phis.iter().all(|phi| phi.0.is_finite()) {
+                    return Err(SyError::kinematics(format!("Waypoint {} of the path movement is unreachable! (Phis: {:?})", 
+                        i + 1, phis.map(|p| p.0))).with_detail(ERR_INVALID_PHIS).into());
+                }
+
+                self.valid_phis(&phis).map_err(|err| SyError::kinematics(format!("Waypoint {} of the path movement is invalid! ({})", 
+                    i + 1, err)))?;
+                self.check_masked(&sub_unit_arrays(self.gammas_from_phis(phis), self.gammas()))
+                    .map_err(|err| SyError::kinematics(format!("Waypoint {} of the path movement is invalid! ({})", 
+                        i + 1, err)))?;
+
+                let factor = match self.vars().singularity {
+                    Some(guard) => {
+                        let m = desc.manipulability(&phis)?;
+                        guard.factor(m).ok_or_else(|| SyError::kinematics(
+                            format!("Waypoint {} of the path movement is too close to a singularity! (Manipulability: {})", i + 1, m)))?
+                    },
+                    None => 1.0
+                };
+
+                waypoints.push((seg_time, phis, factor));
+            }
+
+            // Each segment is driven within the time required for its length at the TCP speed given, segments close 
+            // to singularities are slowed down, the soft limit factor and the speed override are applied by `drive_j()`
+            for (time, phis, sing_factor) in waypoints {
+                let deltas = sub_unit_arrays(self.gammas_from_phis(phis), self.gammas());
+                let speed_f = self.speed_f_for_time(&deltas, time);
+
+                self.drive_j(deltas, scale_speed_f(speed_f, sing_factor)).await?;
+                desc.update(self, &phis)?;
+                self.track_travel(time * speed.0);
+            }
+
+            Ok(())
+        }
+
+        /// Moves to the given position on a collision-free joint path found by the planner, see `RrtConnect`
+        async fn move_safe<D : Descriptor<C>>(&mut self, desc : &mut D, planner : &RrtConnect<'_, C>, target : Position, 
+            speed_f : Factor) -> Result<(), crate::Error> 
+        where Self: Sized {
+            let goal = desc.phis_for_pos(target)?;
+            self.check_masked(&sub_unit_arrays(self.gammas_from_phis(goal), self.gammas()))?;
+
+            // Masked axes are kept frozen by the planner
+            let planner = RrtConnect { limits: self.freeze_masked(planner.limits), check: planner.check, conf: planner.conf };
+            let path = planner.plan(self.phis(), goal)?;
+
+            for phis in path.into_iter().skip(1) {
+                self.move_abs_j(phis, speed_f).await?;
+                desc.update(self, &phis)?;
+            }
+
+            Ok(())
+        }
+
         async fn move_abs_l<D : Descriptor<C>>(&mut self, desc : &mut D, pos : Vec3, accuracy : f32, speed : Velocity) -> Result<(), crate::Error> {
-            let pos_0 = desc.tcp().pos();
+            // Both positions in program coordinates, the offset is added by `move_path()`
+            let pos_0 = *self.tcp_pose(desc).pos() - self.vars().scope.offset;
             self.move_l(desc, pos - pos_0, accuracy, speed).await
         }
 
         async fn move_p<D : Descriptor<C>>(&mut self, desc: &mut D, p : Position, speed_f : Factor) -> Result<(), crate::Error>
         where Self: Sized {
-            let phis = desc.phis_for_pos(p)?;
+            let (target, phis) = self.phis_for_tracked(desc, self.resolve_target(p), speed_f)?;
+            let distance = desc.tcp().pos().distance(*target.pos());
+            let speed_f = self.limit_speed_f(speed_f, &phis, distance);
+
             self.move_abs_j(
                 phis,
                 speed_f
-            ).await
+            ).await?;
+
+            // The TCP of the descriptor is updated, so relative movements start at the position reached
+            desc.update(self, &phis)?;
+            self.track_travel(distance);
+            Ok(())
         }
     // 
 
     // Loads & Limits
         #[inline]
         fn apply_forces(&mut self, forces : &[Force; C]) -> Result<(), crate::Error> {
-            self.comps_mut().apply_forces(forces)
+            self.comps_mut().apply_forces(forces).map_err(crate::Error::from_syact)
         }
 
         #[inline]
@@ -228,9 +909,61 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
             self.comps_mut().set_limits(min, max)
         }
 
+        /// Returns the minimum and maximum limits of the components set with `set_limits()`, `None` if they are unknown
+        fn limits(&self) -> Option<([Option<Gamma>; C], [Option<Gamma>; C])> {
+            None
+        }
+
         fn set_omega_max(&mut self, omega_max : [Velocity; C]) {
             self.comps_mut().set_velocity_max(omega_max)
         }
+
+        /// Returns the maximum velocities of the components set with `set_omega_max()`, `None` if they are unknown
+        fn omega_max(&self) -> Option<[Velocity; C]> {
+            None
+        }
+
+        /// Returns the speed factor required to drive the `deltas` within `time` (in seconds), the slowest component 
+        /// defines the factor as all components arrive at the same time. Returns `Factor::MAX` if the maximum velocities 
+        /// of the components are unknown (see `omega_max()`) or the movement cannot be done within `time`
+        fn speed_f_for_time(&self, deltas : &[Delta; C], time : f32) -> Factor {
+            let Some(omega_max) = self.omega_max() else {
+                return Factor::MAX;
+            };
+
+            if time <= 0.0 {
+                return Factor::MAX;
+            }
+
+            let factor = (0 .. C).filter(|i| omega_max[*i].0 > 0.0)
+                .map(|i| deltas[i].0.abs() / time / omega_max[i].0)
+                .fold(0.0, f32::max);
+
+            scale_speed_f(Factor::MAX, factor)
+        }
+
+        /// Returns the time (in seconds) required to drive the `deltas` with the `speed_f` given, the inverse of 
+        /// `speed_f_for_time()`. Returns `0.0` if the maximum velocities of the components are unknown
+        fn time_for(&self, deltas : &[Delta; C], speed_f : Factor) -> f32 {
+            let Some(omega_max) = self.omega_max() else {
+                return 0.0;
+            };
+
+            (0 .. C).filter(|i| omega_max[*i].0 > 0.0)
+                .map(|i| deltas[i].0.abs() / omega_max[i].0 / speed_f.0.max(MIN_SPEED_F))
+                .fold(0.0, f32::max)
+        }
+
+        /// Returns the speed override of the robot, `None` if the robot does not support overrides
+        fn speed_override(&self) -> Option<&SpeedOverride> {
+            None
+        }
+
+        /// Sets the speed override factor (`0.0` to `2.0`), scaling the velocities of all subsequent movements and of 
+        /// running path movements (see `scaled_speed_f()`)
+        fn set_speed_override(&mut self, factor : f32) -> Result<(), crate::Error> {
+            self.speed_override().ok_or_else(|| SyError::config("The robot does not support speed overrides!"))?.set(factor)
+        }
     // 
 
     // Tools
@@ -243,15 +976,43 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
         /// Returns a reference to all the tools registered in the robot
         fn get_tools(&self) -> &Vec<Box<dyn Tool>>;
 
+        /// Returns the id of the tool currently being used by the robot, used e.g. for tracking tool usage
+        fn get_tool_id(&self) -> Option<usize>;
+
         /// Sets the id of the tool to be used and performs an automatic tool swap if necessary
         fn set_tool_id(&mut self, tool_id : Option<usize>) -> Option<&mut dyn Tool>;
 
+        /// Returns the usage tracker of the tools, `None` if the robot does not track the usage of its tools
+        fn tool_usage(&self) -> Option<&ToolUsageTracker> {
+            None
+        }
+
+        /// Returns a mutable reference to the usage tracker of the tools, see `tool_usage()`
+        fn tool_usage_mut(&mut self) -> Option<&mut ToolUsageTracker> {
+            None
+        }
+
+        /// Adds the TCP travel given to the usage of the tool equipped, called by all cartesian movements
+        fn track_travel(&mut self, distance : f32) {
+            if let Some(tool_id) = self.get_tool_id() {
+                if let Some(usage) = self.tool_usage_mut() {
+                    usage.add_travel(tool_id, distance);
+                }
+            }
+        }
+
         // Wrapper functions
             fn activate_tool(&mut self) -> Result<&dyn tool::SimpleTool, crate::Error> {
+                if let (Some(tool_id), true) = (self.get_tool_id(), self.get_tool().map_or(false, |t| t.simple_tool().is_some())) {
+                    if let Some(usage) = self.tool_usage_mut() {
+                        usage.activate(tool_id);
+                    }
+                }
+
                 let tool = self.get_tool_mut()
-                    .ok_or("No tool has been equipped yet!")?;
+                    .ok_or_else(|| SyError::component("No tool has been equipped yet!"))?;
                 let simple_tool = tool.simple_tool_mut()
-                    .ok_or("The tool equipped is no 'SimpleTool' (cannot be activated)")?;
+                    .ok_or_else(|| SyError::component("The tool equipped is no 'SimpleTool' (cannot be activated)"))?;
                 
                 simple_tool.activate();
 
@@ -259,18 +1020,104 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
             } 
 
             fn deactivate_tool(&mut self) -> Result<&dyn tool::SimpleTool, crate::Error> {
+                if let Some(tool_id) = self.get_tool_id() {
+                    if let Some(usage) = self.tool_usage_mut() {
+                        usage.deactivate(tool_id);
+                    }
+                }
+
                 let tool = self.get_tool_mut()
-                    .ok_or("No tool has been equipped yet!")?;
+                    .ok_or_else(|| SyError::component("No tool has been equipped yet!"))?;
                 let simple_tool = tool.simple_tool_mut()
-                    .ok_or("The tool equipped is no 'SimpleTool' (cannot be deactivated)")?;
+                    .ok_or_else(|| SyError::component("The tool equipped is no 'SimpleTool' (cannot be deactivated)"))?;
                 
                 simple_tool.deactivate();
 
                 Ok(simple_tool)
             }
+
+            /// Sets the power of the laser tool equipped (`0.0` to `1.0`), equivalent to `M3 S..`
+            fn set_laser_power(&mut self, power : f32) -> Result<(), crate::Error> {
+                self.get_tool_mut()
+                    .ok_or_else(|| SyError::component("No tool has been equipped yet!"))?
+                    .laser_tool_mut()
+                    .ok_or_else(|| SyError::component("The tool equipped is no 'LaserTool' (cannot set power)"))?
+                    .set_power(power)
+            }
+
+            /// Fires the laser tool equipped, the interlock of the station (see [Station::interlock]) is read right 
+            /// before firing and must be satisfied
+            fn fire_laser<S : Station<G, T, C, Robot = Self>>(&mut self, stat : &S) -> Result<(), crate::Error> 
+            where Self: Sized {
+                let interlock = stat.interlock();
+
+                self.get_tool_mut()
+                    .ok_or_else(|| SyError::component("No tool has been equipped yet!"))?
+                    .laser_tool_mut()
+                    .ok_or_else(|| SyError::component("The tool equipped is no 'LaserTool' (cannot be fired)"))?
+                    .fire(interlock)
+            }
+
+            /// Stops firing the laser tool equipped
+            fn cease_laser(&mut self) -> Result<(), crate::Error> {
+                self.get_tool_mut()
+                    .ok_or_else(|| SyError::component("No tool has been equipped yet!"))?
+                    .laser_tool_mut()
+                    .ok_or_else(|| SyError::component("The tool equipped is no 'LaserTool' (cannot be ceased)"))?
+                    .cease()
+            }
         // 
     // 
 
+    // Safety
+        /// Returns the emergency stop guarding the drives of the robot, `None` if the robot has none
+        fn estop(&self) -> Option<&EStop> {
+            None
+        }
+
+        /// Returns the token cancelling the long-running operations of the robot, `None` if the robot has none. All 
+        /// the drives (and so all moves and probing) are aborted and halted once it is cancelled, homing with 
+        /// `Station::home_checked()` and the program runners stop as well. Unlike the emergency stop the token is 
+        /// meant for regular aborts by the caller
+        fn cancel_token(&self) -> Option<&CancelToken> {
+            None
+        }
+
+        /// Halts all the components immediately, called after the drives have been aborted (e.g. by the emergency 
+        /// stop). Components driven by `syact` stop as soon as their drives are dropped, the external backends of the 
+        /// robot are stopped and the positions reported by them are taken over
+        fn halt(&mut self) -> Result<(), crate::Error> {
+            if let Some(backends) = self.backends_mut() {
+                backends.stop()?;
+                backends.sync()?;
+            }
+
+            Ok(())
+        }
+
+        /// Halts a single component immediately, e.g. after its limit switch has triggered (see `LimitReaction`).
+        /// Components driven by `syact` stop as soon as their drives are dropped, the external backend of the
+        /// component is stopped and the position reported by it is taken over
+        fn halt_axis(&mut self, axis : usize) -> Result<(), crate::Error> {
+            if let Some(Some(backend)) = self.backends_mut().and_then(|b| b.axes.get_mut(axis)) {
+                backend.stop()?;
+                backend.sync()?;
+            }
+
+            Ok(())
+        }
+    // 
+
+    // Events
+        /// Returns the event bus of the robot
+        fn events(&self) -> &EventBus<C>;
+
+        /// Subscribes to the state events of the robot, position updates are limited to one per `rate`
+        fn subscribe(&self, rate : core::time::Duration) -> Subscription<C> {
+            self.events().subscribe(rate)
+        }
+    // 
+
     // Remote
         /// Adds a new remote to the robot
         fn add_remote(&mut self, remote : Box<dyn PushRemote>);
@@ -280,6 +1127,49 @@ pub trait Robot<G : SyncActuatorGroup<T, C>, T : SyncActuator + DefinedActuator
 
         /// Returns a mutable reference to all remotes of the robot
         fn remotes_mut<'a>(&'a mut self) -> &'a mut Vec<Box<dyn PushRemote>>;
+
+        /// Returns the identity of the robot, see `Vars::identity`
+        fn identity(&self) -> Option<&Identity> {
+            self.vars().identity.as_ref()
+        }
+
+        /// Sets the identity of the robot, all messages pushed afterwards carry it
+        fn set_identity(&mut self, identity : Option<Identity>) {
+            self.vars_mut().identity = identity;
+        }
+
+        /// Pushes the phis to all remotes of the robot, namespaced with the identity of the robot
+        fn push_phis(&mut self, phis : &[Phi]) -> Result<(), crate::Error> {
+            let identity = self.identity().cloned();
+
+            for remote in self.remotes_mut() {
+                remote.push_phis_from(identity.as_ref(), phis)?;
+            }
+
+            Ok(())
+        }
+
+        /// Pushes the message to all remotes of the robot, namespaced with the identity of the robot
+        fn push_msg(&mut self, msg : PushMsg) -> Result<(), crate::Error> {
+            let identity = self.identity().cloned();
+
+            for remote in self.remotes_mut() {
+                remote.push_other_from(identity.as_ref(), msg.clone())?;
+            }
+
+            Ok(())
+        }
+
+        /// Pushes the bytes to all remotes of the robot, namespaced with the identity of the robot
+        fn push_bytes(&mut self, msg_type : &str, msg : &[u8]) -> Result<(), crate::Error> {
+            let identity = self.identity().cloned();
+
+            for remote in self.remotes_mut() {
+                remote.push_any_from(identity.as_ref(), msg_type, msg)?;
+            }
+
+            Ok(())
+        }
     //
 
     // Events
@@ -0,0 +1,326 @@
+use core::time::Duration;
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+use syunit::*;
+
+use crate::SyError;
+use crate::estop::EStop;
+
+/// The kind of setpoints a component is driven with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    /// GPIO stepper driven directly by `syact`, the default for all components
+    #[default]
+    Gpio,
+    /// External controller executing streamed blocks of steps (e.g. a serial-connected step generator)
+    StepStream,
+    /// External controller accepting position setpoints (e.g. a servo drive with its own position loop)
+    Position
+}
+
+/// The backend selected for a single component in the configuration
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackendConfig {
+    /// The kind of backend
+    #[serde(default)]
+    pub kind : BackendKind,
+    /// Address of the external controller (e.g. `/dev/ttyACM0`), unused for GPIO
+    #[serde(default)]
+    pub port : Option<String>,
+    /// Channel of the component on the external controller
+    #[serde(default)]
+    pub channel : usize,
+    /// Steps per unit of `Gamma`, required for step streams
+    #[serde(default)]
+    pub steps_per_unit : Option<f32>,
+    /// Maximum deviation between the target and the position reported after a movement, `None` skips the check
+    #[serde(default)]
+    pub tolerance : Option<f32>
+}
+
+/// An external motion controller driving a single component
+///
+/// Backends only implement the setpoints they support, the remaining methods return an error by default
+pub trait MotionBackend : Send {
+    /// Sends a block of `steps` (negative values for the negative direction) to be executed evenly within `duration`
+    fn send_steps(&mut self, _steps : i64, _duration : Duration) -> Result<(), crate::Error> {
+        Err(SyError::component("The backend does not accept step streams!").into())
+    }
+
+    /// Sends a position setpoint, the controller moves to `gamma` with at most the velocity `omega`
+    fn send_setpoint(&mut self, _gamma : Gamma, _omega : Velocity) -> Result<(), crate::Error> {
+        Err(SyError::component("The backend does not accept position setpoints!").into())
+    }
+
+    /// Returns the position reported by the controller
+    fn gamma(&mut self) -> Result<Gamma, crate::Error>;
+
+    /// Stops the movement immediately
+    fn stop(&mut self) -> Result<(), crate::Error>;
+}
+
+/// A component driven by an external `MotionBackend`, tracking its position and the rounding remainder of step streams
+pub struct BackendAxis {
+    backend : Box<dyn MotionBackend>,
+    config : BackendConfig,
+
+    gamma : Gamma,
+    target : Option<Gamma>,
+    remainder : f32
+}
+
+impl BackendAxis {
+    /// Creates a new axis, fails if the configuration is incomplete for the kind of backend
+    pub fn new(backend : Box<dyn MotionBackend>, config : BackendConfig) -> Result<Self, crate::Error> {
+        match config.kind {
+            BackendKind::Gpio => return Err(SyError::config("GPIO components are driven by `syact`, not by a backend!").into()),
+            BackendKind::StepStream if config.steps_per_unit.map_or(true, |s| s <= 0.0) =>
+                return Err(SyError::config("Step stream backends require a positive `steps_per_unit`!").into()),
+            _ => { }
+        }
+
+        Ok(Self { backend, config, gamma: Gamma::ZERO, target: None, remainder: 0.0 })
+    }
+
+    /// Returns the configuration of the axis
+    pub fn config(&self) -> &BackendConfig {
+        &self.config
+    }
+
+    /// Returns the position of the axis as tracked by the library, movements sent are only taken over once they have
+    /// been completed (see `finish()`)
+    pub fn gamma(&self) -> Gamma {
+        self.gamma
+    }
+
+    /// Reads the position from the controller and takes it over, e.g. after homing or an aborted movement
+    pub fn sync(&mut self) -> Result<Gamma, crate::Error> {
+        self.target = None;
+        self.gamma = self.backend.gamma()?;
+        self.remainder = 0.0;
+        Ok(self.gamma)
+    }
+
+    /// Sends the movement to `gamma` with the velocity `omega` to the controller, returns the duration of the movement.
+    /// The position tracked is updated by `finish()` once the movement is done
+    pub fn send_abs(&mut self, gamma : Gamma, omega : Velocity) -> Result<Duration, crate::Error> {
+        let dist = gamma.0 - self.gamma.0;
+        let duration = if omega.0 > 0.0 { Duration::from_secs_f32(dist.abs() / omega.0) } else { Duration::ZERO };
+
+        match self.config.kind {
+            BackendKind::StepStream => {
+                // Steps lost by rounding are carried over into the next block
+                let exact = dist * self.config.steps_per_unit.unwrap_or(1.0) + self.remainder;
+                let steps = exact.round();
+
+                self.backend.send_steps(steps as i64, duration)?;
+                self.remainder = exact - steps;
+            },
+            BackendKind::Position => self.backend.send_setpoint(gamma, omega)?,
+            BackendKind::Gpio => return Err(SyError::config("GPIO components are driven by `syact`, not by a backend!").into())
+        }
+
+        self.target = Some(gamma);
+        Ok(duration)
+    }
+
+    /// Marks the movement sent last as completed, taking over its target position
+    pub fn finish(&mut self) {
+        if let Some(target) = self.target.take() {
+            self.gamma = target;
+        }
+    }
+
+    /// Compares the tracked position with the one reported by the controller, if a tolerance is configured
+    pub fn verify(&mut self) -> Result<(), crate::Error> {
+        let Some(tolerance) = self.config.tolerance else {
+            return Ok(());
+        };
+
+        let reported = self.backend.gamma()?;
+
+        if (reported.0 - self.gamma.0).abs() > tolerance {
+            return Err(SyError::safety(format!("The backend reports a deviating position! (Target: {}, Reported: {}, Tolerance: {})",
+                self.gamma.0, reported.0, tolerance)).into());
+        }
+
+        Ok(())
+    }
+
+    /// Stops the movement immediately
+    pub fn stop(&mut self) -> Result<(), crate::Error> {
+        self.backend.stop()
+    }
+}
+
+/// Function creating a backend from the configuration of a component
+pub type BackendFactory = Box<dyn Fn(&BackendConfig) -> Result<Box<dyn MotionBackend>, crate::Error>>;
+
+/// Creates the backends selected per component in the configuration, factories are registered for each kind of
+/// backend by the application (e.g. opening the serial port given)
+#[derive(Default)]
+pub struct BackendRegistry {
+    factories : HashMap<BackendKind, BackendFactory>
+}
+
+impl BackendRegistry {
+    /// Creates a new registry without any factories
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the factory for the given kind of backend, replacing an existing one
+    pub fn register(&mut self, kind : BackendKind, factory : BackendFactory) {
+        self.factories.insert(kind, factory);
+    }
+
+    /// Creates the axis for a single component, `None` for GPIO components
+    pub fn create(&self, config : &BackendConfig) -> Result<Option<BackendAxis>, crate::Error> {
+        if config.kind == BackendKind::Gpio {
+            return Ok(None);
+        }
+
+        let factory = self.factories.get(&config.kind)
+            .ok_or_else(|| SyError::config(format!("No factory registered for backends of kind {:?}!", config.kind)))?;
+
+        Ok(Some(BackendAxis::new(factory(config)?, config.clone())?))
+    }
+
+    /// Creates the axes for all components of a robot, the error contains the index of the failing component
+    pub fn create_group<const C : usize>(&self, configs : &[BackendConfig; C]) -> Result<BackendGroup<C>, crate::Error> {
+        let mut axes = Vec::with_capacity(C);
+
+        for (i, config) in configs.iter().enumerate() {
+            axes.push(self.create(config).map_err(|err| SyError::component(format!("Component {}: {}", i, err)))?);
+        }
+
+        Ok(BackendGroup { axes: axes.try_into().unwrap_or_else(|_| unreachable!()) })
+    }
+}
+
+/// The backends of all components of a robot, components without backend (`None`) are driven by `syact`
+pub struct BackendGroup<const C : usize> {
+    /// The axes of the components
+    pub axes : [Option<BackendAxis>; C]
+}
+
+impl<const C : usize> BackendGroup<C> {
+    /// Returns wheiter the component is driven by an external backend
+    pub fn is_external(&self, index : usize) -> bool {
+        self.axes.get(index).map_or(false, Option::is_some)
+    }
+
+    /// Overwrites the entries of all external components with the positions tracked by their backends
+    pub fn merge_gammas(&self, mut gammas : [Gamma; C]) -> [Gamma; C] {
+        for (i, axis) in self.axes.iter().enumerate() {
+            if let Some(axis) = axis {
+                gammas[i] = axis.gamma();
+            }
+        }
+
+        gammas
+    }
+
+    /// Drives all external components to the given `gammas`, synchronized to arrive at the same time. The movement
+    /// takes as long as the slowest component requires with its maximum velocity in `omega_max`, entries of GPIO
+    /// components are ignored, `speed_f` (`0.0` to `1.0`) scales all velocities. See `drive_abs_within()`
+    pub async fn drive_abs(&mut self, gammas : [Gamma; C], omega_max : [Velocity; C], speed_f : f32, estop : Option<&EStop>) 
+    -> Result<(), crate::Error> {
+        let mut time = 0.0f32;
+
+        for (i, axis) in self.axes.iter().enumerate() {
+            if let Some(axis) = axis {
+                if omega_max[i].0 > 0.0 {
+                    time = time.max((gammas[i].0 - axis.gamma().0).abs() / omega_max[i].0);
+                }
+            }
+        }
+
+        self.drive_abs_within(gammas, time / speed_f.clamp(1e-3, 1.0), estop).await
+    }
+
+    /// Drives all external components to the given `gammas` within the `time` given (in seconds), e.g. the duration of
+    /// the `syact` drives running alongside. Entries of GPIO components are ignored. Waits until the movement is done
+    /// and verifies the positions reported afterwards
+    /// 
+    /// The movement is aborted if the `estop` given is triggered. All external components are stopped and their 
+    /// positions are re-synced if the movement is aborted or the returned future is dropped before the movement is done
+    pub async fn drive_abs_within(&mut self, gammas : [Gamma; C], time : f32, estop : Option<&EStop>) 
+    -> Result<(), crate::Error> {
+        if let Some(estop) = estop {
+            estop.check()?;
+        }
+
+        let mut duration = Duration::ZERO;
+
+        // Stops all the axes unless the movement has been completed
+        let mut guard = StopGuard { group: self, done: false };
+
+        for (i, axis) in guard.group.axes.iter_mut().enumerate() {
+            if let Some(axis) = axis {
+                let dist = (gammas[i].0 - axis.gamma().0).abs();
+                let omega = if time > 0.0 { Velocity(dist / time) } else { Velocity(0.0) };
+
+                duration = duration.max(axis.send_abs(gammas[i], omega)?);
+            }
+        }
+
+        match estop {
+            Some(estop) => estop.guard(async { tokio::time::sleep(duration).await; Ok(()) }).await?,
+            None => tokio::time::sleep(duration).await
+        }
+
+        guard.done = true;
+
+        for axis in guard.group.axes.iter_mut().flatten() {
+            axis.finish();
+            axis.verify()?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the positions of all external components from their controllers, e.g. after the movement has been aborted
+    pub fn sync(&mut self) -> Result<(), crate::Error> {
+        for axis in self.axes.iter_mut().flatten() {
+            axis.sync()?;
+        }
+
+        Ok(())
+    }
+
+    /// Stops all external components immediately, all axes are stopped even if some fail
+    pub fn stop(&mut self) -> Result<(), crate::Error> {
+        let mut res = Ok(());
+
+        for axis in self.axes.iter_mut().flatten() {
+            if let Err(err) = axis.stop() {
+                res = Err(err);
+            }
+        }
+
+        res
+    }
+}
+
+/// Stops all the axes of the group and re-syncs their positions when dropped before the movement is marked as done
+struct StopGuard<'a, const C : usize> {
+    group : &'a mut BackendGroup<C>,
+    done : bool
+}
+
+impl<'a, const C : usize> Drop for StopGuard<'a, C> {
+    fn drop(&mut self) {
+        if !self.done {
+            // Errors cannot be reported while dropping, the axes are stopped and synced as far as possible. Axes 
+            // failing to sync keep the position of the last completed movement
+            let _ = self.group.stop();
+
+            for axis in self.group.axes.iter_mut().flatten() {
+                let _ = axis.sync();
+            }
+        }
+    }
+}
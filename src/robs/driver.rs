@@ -0,0 +1,75 @@
+use serde::{Serialize, Deserialize};
+
+use crate::SyError;
+
+/// Parameters of an external driver board, `None` values are not pushed or verified
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DriverParams {
+    /// Motor current in amperes
+    pub current : Option<f32>,
+    /// Microstepping resolution
+    pub microsteps : Option<u16>,
+    /// Threshold of the stall detection
+    pub stall_threshold : Option<i16>
+}
+
+impl DriverParams {
+    /// Maximum deviation allowed between the current pushed and read back (drivers often round the current)
+    pub const CURRENT_TOLERANCE : f32 = 0.05;
+
+    /// Returns the names of the parameters that do not match the `actual` values read from the driver
+    pub fn mismatches(&self, actual : &DriverParams) -> Vec<&'static str> {
+        let mut mismatches = Vec::new();
+
+        if let Some(current) = self.current {
+            if actual.current.map_or(true, |a| (a - current).abs() > Self::CURRENT_TOLERANCE) {
+                mismatches.push("current");
+            }
+        }
+
+        if self.microsteps.is_some() & (self.microsteps != actual.microsteps) {
+            mismatches.push("microsteps");
+        }
+
+        if self.stall_threshold.is_some() & (self.stall_threshold != actual.stall_threshold) {
+            mismatches.push("stall_threshold");
+        }
+
+        mismatches
+    }
+}
+
+/// A connection to an external driver board (e.g. over serial or CAN), that can be configured remotely
+pub trait DriverLink {
+    /// Writes the parameters to the driver
+    fn write_params(&mut self, params : &DriverParams) -> Result<(), crate::Error>;
+
+    /// Reads the parameters currently active on the driver
+    fn read_params(&mut self) -> Result<DriverParams, crate::Error>;
+
+    /// Writes the parameters to the driver and verifies them by reading them back
+    fn push_params(&mut self, params : &DriverParams) -> Result<(), crate::Error> {
+        self.write_params(params)?;
+
+        let mismatches = params.mismatches(&self.read_params()?);
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(SyError::component(format!("The driver did not accept the parameters: {:?}", mismatches)).into())
+        }
+    }
+}
+
+/// Pushes the parameters to all the driver links at startup, the error contains the index of the failing driver
+pub fn push_all(links : &mut [&mut dyn DriverLink], params : &[DriverParams]) -> Result<(), crate::Error> {
+    if links.len() != params.len() {
+        return Err(SyError::config(format!("The number of parameter sets must match the number of drivers! (Drivers: {}, Parameters: {})", 
+            links.len(), params.len())).into());
+    }
+
+    for (i, (link, params)) in links.iter_mut().zip(params).enumerate() {
+        link.push_params(params).map_err(|err| SyError::component(format!("Driver {}: {}", i, err)))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+use syunit::*;
+
+/// The type of filter applied to reported positions
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FilterKind {
+    /// No filtering, raw values are reported
+    #[default]
+    None,
+    /// Moving average over the given number of samples
+    MovingAverage(usize),
+    /// Exponential smoothing with the given factor (`0.0` to `1.0`, higher values follow the raw values faster)
+    Exponential(f32)
+}
+
+/// Filters reported `Phi` values before they are pushed to remotes and front ends, the raw values stay accessible
+#[derive(Clone, Debug, Default)]
+pub struct PhiFilter<const C : usize> {
+    kind : FilterKind,
+
+    window : VecDeque<[Phi; C]>,
+    state : Option<[Phi; C]>,
+    raw : Option<[Phi; C]>
+}
+
+impl<const C : usize> PhiFilter<C> {
+    /// Creates a new filter of the given kind
+    pub fn new(kind : FilterKind) -> Self {
+        Self {
+            kind,
+            window: VecDeque::new(),
+            state: None,
+            raw: None
+        }
+    }
+
+    /// Returns the kind of the filter
+    pub fn kind(&self) -> FilterKind {
+        self.kind
+    }
+
+    /// Changes the kind of the filter, resetting its state
+    pub fn set_kind(&mut self, kind : FilterKind) {
+        *self = Self::new(kind);
+    }
+
+    /// Returns the last raw values given to the filter
+    pub fn raw(&self) -> Option<[Phi; C]> {
+        self.raw
+    }
+
+    /// Returns the last filtered values
+    pub fn filtered(&self) -> Option<[Phi; C]> {
+        self.state
+    }
+
+    /// Adds a new set of raw values, returning the filtered values
+    pub fn apply(&mut self, phis : [Phi; C]) -> [Phi; C] {
+        self.raw = Some(phis);
+
+        let filtered = match self.kind {
+            FilterKind::None => phis,
+            FilterKind::MovingAverage(len) => {
+                self.window.push_back(phis);
+                while self.window.len() > len.max(1) {
+                    self.window.pop_front();
+                }
+
+                let mut avg = [Phi::ZERO; C];
+                for i in 0 .. C {
+                    avg[i] = Phi(self.window.iter().map(|p| p[i].0).sum::<f32>() / self.window.len() as f32);
+                }
+                avg
+            },
+            FilterKind::Exponential(alpha) => {
+                if let Some(state) = self.state {
+                    let mut smooth = [Phi::ZERO; C];
+                    for i in 0 .. C {
+                        smooth[i] = Phi(state[i].0 + alpha * (phis[i].0 - state[i].0));
+                    }
+                    smooth
+                } else {
+                    phis
+                }
+            }
+        };
+
+        self.state = Some(filtered);
+        filtered
+    }
+}
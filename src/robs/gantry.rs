@@ -0,0 +1,132 @@
+use syunit::*;
+
+use crate::SyError;
+
+/// A single motor of a linked gantry axis
+#[allow(async_fn_in_trait)]
+pub trait GantrySide {
+    /// Returns the current position of the side
+    fn gamma(&self) -> Gamma;
+
+    /// Drives the side by the given distance
+    async fn drive_rel(&mut self, delta : Delta, speed_f : Factor) -> Result<(), crate::Error>;
+
+    /// Drives the side against its own home switch and sets its position to the given home gamma
+    async fn home(&mut self, home : Gamma) -> Result<(), crate::Error>;
+
+    /// Stops the side immediately
+    fn halt(&mut self);
+}
+
+/// Error returned if the two sides of a gantry diverge further than allowed
+#[derive(Clone, Debug)]
+pub struct GantryFault {
+    /// Position of the primary side
+    pub primary : Gamma,
+    /// Position of the secondary side
+    pub secondary : Gamma,
+    /// The maximum divergence allowed
+    pub max_divergence : f32
+}
+
+impl core::fmt::Display for GantryFault {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The gantry sides diverged! (Primary: {}, Secondary: {}, Max divergence: {})", 
+            self.primary.0, self.secondary.0, self.max_divergence)
+    }
+}
+
+impl std::error::Error for GantryFault { }
+
+/// A logical axis driven by two motors (e.g. both sides of a large gantry), stepped in sync and homed independently 
+/// against separate switches to square the axis
+pub struct Gantry<A : GantrySide, B : GantrySide> {
+    /// The primary side, its position is the position of the axis
+    pub primary : A,
+    /// The secondary side
+    pub secondary : B,
+    /// The maximum divergence allowed between the two sides
+    pub max_divergence : f32,
+
+    faulted : bool
+}
+
+impl<A : GantrySide, B : GantrySide> Gantry<A, B> {
+    /// Creates a new gantry out of two sides
+    pub fn new(primary : A, secondary : B, max_divergence : f32) -> Self {
+        Self {
+            primary,
+            secondary,
+            max_divergence,
+
+            faulted: false
+        }
+    }
+
+    /// Returns the position of the axis
+    pub fn gamma(&self) -> Gamma {
+        self.primary.gamma()
+    }
+
+    /// Returns the current divergence between the two sides
+    pub fn divergence(&self) -> f32 {
+        (self.primary.gamma().0 - self.secondary.gamma().0).abs()
+    }
+
+    /// Returns wheiter the gantry has faulted, movements are rejected until it has been homed again
+    pub fn is_faulted(&self) -> bool {
+        self.faulted
+    }
+
+    /// Checks the divergence of the sides, halts both and marks the gantry as faulted if it is too large
+    pub fn check(&mut self) -> Result<(), GantryFault> {
+        if self.divergence() > self.max_divergence {
+            self.primary.halt();
+            self.secondary.halt();
+            self.faulted = true;
+
+            Err(GantryFault {
+                primary: self.primary.gamma(),
+                secondary: self.secondary.gamma(),
+                max_divergence: self.max_divergence
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Drives both sides by the given distance at the same time
+    pub async fn drive_rel(&mut self, delta : Delta, speed_f : Factor) -> Result<(), crate::Error> {
+        if self.faulted {
+            return Err(SyError::safety("The gantry has faulted and has to be homed again!").into());
+        }
+
+        let (res_a, res_b) = tokio::join!(
+            self.primary.drive_rel(delta, speed_f), 
+            self.secondary.drive_rel(delta, speed_f)
+        );
+
+        self.check()?;
+        res_a?;
+        res_b
+    }
+
+    /// Drives both sides to the given absolute position
+    pub async fn drive_abs(&mut self, gamma : Gamma, speed_f : Factor) -> Result<(), crate::Error> {
+        let delta = Delta(gamma.0 - self.gamma().0);
+        self.drive_rel(delta, speed_f).await
+    }
+
+    /// Homes both sides independently against their own switches, which squares the axis and clears any fault
+    pub async fn home(&mut self, home : Gamma) -> Result<(), crate::Error> {
+        let (res_a, res_b) = tokio::join!(
+            self.primary.home(home), 
+            self.secondary.home(home)
+        );
+        res_a?;
+        res_b?;
+
+        self.faulted = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use core::time::Duration;
+
+use glam::Vec3;
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, Robot, SyError};
+use crate::cancel::CancelToken;
+use crate::events::StateEvent;
+use crate::rcs::Position;
+use crate::rcs::math::sub_phis;
+
+/// Cartesian jogging of the TCP, the commanded TCP velocity is mapped into joint velocities every control period and 
+/// scaled down so no joint exceeds the maximum velocity of the robot (see `Robot::omega_max()`)
+#[derive(Clone, Debug)]
+pub struct CartesianJog<const C : usize> {
+    /// The control period in which the commanded velocity is remapped
+    pub period : Duration
+}
+
+impl<const C : usize> CartesianJog<C> {
+    /// Creates a new cartesian jog with the given control period
+    pub fn new(period : Duration) -> Self {
+        Self { period }
+    }
+
+    /// Returns the joint deltas required to move the TCP by `speed` into the direction `dir` for one control period, 
+    /// together with the scale (`0.0` to `1.0`) that has been applied to the TCP velocity to stay within the joint 
+    /// limits `omega_max`
+    pub fn step_deltas<D : Descriptor<C>>(&self, desc : &D, phis : [Phi; C], omega_max : &[Velocity; C], dir : Vec3, 
+        speed : Velocity) -> Result<([Delta; C], f32), crate::Error> {
+        let dt = self.period.as_secs_f32();
+        let dir = dir.try_normalize().ok_or_else(|| SyError::kinematics("The jog direction must not be zero!"))?;
+
+        let pos_0 = desc.tcp().pos();
+        let deltas = sub_phis(desc.phis_for_pos(Position::from_vec3(pos_0 + dir * speed.0 * dt))?, phis);
+
+        // The joint deltas are linear to the TCP velocity for small steps (jacobian), so the limiting axis defines the scale
+        let mut scale : f32 = 1.0;
+        for i in 0 .. C {
+            let omega = deltas[i].0.abs() / dt;
+            if omega > omega_max[i].0 {
+                scale = scale.min(omega_max[i].0 / omega);
+            }
+        }
+
+        if scale < 1.0 {
+            let deltas = sub_phis(desc.phis_for_pos(Position::from_vec3(pos_0 + dir * speed.0 * scale * dt))?, phis);
+            return Ok((deltas, scale));
+        }
+
+        Ok((deltas, scale))
+    }
+
+    /// Jogs the TCP into the direction `dir` with the given `speed` until the `cancel` token is triggered, e.g. when the 
+    /// operator releases the jog button. The jog decelerates in front of the soft limits of the robot and stops exactly 
+    /// at them, publishing a `StateEvent::JogLimit`. Requires the maximum velocities of the robot to be set (see 
+    /// `Robot::set_omega_max()`)
+    pub async fn run<R, G, T, D>(&self, rob : &mut R, desc : &mut D, dir : Vec3, speed : Velocity, cancel : &CancelToken) 
+    -> Result<(), crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        let omega_max = rob.omega_max().ok_or_else(|| SyError::config(
+            "Cartesian jogging requires the maximum velocities of the robot, see `Robot::set_omega_max()`!"))?;
+
+        while !cancel.is_cancelled() {
+            let phis = rob.phis();
+            let (deltas, _) = self.step_deltas(desc, phis, &omega_max, dir, speed)?;
+
+            let mut target = phis;
+            for i in 0 .. C {
+                target[i] = Phi(phis[i].0 + deltas[i].0);
+            }
+
+            let (deltas, stop) = rob.limit_jog(sub_unit_arrays(rob.gammas_from_phis(target), rob.gammas()));
+            let speed_f = rob.speed_f_for_time(&deltas, self.period.as_secs_f32());
+            rob.drive_j(deltas, speed_f).await?;
+
+            let phis = rob.phis();
+            desc.update(rob, &phis)?;
+
+            if let Some(axis) = stop {
+                rob.events().publish(StateEvent::JogLimit { axis });
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use glam::Vec3;
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, Robot, SyError};
+use crate::desc::Kinematic;
+use crate::rcs::{Point, Position};
+
+/// Collision check of a pose, returns a description of the collision if there is one (see `SimChecks::collision`)
+pub type CollisionCheck<'a, const C : usize> = &'a dyn Fn(&[Phi; C]) -> Option<String>;
+
+/// Strategies to reach the park pose, tried in the order listed until a collision-free one is found
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParkStrategy {
+    /// Moves all joints at once
+    Direct,
+    /// Raises the TCP by the clearance first, then moves all joints at once
+    Retract,
+    /// Moves the joints one after another in the order configured
+    Sequential
+}
+
+/// A safe park pose and how to get there from wherever the robot currently is
+#[derive(Clone, Debug)]
+pub struct Park<const C : usize> {
+    /// The `Phi` values of the park pose
+    pub pose : [Phi; C],
+    /// Height the TCP is raised by for `ParkStrategy::Retract`
+    pub clearance : f32,
+    /// The order of the joints for `ParkStrategy::Sequential`, e.g. lifting axes first
+    pub order : [usize; C],
+    /// The strategies tried
+    pub strategies : Vec<ParkStrategy>,
+    /// Maximum joint distance between two poses checked for collisions along a path
+    pub resolution : f32
+}
+
+impl<const C : usize> Park<C> {
+    /// Creates a new park configuration with all strategies, a clearance of `50.0`, the joints in their natural order
+    /// and a resolution of `0.05`
+    pub fn new(pose : [Phi; C]) -> Self {
+        let mut order = [0; C];
+        for i in 0 .. C {
+            order[i] = i;
+        }
+
+        Self {
+            pose,
+            clearance: 50.0,
+            order,
+            strategies: vec![ ParkStrategy::Direct, ParkStrategy::Retract, ParkStrategy::Sequential ],
+            resolution: 0.05
+        }
+    }
+
+    /// Returns the waypoints of the given strategy, starting at `start` (excluded)
+    fn waypoints<D : Descriptor<C>>(&self, strategy : ParkStrategy, start : [Phi; C], desc : &mut D)
+        -> Result<Vec<[Phi; C]>, crate::Error>
+    {
+        match strategy {
+            ParkStrategy::Direct => Ok(vec![ self.pose ]),
+            ParkStrategy::Retract => {
+                let kin = desc.kinematic_mut();
+                let phis_0 = kin.phis();
+
+                kin.update(&start)?;
+                let pos = kin.calculate_end();
+                kin.update(&phis_0)?;
+
+                let retract = desc.phis_for_pos(Position::new_ori(*pos.pos() + Vec3::Z * self.clearance, *pos.ori()))?;
+                Ok(vec![ retract, self.pose ])
+            },
+            ParkStrategy::Sequential => {
+                let mut phis = start;
+
+                Ok(self.order.iter().filter_map(|&i| {
+                    if phis[i].0 == self.pose[i].0 {
+                        return None;
+                    }
+
+                    phis[i] = self.pose[i];
+                    Some(phis)
+                }).collect())
+            }
+        }
+    }
+
+    /// Checks the path along the waypoints, returning the first collision found
+    fn check_path(&self, start : [Phi; C], waypoints : &[[Phi; C]], check : CollisionCheck<C>) -> Option<String> {
+        let mut last = start;
+
+        for target in waypoints {
+            let dist = (0 .. C).map(|i| (target[i].0 - last[i].0).abs()).fold(0.0, f32::max);
+            let n = ((dist / self.resolution).ceil() as usize).max(1);
+
+            for s in 1 ..= n {
+                let t = s as f32 / n as f32;
+                let mut phis = last;
+
+                for i in 0 .. C {
+                    phis[i] = Phi(last[i].0 + (target[i].0 - last[i].0) * t);
+                }
+
+                if let Some(msg) = check(&phis) {
+                    return Some(msg);
+                }
+            }
+
+            last = *target;
+        }
+
+        None
+    }
+
+    /// Plans a collision-free path from `start` to the park pose, returning the strategy chosen and its waypoints. The
+    /// kinematic of the descriptor is restored afterwards
+    pub fn plan<D : Descriptor<C>>(&self, start : [Phi; C], desc : &mut D, check : CollisionCheck<C>)
+        -> Result<(ParkStrategy, Vec<[Phi; C]>), crate::Error>
+    {
+        let mut reasons = Vec::new();
+
+        for &strategy in &self.strategies {
+            let waypoints = match self.waypoints(strategy, start, desc) {
+                Ok(waypoints) => waypoints,
+                Err(err) => {
+                    reasons.push(format!("{:?}: {}", strategy, err));
+                    continue;
+                }
+            };
+
+            match self.check_path(start, &waypoints, check) {
+                None => return Ok((strategy, waypoints)),
+                Some(msg) => reasons.push(format!("{:?}: {}", strategy, msg))
+            }
+        }
+
+        Err(SyError::safety(format!("No collision-free path to the park pose found! ({})", reasons.join(", "))).into())
+    }
+
+    /// Drives the robot to the park pose on the first collision-free path found, the joint limits and the masked 
+    /// axes of the robot are checked along with the collision check given
+    pub async fn execute<R, G, T, D>(&self, rob : &mut R, desc : &mut D, check : CollisionCheck<'_, C>, speed_f : Factor)
+        -> Result<ParkStrategy, crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        let (strategy, waypoints) = {
+            let rob_ref = &*rob;
+            let gammas = rob.gammas();
+
+            // Paths moving masked axes are rejected like colliding ones
+            let checked = |phis : &[Phi; C]| {
+                rob_ref.valid_phis(phis).err()
+                    .or_else(|| rob_ref.check_masked(&sub_unit_arrays(rob_ref.gammas_from_phis(*phis), gammas)).err())
+                    .map(|err| err.to_string()).or_else(|| check(phis))
+            };
+
+            self.plan(rob.phis(), desc, &checked)?
+        };
+
+        for phis in waypoints {
+            rob.move_abs_j(phis, speed_f).await?;
+        }
+
+        Ok(strategy)
+    }
+}
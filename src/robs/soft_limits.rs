@@ -0,0 +1,156 @@
+use syunit::*;
+
+/// The minimum speed factor applied in a margin zone, so movements never stall completely
+const MIN_FACTOR : f32 = 0.05;
+
+/// Remaining distances below this value count as the limit being reached
+const LIMIT_EPSILON : f32 = 1e-6;
+
+/// Soft limits of a single axis with a margin zone in front of each limit
+#[derive(Clone, Copy, Debug)]
+pub struct SoftLimit {
+    /// The minimum `Phi` value of the axis
+    pub min : Option<Phi>,
+    /// The maximum `Phi` value of the axis
+    pub max : Option<Phi>,
+    /// The width of the margin zones in front of the limits
+    pub margin : f32,
+    /// The speed factor right at the limit, the speed is reduced linearly from `1.0` at the start of the margin zone
+    pub slowdown : f32
+}
+
+impl SoftLimit {
+    /// A soft limit without any limits
+    pub const NONE : Self = Self { min: None, max: None, margin: 0.0, slowdown: 1.0 };
+
+    /// Creates a new soft limit
+    pub fn new(min : Option<Phi>, max : Option<Phi>, margin : f32, slowdown : f32) -> Self {
+        Self { min, max, margin, slowdown }
+    }
+
+    /// Returns the distance of the given value to the closest limit, negative if the limit is exceeded
+    pub fn distance(&self, phi : Phi) -> Option<f32> {
+        let d_min = self.min.map(|min| phi.0 - min.0);
+        let d_max = self.max.map(|max| max.0 - phi.0);
+
+        match (d_min, d_max) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b)
+        }
+    }
+
+    /// Returns the distance to the limit in the direction of the movement, `None` if there is no limit in that direction
+    pub fn remaining(&self, phi : Phi, delta : Delta) -> Option<f32> {
+        if delta.0 > 0.0 {
+            self.max.map(|max| (max.0 - phi.0).max(0.0))
+        } else if delta.0 < 0.0 {
+            self.min.map(|min| (phi.0 - min.0).max(0.0))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the speed factor for the given value (`1.0` outside of the margin zones)
+    pub fn factor(&self, phi : Phi) -> f32 {
+        match self.distance(phi) {
+            Some(dist) if dist < self.margin => {
+                let t = (dist / self.margin).clamp(0.0, 1.0);
+                (self.slowdown + (1.0 - self.slowdown) * t).max(MIN_FACTOR)
+            },
+            _ => 1.0
+        }
+    }
+}
+
+impl Default for SoftLimit {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// The zone a set of `Phi` values is in, see `SoftLimits::zone`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LimitZone {
+    /// All axes are outside of the margin zones
+    Free,
+    /// The given axis is inside of its margin zone, `distance` to the limit
+    Margin { axis : usize, distance : f32 },
+    /// The given axis exceeds its limits
+    Exceeded { axis : usize }
+}
+
+/// Per-axis soft limits with margin zones, where movements are slowed down and warnings are emitted
+#[derive(Clone, Copy, Debug)]
+pub struct SoftLimits<const C : usize> {
+    /// The limits of each axis
+    pub axes : [SoftLimit; C]
+}
+
+impl<const C : usize> SoftLimits<C> {
+    /// Creates new soft limits
+    pub fn new(axes : [SoftLimit; C]) -> Self {
+        Self { axes }
+    }
+
+    /// Returns the zone of the given values, an exceeded limit takes precedence over the closest margin zone
+    pub fn zone(&self, phis : &[Phi; C]) -> LimitZone {
+        let mut zone = LimitZone::Free;
+
+        for (axis, (limit, phi)) in self.axes.iter().zip(phis).enumerate() {
+            let Some(distance) = limit.distance(*phi) else {
+                continue;
+            };
+
+            if distance < 0.0 {
+                return LimitZone::Exceeded { axis };
+            }
+
+            if distance < limit.margin {
+                if let LimitZone::Margin { distance: closest, .. } = zone {
+                    if closest <= distance {
+                        continue;
+                    }
+                }
+
+                zone = LimitZone::Margin { axis, distance };
+            }
+        }
+
+        zone
+    }
+
+    /// Returns the speed factor for the given values, the minimum of all the axes
+    pub fn factor(&self, phis : &[Phi; C]) -> f32 {
+        self.axes.iter().zip(phis).map(|(limit, phi)| limit.factor(*phi)).fold(1.0, f32::min)
+    }
+
+    /// Limits a jog step, so the jog stops exactly at the limit. All deltas are scaled by the same factor to keep the 
+    /// direction of the jog. The distance is only limited at the limit itself, the deceleration inside the margin zones 
+    /// is done by the speed (see `factor()`). Returns the scaled deltas and the axis whose limit is reached with them, 
+    /// if any
+    pub fn limit_jog(&self, phis : &[Phi; C], deltas : &[Delta; C]) -> ([Delta; C], Option<usize>) {
+        let mut scale : f32 = 1.0;
+        let mut stop = None;
+
+        for (axis, limit) in self.axes.iter().enumerate() {
+            let Some(remaining) = limit.remaining(phis[axis], deltas[axis]) else {
+                continue;
+            };
+
+            let step = deltas[axis].0.abs();
+
+            // Only the axis limiting the step is reported
+            if (step >= remaining - LIMIT_EPSILON) & (remaining / step <= scale) {
+                scale = remaining / step;
+                stop = Some(axis);
+            }
+        }
+
+        let mut limited = *deltas;
+        for delta in limited.iter_mut() {
+            delta.0 *= scale;
+        }
+
+        (limited, stop)
+    }
+}
@@ -0,0 +1,56 @@
+use core::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::SyError;
+
+/// A cloneable, thread-safe speed override factor (`0.0` to `2.0`, i.e. 0% to 200%) scaling the velocity of movements, 
+/// changes apply to running movements as well
+#[derive(Clone, Debug)]
+pub struct SpeedOverride {
+    bits : Arc<AtomicU32>
+}
+
+impl SpeedOverride {
+    /// The maximum factor allowed (200%)
+    pub const MAX : f32 = 2.0;
+
+    /// Interval the factor is polled in while movements are held, see `resumed()`
+    pub const HOLD_POLL : Duration = Duration::from_millis(10);
+
+    /// Creates a new override with the factor `1.0` (100%)
+    pub fn new() -> Self {
+        Self {
+            bits: Arc::new(AtomicU32::new(1.0f32.to_bits()))
+        }
+    }
+
+    /// Returns the current factor
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+
+    /// Sets the factor, fails if it is outside of the range `0.0` to `2.0`
+    pub fn set(&self, factor : f32) -> Result<(), crate::Error> {
+        if !(0.0 ..= Self::MAX).contains(&factor) {
+            return Err(SyError::config(format!("Invalid speed override! Must be between 0.0 and {} (Factor: {})", 
+                Self::MAX, factor)).into());
+        }
+
+        self.bits.store(factor.to_bits(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Waits until the factor is above zero, used to hold movements while the override is at zero
+    pub async fn resumed(&self) {
+        while self.get() <= 0.0 {
+            tokio::time::sleep(Self::HOLD_POLL).await;
+        }
+    }
+}
+
+impl Default for SpeedOverride {
+    fn default() -> Self {
+        Self::new()
+    }
+}
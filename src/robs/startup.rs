@@ -0,0 +1,77 @@
+use core::time::Duration;
+
+use crate::SyError;
+
+/// The power stage of a robot, giving access to the drive electronics during the startup sequence
+pub trait PowerStage {
+    /// Enables the drive of the given axis
+    fn enable(&mut self, axis : usize) -> Result<(), crate::Error>;
+
+    /// Sets the holding current of the given axis (`0.0` to `1.0` of the nominal current)
+    fn set_holding_current(&mut self, axis : usize, factor : f32) -> Result<(), crate::Error>;
+
+    /// Reads the states of all the limit switches of the robot
+    fn limit_states(&mut self) -> Result<Vec<bool>, crate::Error>;
+}
+
+/// A configurable soft-start sequence, executed before the robot goes "live"
+/// 
+/// 1. The drives are enabled one by one with a delay in between
+/// 2. The holding current of each drive is ramped up in steps
+/// 3. The limit switch states are verified
+/// 
+/// Afterwards the robot requires homing before any absolute movement, if `require_homing` is set
+#[derive(Clone, Debug)]
+pub struct StartupSequence {
+    /// Delay between enabling the drives
+    pub enable_delay : Duration,
+    /// Number of steps of the holding current ramp
+    pub ramp_steps : usize,
+    /// Total duration of the holding current ramp
+    pub ramp_time : Duration,
+    /// The limit switch states expected after power-up, `None` to skip the check
+    pub expected_limits : Option<Vec<bool>>,
+    /// Wheiter or not the robot requires homing before absolute movements
+    pub require_homing : bool
+}
+
+impl Default for StartupSequence {
+    fn default() -> Self {
+        Self {
+            enable_delay: Duration::from_millis(200),
+            ramp_steps: 10,
+            ramp_time: Duration::from_millis(500),
+            expected_limits: None,
+            require_homing: true
+        }
+    }
+}
+
+impl StartupSequence {
+    /// Runs the sequence for the given number of `axes`
+    pub async fn run(&self, stage : &mut dyn PowerStage, axes : usize) -> Result<(), crate::Error> {
+        let steps = self.ramp_steps.max(1);
+
+        for axis in 0 .. axes {
+            stage.enable(axis)?;
+
+            for step in 1 ..= steps {
+                stage.set_holding_current(axis, step as f32 / steps as f32)?;
+                tokio::time::sleep(self.ramp_time / steps as u32).await;
+            }
+
+            tokio::time::sleep(self.enable_delay).await;
+        }
+
+        if let Some(expected) = &self.expected_limits {
+            let states = stage.limit_states()?;
+
+            if states != *expected {
+                return Err(SyError::safety(format!("Unexpected limit switch states after power-up! (Expected: {:?}, Actual: {:?})", 
+                    expected, states)).into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,14 +1,19 @@
 use core::marker::PhantomData;
 
 use glam::Vec3;
-use syact::Setup;
+use syact::{Setup, SyncActuatorGroup};
 use syact::act::stepper::{StepperActuator, StepperActuatorGroup};
 use syact::math::movements::DefinedActuator;
 use syunit::*;
 
 use crate::{Robot, PushRemote, Descriptor};
-use crate::config::AngleConfig;
-use crate::robs::{Vars, Tool};
+use crate::config::{AngleConfig, AxisCoupling};
+use crate::rcs::{Linear, Point};
+use crate::cancel::CancelToken;
+use crate::estop::EStop;
+use crate::events::{EventBus, StateEvent};
+use crate::robs::{BackendGroup, PhiFilter, FilterKind, PowerStage, SpeedOverride, StartupSequence, Vars, Tool};
+use crate::stat::ToolUsageTracker;
 
 /// A robot that uses stepper motors as actuators
 pub struct StepperRobot<G, T, const C : usize> 
@@ -19,12 +24,23 @@ where
     _vars : Vars<C>,
 
     _ang_confs : [AngleConfig; C],
+    _couplings : Vec<AxisCoupling>,
     _comps : G,
 
     tools : Vec<Box<dyn Tool>>,
     tool_id : Option<usize>,
+    usage : ToolUsageTracker,
 
     remotes : Vec<Box<dyn PushRemote>>,
+    events : EventBus<C>,
+    filter : PhiFilter<C>,
+    estop : EStop,
+    cancel : CancelToken,
+
+    speed : SpeedOverride,
+    limits : Option<([Option<Gamma>; C], [Option<Gamma>; C])>,
+    omega_max : Option<[Velocity; C]>,
+    backends : Option<BackendGroup<C>>,
 
     __pd : PhantomData<T>
 }
@@ -43,16 +59,153 @@ where
             _vars: Vars::default(),
 
             _ang_confs: ang_confs,
+            _couplings: Vec::new(),
             _comps: comps,
             
             tools,
             tool_id: None,
+            usage: ToolUsageTracker::new(),
 
             remotes: Vec::new(),
+            events: EventBus::default(),
+            filter: PhiFilter::default(),
+            estop: EStop::new(),
+            cancel: CancelToken::new(),
+
+            speed: SpeedOverride::new(),
+            limits: None,
+            omega_max: None,
+            backends: None,
 
             __pd : PhantomData::default()
         }
     }
+
+    /// Runs the soft-start `sequence` on the power stage of the robot, afterwards the robot requires homing before 
+    /// absolute movements if configured by the sequence
+    pub async fn power_up(&mut self, sequence : &StartupSequence, stage : &mut dyn PowerStage) -> Result<(), crate::Error> {
+        self._vars.homed = false;
+        self._vars.require_homing = sequence.require_homing;
+        sequence.run(stage, C).await
+    }
+
+    /// Sets the mechanical couplings between the axes, see [AxisCoupling]
+    pub fn set_couplings(&mut self, couplings : Vec<AxisCoupling>) -> Result<(), crate::Error> {
+        AxisCoupling::validate(&couplings, C)?;
+        self._couplings = couplings;
+        Ok(())
+    }
+
+    /// Sets the filter applied to the positions pushed to remotes and subscribers, `Robot::phis()` stays unfiltered
+    pub fn set_filter(&mut self, kind : FilterKind) {
+        self.filter.set_kind(kind)
+    }
+
+    /// Returns the filter applied to the positions pushed to remotes and subscribers
+    pub fn filter(&self) -> &PhiFilter<C> {
+        &self.filter
+    }
+
+    /// Sets the emergency stop of the robot, e.g. to share one stop between all the robots of a cell
+    pub fn set_estop(&mut self, estop : EStop) {
+        self.estop = estop;
+    }
+
+    /// Sets the token cancelling the operations of the robot, e.g. to share one token with a front end
+    pub fn set_cancel_token(&mut self, cancel : CancelToken) {
+        self.cancel = cancel;
+    }
+
+    /// Sets the external backends driving some of the components (see `BackendRegistry::create_group()`), the 
+    /// positions of the external components are read from their controllers
+    pub fn set_backends(&mut self, mut backends : BackendGroup<C>) -> Result<(), crate::Error> {
+        backends.sync()?;
+        self.backends = Some(backends);
+        Ok(())
+    }
+}
+
+/// Builder for a `StepperRobot`, allowing programmatic assembly without configuration files
+/// 
+/// The number of axes is checked at compile time by the const generic `C`
+pub struct StepperRobotBuilder<G, T, const C : usize> 
+where 
+    G : StepperActuatorGroup<T, C>,
+    T : StepperActuator + ?Sized + 'static
+{
+    comps : G,
+    ang_confs : [AngleConfig; C],
+    tools : Vec<Box<dyn Tool>>,
+
+    limits_min : [Option<Gamma>; C],
+    limits_max : [Option<Gamma>; C],
+    omega_max : Option<[Velocity; C]>,
+
+    __pd : PhantomData<T>
+}
+
+impl<G, T, const C : usize> StepperRobotBuilder<G, T, C> 
+where 
+    G : StepperActuatorGroup<T, C>,
+    T : StepperActuator + DefinedActuator + ?Sized + 'static
+{
+    /// Sets the angle configurations of the robot (default: `AngleConfig::EMPTY`)
+    pub fn ang_confs(mut self, ang_confs : [AngleConfig; C]) -> Self {
+        self.ang_confs = ang_confs;
+        self
+    }
+
+    /// Adds a tool to the robot
+    pub fn tool(mut self, tool : Box<dyn Tool>) -> Self {
+        self.tools.push(tool);
+        self
+    }
+
+    /// Sets the limits of the components
+    pub fn limits(mut self, min : [Option<Gamma>; C], max : [Option<Gamma>; C]) -> Self {
+        self.limits_min = min;
+        self.limits_max = max;
+        self
+    }
+
+    /// Sets the maximum velocities of the components
+    pub fn omega_max(mut self, omega_max : [Velocity; C]) -> Self {
+        self.omega_max = Some(omega_max);
+        self
+    }
+
+    /// Builds the robot, applying the limits to the components
+    pub fn build(self) -> StepperRobot<G, T, C> {
+        let mut rob = StepperRobot::new(self.ang_confs, self.comps, self.tools);
+        rob.set_limits(&self.limits_min, &self.limits_max);
+
+        if let Some(omega_max) = self.omega_max {
+            rob.set_omega_max(omega_max);
+        }
+
+        rob
+    }
+}
+
+impl<G, T, const C : usize> StepperRobot<G, T, C>
+where 
+    G : StepperActuatorGroup<T, C>,
+    T : StepperActuator + DefinedActuator + ?Sized + 'static
+{
+    /// Starts building a new robot out of the given components
+    pub fn builder(comps : G) -> StepperRobotBuilder<G, T, C> {
+        StepperRobotBuilder {
+            comps,
+            ang_confs: [AngleConfig::EMPTY; C],
+            tools: Vec::new(),
+
+            limits_min: [None; C],
+            limits_max: [None; C],
+            omega_max: None,
+
+            __pd: PhantomData::default()
+        }
+    }
 }
 
 impl<G, T, const C : usize> Setup for StepperRobot<G, T, C> 
@@ -76,6 +229,16 @@ where
             &self._ang_confs
         }
 
+        #[inline]
+        fn ang_confs_mut<'a>(&'a mut self) -> &mut [AngleConfig; C] {
+            &mut self._ang_confs
+        }
+
+        #[inline]
+        fn couplings(&self) -> &[AxisCoupling] {
+            &self._couplings
+        }
+
         #[inline]
         fn comps<'a>(&'a self) -> &'a G {
             &self._comps
@@ -85,6 +248,16 @@ where
         fn comps_mut<'a>(&'a mut self) -> &'a mut G {
             &mut self._comps
         }
+
+        #[inline]
+        fn backends(&self) -> Option<&BackendGroup<C>> {
+            self.backends.as_ref()
+        }
+
+        #[inline]
+        fn backends_mut(&mut self) -> Option<&mut BackendGroup<C>> {
+            self.backends.as_mut()
+        }
         
         #[inline]
         fn vars<'a>(&'a self) -> &'a Vars<C> {
@@ -97,21 +270,50 @@ where
         }
     //
 
+    // Loads & Limits
+        fn set_limits(&mut self, min : &[Option<Gamma>; C], max : &[Option<Gamma>; C]) {
+            self.limits = Some((*min, *max));
+            <G as SyncActuatorGroup<T, C>>::set_limits(&mut self._comps, min, max);
+        }
+
+        #[inline]
+        fn limits(&self) -> Option<([Option<Gamma>; C], [Option<Gamma>; C])> {
+            self.limits
+        }
+
+        fn set_omega_max(&mut self, omega_max : [Velocity; C]) {
+            self.omega_max = Some(omega_max);
+            <G as SyncActuatorGroup<T, C>>::set_velocity_max(&mut self._comps, omega_max);
+        }
+
+        #[inline]
+        fn omega_max(&self) -> Option<[Velocity; C]> {
+            self.omega_max
+        }
+
+        fn speed_override(&self) -> Option<&SpeedOverride> {
+            Some(&self.speed)
+        }
+    // 
+
     // Movement
-        #[allow(unused)]
         async fn move_l<D : Descriptor<C>>(&mut self, desc : &mut D, distance : Vec3, accuracy : f32, speed : Velocity) -> Result<(), crate::Error> {
-            todo!();
-            Ok(())
+            // The orientation of the TCP is kept, the target is given in program coordinates (see `move_path()`)
+            let mut target = self.tcp_pose(desc);
+            target.shift(distance - self._vars.scope.offset);
+            self.move_path(desc, &Linear, target, accuracy, speed).await
         }
     // 
 
     // Events
         fn update(&mut self) -> Result<(), crate::Error> {
-            let phis = self.phis();
+            let phis = self.filter.apply(self.phis());
             for rem in &mut self.remotes {
-                rem.push_phis(&phis)?;
+                rem.push_phis_from(self._vars.identity.as_ref(), &phis)?;
             }
 
+            self.events.publish(StateEvent::Phis(phis));
+
             Ok(())
         }
     // 
@@ -141,9 +343,24 @@ where
             &self.tools 
         }
 
+        fn get_tool_id(&self) -> Option<usize> {
+            self.tool_id
+        }
+
+        #[inline]
+        fn tool_usage(&self) -> Option<&ToolUsageTracker> {
+            Some(&self.usage)
+        }
+
+        #[inline]
+        fn tool_usage_mut(&mut self) -> Option<&mut ToolUsageTracker> {
+            Some(&mut self.usage)
+        }
+
         fn set_tool_id(&mut self, tool_id : Option<usize>) -> Option<&mut dyn Tool> {
             if let Some(id) = tool_id {   
                 if id < self.tools.len() {
+                    self.events.publish(StateEvent::ToolChange { old: self.tool_id, new: tool_id });
                     self.tool_id = tool_id;
                     Some(self.tools[id].as_mut())
                 } else {
@@ -155,6 +372,22 @@ where
         }
     // 
 
+    // Safety
+        fn estop(&self) -> Option<&EStop> {
+            Some(&self.estop)
+        }
+
+        fn cancel_token(&self) -> Option<&CancelToken> {
+            Some(&self.cancel)
+        }
+    // 
+
+    // Events
+        fn events(&self) -> &EventBus<C> {
+            &self.events
+        }
+    // 
+
     // Remote
         fn add_remote(&mut self, remote : Box<dyn PushRemote>) {
             self.remotes.push(remote)
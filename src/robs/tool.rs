@@ -5,6 +5,21 @@ use glam::Vec3;
 use syact::{Dismantle, Setup};
 use syunit::*;
 
+use crate::SyError;
+
+// Submodules
+    mod ident;
+    pub use ident::{IdPins, ResistorLadder, ToolIdent};
+// 
+
+// Tools
+    mod laser;
+    pub use laser::Laser;
+
+    mod relay;
+    pub use relay::{RelayBoard, RelayConfig};
+//
+
 // // Tools
 //     mod axial_joint;
 //     pub use axial_joint::AxialJoint;
@@ -52,6 +67,26 @@ pub trait Tool : Setup + Dismantle {
         fn spindle_tool_mut(&mut self) -> Option<&mut dyn SpindleTool> {
             None
         }
+
+        /// Upgrade the tool to a [LaserTool] if possible, returns `None` otherwise
+        fn laser_tool(&self) -> Option<&dyn LaserTool> {
+            None
+        }
+
+        /// Upgrade the tool to a [LaserTool] if possible, returns `None` otherwise
+        fn laser_tool_mut(&mut self) -> Option<&mut dyn LaserTool> {
+            None
+        }
+
+        /// Upgrade the tool to an [OutputTool] if possible, returns `None` otherwise
+        fn output_tool(&self) -> Option<&dyn OutputTool> {
+            None
+        }
+
+        /// Upgrade the tool to an [OutputTool] if possible, returns `None` otherwise
+        fn output_tool_mut(&mut self) -> Option<&mut dyn OutputTool> {
+            None
+        }
     //
 
     // Stats
@@ -128,4 +163,57 @@ pub trait Tool : Setup + Dismantle {
             fn is_active(&self) -> Option<bool>;
         //
     }
-//
\ No newline at end of file
+
+    /// A trait for tools that emit a laser beam with a scalable power
+    /// 
+    /// The output of the laser is gated by motion: During rapid movements the beam is suspended and restored afterwards
+    pub trait LaserTool {
+        // Actions
+            /// Sets the power of the laser (`0.0` to `1.0`), scaled to the PWM duty cycle (like `M3 S..`)
+            fn set_power(&mut self, power : f32) -> Result<(), crate::Error>;
+
+            /// Starts firing the laser, fails if the given `interlock` of the station is not satisfied
+            fn fire(&mut self, interlock : bool) -> Result<(), crate::Error>;
+
+            /// Stops firing the laser
+            fn cease(&mut self) -> Result<(), crate::Error>;
+
+            /// Gates the laser output, `rapid` should be `true` for rapid movements, which turns off the beam
+            fn gate(&mut self, rapid : bool) -> Result<(), crate::Error>;
+        // 
+
+        // State
+            /// Returns the power of the laser set (`0.0` to `1.0`)
+            fn power(&self) -> f32;
+
+            /// Returns wheiter the laser is firing or not (the beam can still be suspended because of a rapid movement)
+            fn is_firing(&self) -> bool;
+        //
+    }
+
+    /// A trait for tools with a number of named on/off output channels (e.g. relay boards)
+    pub trait OutputTool {
+        // Actions
+            /// Sets the state of the channel with the given index
+            fn set_channel(&mut self, channel : usize, state : bool) -> Result<(), crate::Error>;
+
+            /// Sets the state of the channel with the given name
+            fn set_named(&mut self, name : &str, state : bool) -> Result<(), crate::Error> {
+                let channel = (0 .. self.channels()).find(|c| self.channel_name(*c) == Some(name))
+                    .ok_or_else(|| SyError::config(format!("The tool has no output channel named '{}'!", name)))?;
+                self.set_channel(channel, state)
+            }
+        // 
+
+        // State
+            /// Returns the number of channels
+            fn channels(&self) -> usize;
+
+            /// Returns the name of the channel with the given index
+            fn channel_name(&self, channel : usize) -> Option<&str>;
+
+            /// Returns the state of the channel with the given index
+            fn channel(&self, channel : usize) -> Option<bool>;
+        //
+    }
+//
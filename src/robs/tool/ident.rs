@@ -0,0 +1,91 @@
+use embedded_hal::digital::InputPin;
+
+use crate::SyError;
+
+/// A device that can identify the tool that is currently mounted to the robot
+pub trait ToolIdent {
+    /// Reads the id of the tool mounted, `None` if no tool is mounted
+    fn read_tool_id(&mut self) -> Result<Option<usize>, crate::Error>;
+
+    /// Verifies that the tool mounted matches the `selected` tool id
+    fn verify(&mut self, selected : Option<usize>) -> Result<(), crate::Error> {
+        let mounted = self.read_tool_id()?;
+
+        if mounted == selected {
+            Ok(())
+        } else {
+            Err(SyError::component(format!("The tool mounted does not match the tool selected! (Mounted: {:?}, Selected: {:?})", 
+                mounted, selected)).into())
+        }
+    }
+}
+
+// ID pins
+    /// Identifies tools by a set of binary coded ID pins, the value read is the tool id + 1 (`0` meaning no tool mounted)
+    pub struct IdPins<P : InputPin, const N : usize> {
+        pins : [P; N]
+    }
+
+    impl<P : InputPin, const N : usize> IdPins<P, N> {
+        /// Creates a new set of ID pins, the first pin being the least significant bit
+        pub fn new(pins : [P; N]) -> Self {
+            Self { pins }
+        }
+    }
+
+    impl<P : InputPin, const N : usize> ToolIdent for IdPins<P, N> {
+        fn read_tool_id(&mut self) -> Result<Option<usize>, crate::Error> {
+            let mut value = 0;
+
+            for i in 0 .. N {
+                if self.pins[i].is_high().map_err(|err| SyError::component(format!("Failed to read tool ID pin {}! ({:?})", 
+                    i, err)))? {
+                    value |= 1 << i;
+                }
+            }
+
+            Ok(value.checked_sub(1))
+        }
+    }
+// 
+
+// Resistor ladder
+    /// Identifies tools by a resistor ladder on an analog pin, each tool has its own voltage level
+    /// 
+    /// Voltages matching none of the levels (e.g. a broken contact) are an error, they never identify a tool
+    pub struct ResistorLadder<F : FnMut() -> Result<f32, crate::Error>> {
+        read : F,
+        
+        /// The voltage levels (relative to the reference voltage, `0.0` to `1.0`) of each tool id
+        pub levels : Vec<f32>,
+        /// The voltage level read if no tool is mounted
+        pub empty : f32,
+        /// The maximum deviation allowed between a measured voltage and a level
+        pub tolerance : f32
+    }
+
+    impl<F : FnMut() -> Result<f32, crate::Error>> ResistorLadder<F> {
+        /// Creates a new resistor ladder identifier
+        /// - `read`: Function reading the relative voltage of the analog pin (`0.0` to `1.0`)
+        /// - `levels`: The voltage levels of each tool id
+        /// - `empty`: The voltage level read if no tool is mounted
+        /// - `tolerance`: The maximum deviation allowed between a measured voltage and a level
+        pub fn new(read : F, levels : Vec<f32>, empty : f32, tolerance : f32) -> Self {
+            Self { read, levels, empty, tolerance }
+        }
+    }
+
+    impl<F : FnMut() -> Result<f32, crate::Error>> ToolIdent for ResistorLadder<F> {
+        fn read_tool_id(&mut self) -> Result<Option<usize>, crate::Error> {
+            let voltage = (self.read)()?;
+
+            if (self.empty - voltage).abs() <= self.tolerance {
+                return Ok(None);
+            }
+
+            self.levels.iter().position(|level| (level - voltage).abs() <= self.tolerance)
+                .map(Some)
+                .ok_or_else(|| SyError::component(format!("The tool ID voltage matches no tool! (Voltage: {})", voltage)).into())
+        }
+    }
+// 
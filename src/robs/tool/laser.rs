@@ -0,0 +1,144 @@
+use embedded_hal::pwm::SetDutyCycle;
+use glam::Vec3;
+use serde_json::json;
+use syact::{Dismantle, Setup};
+use syunit::*;
+
+use crate::SyError;
+use crate::robs::tool::{LaserTool, Tool};
+
+/// A laser tool driven by a PWM output, the power is set by the duty cycle
+pub struct Laser<P : SetDutyCycle> {
+    pwm : P,
+
+    _power : f32,
+    _firing : bool,
+    _gated : bool,
+
+    _vec : Vec3,
+    _mass : f32
+}
+
+impl<P : SetDutyCycle> Laser<P> {
+    /// Creates a new laser tool
+    /// - `pwm`: The PWM output driving the laser power
+    /// - `vec`: The characteristic vector of the tool (pointing to the focus point)
+    /// - `mass`: The mass of the tool
+    pub fn new(pwm : P, vec : Vec3, mass : f32) -> Self {
+        Self {
+            pwm,
+
+            _power: 0.0,
+            _firing: false,
+            _gated: false,
+
+            _vec: vec,
+            _mass: mass
+        }
+    }
+
+    /// Writes the current state to the PWM output
+    fn write_output(&mut self) -> Result<(), crate::Error> {
+        let res = if self._firing & !self._gated {
+            let duty = (self._power * self.pwm.max_duty_cycle() as f32) as u16;
+            self.pwm.set_duty_cycle(duty)
+        } else {
+            self.pwm.set_duty_cycle_fully_off()
+        };
+
+        res.map_err(|err| SyError::component(format!("Failed to set the laser PWM output! ({:?})", err)).into())
+    }
+}
+
+impl<P : SetDutyCycle> Setup for Laser<P> {
+    fn setup(&mut self) -> Result<(), syact::Error> {
+        self._firing = false;
+        self.write_output().map_err(Into::into)
+    }
+}
+
+impl<P : SetDutyCycle> Dismantle for Laser<P> {
+    fn dismantle(&mut self) -> Result<(), syact::Error> {
+        self._firing = false;
+        self.write_output().map_err(Into::into)
+    }
+}
+
+impl<P : SetDutyCycle + 'static> Tool for Laser<P> {
+    // Upgrade
+        fn laser_tool(&self) -> Option<&dyn LaserTool> {
+            Some(self)
+        }
+
+        fn laser_tool_mut(&mut self) -> Option<&mut dyn LaserTool> {
+            Some(self)
+        }
+    //
+
+    // Stats
+        fn get_json(&self) -> serde_json::Value {
+            json!({
+                "power": self._power,
+                "firing": self._firing,
+                "vec": self._vec.to_array(),
+                "mass": self._mass
+            })
+        }
+
+        fn vec(&self) -> Vec3 {
+            self._vec
+        }
+
+        fn inertia(&self) -> Inertia {
+            Inertia(self._mass * self._vec.length().powi(2) / 1_000_000.0)
+        }
+
+        fn mass(&self) -> f32 {
+            self._mass
+        }
+    //
+}
+
+impl<P : SetDutyCycle> LaserTool for Laser<P> {
+    // Actions
+        fn set_power(&mut self, power : f32) -> Result<(), crate::Error> {
+            if !(0.0 ..= 1.0).contains(&power) {
+                return Err(SyError::config(format!("Invalid laser power! Must be between 0.0 and 1.0 (Power: {})", 
+                    power)).into());
+            }
+
+            self._power = power;
+            self.write_output()
+        }
+
+        fn fire(&mut self, interlock : bool) -> Result<(), crate::Error> {
+            if !interlock {
+                self.cease()?;
+                return Err(SyError::safety("The station interlock is not satisfied, the laser cannot fire!").into());
+            }
+
+            self._firing = true;
+            self.write_output()
+        }
+
+        fn cease(&mut self) -> Result<(), crate::Error> {
+            self._firing = false;
+            self.write_output()
+        }
+
+        fn gate(&mut self, rapid : bool) -> Result<(), crate::Error> {
+            self._gated = rapid;
+            self.write_output()
+        }
+    //
+
+    // State
+        fn power(&self) -> f32 {
+            self._power
+        }
+
+        fn is_firing(&self) -> bool {
+            self._firing
+        }
+    //
+}
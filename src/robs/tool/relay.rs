@@ -0,0 +1,146 @@
+use embedded_hal::digital::OutputPin;
+use glam::Vec3;
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use syact::{Dismantle, Setup};
+use syunit::*;
+
+use crate::SyError;
+use crate::robs::tool::{OutputTool, Tool};
+
+/// Configuration of a `RelayBoard`, as stored in configuration files
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RelayConfig {
+    /// Names of the channels, in the order of the pins
+    pub channels : Vec<String>,
+    /// The characteristic vector of the board
+    #[serde(default)]
+    pub vec : [f32; 3],
+    /// The mass of the board
+    #[serde(default)]
+    pub mass : f32
+}
+
+/// A board of relays or dry-contact outputs (lights, fans, solenoids ...) mounted to the flange, each channel driven
+/// by an output pin (GPIO or I/O expander)
+pub struct RelayBoard<P : OutputPin> {
+    pins : Vec<P>,
+    names : Vec<String>,
+    states : Vec<bool>,
+
+    _vec : Vec3,
+    _mass : f32
+}
+
+impl<P : OutputPin> RelayBoard<P> {
+    /// Creates a new relay board, all channels are off
+    /// - `pins`: The output pins of the channels
+    /// - `names`: The names of the channels, must match the number of pins
+    /// - `vec`: The characteristic vector of the board
+    /// - `mass`: The mass of the board
+    pub fn new(pins : Vec<P>, names : Vec<String>, vec : Vec3, mass : f32) -> Result<Self, crate::Error> {
+        if pins.len() != names.len() {
+            return Err(SyError::config(format!("The number of channel names must match the number of pins! (Names: {}, Pins: {})",
+                names.len(), pins.len())).into());
+        }
+
+        Ok(Self {
+            states: vec![false; pins.len()],
+            pins,
+            names,
+
+            _vec: vec,
+            _mass: mass
+        })
+    }
+
+    /// Creates a new relay board out of a configuration
+    pub fn from_config(conf : &RelayConfig, pins : Vec<P>) -> Result<Self, crate::Error> {
+        Self::new(pins, conf.channels.clone(), Vec3::from_array(conf.vec), conf.mass)
+    }
+
+    fn write_all(&mut self, state : bool) -> Result<(), crate::Error> {
+        for channel in 0 .. self.pins.len() {
+            self.set_channel(channel, state)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<P : OutputPin> Setup for RelayBoard<P> {
+    fn setup(&mut self) -> Result<(), syact::Error> {
+        self.write_all(false).map_err(Into::into)
+    }
+}
+
+impl<P : OutputPin> Dismantle for RelayBoard<P> {
+    fn dismantle(&mut self) -> Result<(), syact::Error> {
+        self.write_all(false).map_err(Into::into)
+    }
+}
+
+impl<P : OutputPin + 'static> Tool for RelayBoard<P> {
+    // Upgrade
+        fn output_tool(&self) -> Option<&dyn OutputTool> {
+            Some(self)
+        }
+
+        fn output_tool_mut(&mut self) -> Option<&mut dyn OutputTool> {
+            Some(self)
+        }
+    //
+
+    // Stats
+        fn get_json(&self) -> serde_json::Value {
+            json!({
+                "channels": self.names,
+                "states": self.states,
+                "vec": self._vec.to_array(),
+                "mass": self._mass
+            })
+        }
+
+        fn vec(&self) -> Vec3 {
+            self._vec
+        }
+
+        fn inertia(&self) -> Inertia {
+            Inertia(self._mass * self._vec.length().powi(2) / 1_000_000.0)
+        }
+
+        fn mass(&self) -> f32 {
+            self._mass
+        }
+    //
+}
+
+impl<P : OutputPin> OutputTool for RelayBoard<P> {
+    // Actions
+        fn set_channel(&mut self, channel : usize, state : bool) -> Result<(), crate::Error> {
+            let pin = self.pins.get_mut(channel)
+                .ok_or_else(|| SyError::config(format!("Invalid relay channel! (Channel: {}, Channels: {})", 
+                    channel, self.states.len())))?;
+
+            let res = if state { pin.set_high() } else { pin.set_low() };
+            res.map_err(|err| SyError::component(format!("Failed to set relay channel {}! ({:?})", channel, err)))?;
+
+            self.states[channel] = state;
+            Ok(())
+        }
+    //
+
+    // State
+        fn channels(&self) -> usize {
+            self.pins.len()
+        }
+
+        fn channel_name(&self, channel : usize) -> Option<&str> {
+            self.names.get(channel).map(|n| n.as_str())
+        }
+
+        fn channel(&self, channel : usize) -> Option<bool> {
+            self.states.get(channel).copied()
+        }
+    //
+}
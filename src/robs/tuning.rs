@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Robot, SyError};
+use crate::robs::{DriverLink, DriverParams};
+
+/// Motion tuning of a single component
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AxisTuning {
+    /// Maximum velocity of the component
+    pub omega_max : f32,
+    /// Maximum acceleration of the component, used by motion planners
+    pub alpha_max : Option<f32>,
+    /// Maximum jerk of the component, used by motion planners
+    pub jerk_max : Option<f32>,
+    /// Motor current in amperes, pushed to the driver if the component has a `DriverLink`
+    pub current : Option<f32>
+}
+
+/// Named tuning profiles (e.g. "precise", "fast", "quiet") for all components of a robot, stored in the package 
+/// and switchable at runtime
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TuningSet {
+    /// The profiles by name, each containing the tuning of every component
+    pub profiles : HashMap<String, Vec<AxisTuning>>,
+
+    #[serde(skip)]
+    active : Option<String>
+}
+
+impl TuningSet {
+    /// Creates a new empty set of profiles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a profile
+    pub fn add<S : Into<String>>(&mut self, name : S, axes : Vec<AxisTuning>) {
+        self.profiles.insert(name.into(), axes);
+    }
+
+    /// Returns the name of the active profile
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    /// Returns the tuning of the given profile, checking the number of components
+    pub fn profile<const C : usize>(&self, name : &str) -> Result<&[AxisTuning], crate::Error> {
+        let axes = self.profiles.get(name).ok_or_else(|| SyError::config(format!("No tuning profile named '{}'!", name)))?;
+
+        if axes.len() != C {
+            return Err(SyError::config(format!("The tuning profile '{}' does not match the number of components! (Profile: {}, Components: {})", 
+                name, axes.len(), C)).into());
+        }
+
+        Ok(axes)
+    }
+
+    /// Activates a profile, applying the velocities to the robot and pushing the currents to the `links` given 
+    /// (one per component, `None` for components without a driver link)
+    pub fn activate<R, G, T, const C : usize>(&mut self, name : &str, rob : &mut R, links : &mut [Option<&mut dyn DriverLink>]) 
+    -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        let axes = self.profile::<C>(name)?;
+
+        let mut omega_max = [Velocity(0.0); C];
+        for i in 0 .. C {
+            omega_max[i] = Velocity(axes[i].omega_max);
+        }
+
+        for (i, link) in links.iter_mut().enumerate() {
+            if let (Some(link), Some(current)) = (link, axes.get(i).and_then(|a| a.current)) {
+                link.push_params(&DriverParams { current: Some(current), ..Default::default() })
+                    .map_err(|err| SyError::component(format!("Driver {}: {}", i, err)))?;
+            }
+        }
+
+        rob.set_omega_max(omega_max);
+        self.active = Some(name.to_owned());
+
+        Ok(())
+    }
+}
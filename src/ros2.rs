@@ -0,0 +1,160 @@
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Identity, Robot, SyError};
+use crate::traj::{StreamSender, TrajPoint};
+
+/// Name of the topic the joint states are published on
+pub const JOINT_STATES_TOPIC : &str = "joint_states";
+/// Name of the topic joint trajectories are received on
+pub const TRAJECTORY_TOPIC : &str = "joint_trajectory";
+/// Name of the tool service
+pub const TOOL_SERVICE : &str = "tool";
+
+/// Mirror of `sensor_msgs/msg/JointState`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JointState {
+    /// Time stamp in seconds
+    pub stamp : f64,
+    /// Names of the joints
+    pub name : Vec<String>,
+    /// Positions of the joints (radians or meters)
+    pub position : Vec<f64>,
+    /// Velocities of the joints, may be empty
+    pub velocity : Vec<f64>,
+    /// Efforts of the joints, may be empty
+    pub effort : Vec<f64>
+}
+
+/// Mirror of `trajectory_msgs/msg/JointTrajectoryPoint`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JointTrajectoryPoint {
+    /// Positions of the joints
+    pub positions : Vec<f64>,
+    /// Time from the start of the trajectory in seconds
+    pub time_from_start : f64
+}
+
+/// Mirror of `trajectory_msgs/msg/JointTrajectory`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JointTrajectory {
+    /// Names of the joints, defining the order of the positions
+    pub joint_names : Vec<String>,
+    /// The points of the trajectory
+    pub points : Vec<JointTrajectoryPoint>
+}
+
+/// Tool actions exposed as services
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToolService {
+    /// Activates the tool equipped
+    Activate,
+    /// Deactivates the tool equipped
+    Deactivate,
+    /// Selects the tool with the given id
+    Select(Option<usize>)
+}
+
+/// The ROS 2 node binding (e.g. built with `rclrs` or `r2r`), the bridge itself does not depend on a client library. 
+/// The topics are given by the bridge, namespaced with the identity of the robot (see `Ros2Bridge::topic()`)
+pub trait Ros2Node {
+    /// Publishes a message on the joint states topic given
+    fn publish_joint_state(&mut self, topic : &str, msg : &JointState) -> Result<(), crate::Error>;
+
+    /// Returns the next message received on the joint trajectory topic given, if any
+    fn try_recv_trajectory(&mut self, topic : &str) -> Option<JointTrajectory>;
+
+    /// Returns the next request of the tool service given, if any
+    fn try_recv_tool_service(&mut self, service : &str) -> Option<ToolService>;
+}
+
+/// Bridges a robot to a `Ros2Node`, so sybot robots can be used with ROS tooling (RViz, MoveIt clients)
+pub struct Ros2Bridge<N : Ros2Node, const C : usize> {
+    /// The node used
+    pub node : N,
+    /// Names of the joints, in the order of the components of the robot
+    pub joint_names : [String; C],
+    /// The identity of the robot, namespacing all topics so multiple robots can share one ROS graph
+    pub identity : Option<Identity>
+}
+
+impl<N : Ros2Node, const C : usize> Ros2Bridge<N, C> {
+    /// Creates a new bridge without identity
+    pub fn new(node : N, joint_names : [String; C]) -> Self {
+        Self { node, joint_names, identity: None }
+    }
+
+    /// Returns the full path of a topic or service, e.g. `/arm/0042/joint_states` or `/joint_states` without identity
+    pub fn topic(&self, name : &str) -> String {
+        match &self.identity {
+            Some(identity) => format!("/{}", identity.topic(name)),
+            None => format!("/{}", name)
+        }
+    }
+
+    /// Publishes the current `Phi` values of the robot as joint state
+    pub fn publish_state<R, G, T>(&mut self, rob : &R, stamp : f64) -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        let msg = JointState {
+            stamp,
+            name: self.joint_names.to_vec(),
+            position: rob.phis().iter().map(|phi| phi.0 as f64).collect(),
+            ..Default::default()
+        };
+
+        let topic = self.topic(JOINT_STATES_TOPIC);
+        self.node.publish_joint_state(&topic, &msg)
+    }
+
+    /// Converts a joint trajectory into trajectory points, reordering the joints by their names
+    pub fn convert_trajectory(&self, traj : &JointTrajectory) -> Result<Vec<TrajPoint<C>>, crate::Error> {
+        let mut order = [0; C];
+        for (i, name) in self.joint_names.iter().enumerate() {
+            order[i] = traj.joint_names.iter().position(|n| n == name)
+                .ok_or_else(|| SyError::remote(format!("The trajectory is missing the joint '{}'!", name)))?;
+        }
+
+        traj.points.iter().map(|point| {
+            let mut phis = [Phi::ZERO; C];
+            for i in 0 .. C {
+                let pos = point.positions.get(order[i])
+                    .ok_or_else(|| SyError::remote("The trajectory point is missing positions!"))?;
+                phis[i] = Phi(*pos as f32);
+            }
+
+            Ok(TrajPoint { time: point.time_from_start as f32, phis })
+        }).collect()
+    }
+
+    /// Handles all pending requests: trajectories are forwarded into the trajectory `stream` of the motion core, tool 
+    /// services are applied to the robot
+    pub async fn spin_once<R, G, T>(&mut self, rob : &mut R, stream : &StreamSender<C>) -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        let (traj_topic, tool_service) = (self.topic(TRAJECTORY_TOPIC), self.topic(TOOL_SERVICE));
+
+        while let Some(traj) = self.node.try_recv_trajectory(&traj_topic) {
+            for point in self.convert_trajectory(&traj)? {
+                stream.send(point).await?;
+            }
+        }
+
+        while let Some(service) = self.node.try_recv_tool_service(&tool_service) {
+            match service {
+                ToolService::Activate => { rob.activate_tool()?; },
+                ToolService::Deactivate => { rob.deactivate_tool()?; },
+                ToolService::Select(id) => { rob.set_tool_id(id); }
+            }
+        }
+
+        Ok(())
+    }
+}
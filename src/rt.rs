@@ -0,0 +1,47 @@
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+
+use tokio::runtime::{Builder, Runtime};
+use tokio::sync::Mutex;
+
+/// A robot, descriptor or station shared between multiple tasks on the same runtime
+pub type Shared<T> = Arc<Mutex<T>>;
+
+static RUNTIME : OnceLock<Runtime> = OnceLock::new();
+
+/// Wraps the given value for sharing it between tasks
+pub fn shared<T>(value : T) -> Shared<T> {
+    Arc::new(Mutex::new(value))
+}
+
+/// Returns the runtime shared by the whole library, created on first use
+/// 
+/// Remotes, interpreters and the motion of the robot should all spawn their tasks on this runtime instead of 
+/// creating their own threads
+pub fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("sybot-rt")
+            .build()
+            .expect("Failed to create the tokio runtime!")
+    })
+}
+
+/// Runs the given future to completion on the shared runtime, used by synchronous callers
+/// 
+/// # Panics
+/// 
+/// Panics if called from within an async context, use `.await` there instead
+pub fn block_on<F : Future>(fut : F) -> F::Output {
+    runtime().block_on(fut)
+}
+
+/// Spawns the given future on the shared runtime
+pub fn spawn<F>(fut : F) -> tokio::task::JoinHandle<F::Output> 
+where
+    F : Future + Send + 'static,
+    F::Output : Send + 'static
+{
+    runtime().spawn(fut)
+}
@@ -0,0 +1,242 @@
+use std::time::{Duration, Instant};
+
+use serde::{Serialize, Deserialize};
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Interpreter, Robot, SyError};
+
+// Submodules
+    mod exec;
+    pub use exec::{Breakpoints, ExecControl, ExecSnapshot, ExecState, Executor, LineResult, ModalState};
+
+    mod store;
+    pub use store::{ProgramStore, StoredProgram};
+// 
+
+/// The state of a `Sender`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SenderState {
+    /// Sending lines
+    #[default]
+    Running,
+    /// Paused, can be resumed
+    Paused,
+    /// Stopped, the program can be resumed from a given line with `resume_from()`
+    Stopped,
+    /// All lines have been sent and acknowledged
+    Finished
+}
+
+/// Progress report of a `Sender`, consumable by any front end
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Progress {
+    /// The current state of the sender
+    pub state : SenderState,
+    /// Number of lines acknowledged
+    pub acked : usize,
+    /// Total number of lines in the program
+    pub total : usize,
+    /// Progress in percent
+    pub percent : f32,
+    /// Estimated time remaining
+    pub eta : Option<Duration>
+}
+
+/// Sends a program line by line, tracking the acknowledgement of each line
+/// 
+/// Empty lines and comment lines (starting with `;`) are skipped
+pub struct Sender {
+    lines : Vec<String>,
+    estimates : Option<Vec<Duration>>,
+
+    acked : usize,
+    pending : bool,
+    state : SenderState,
+
+    started : Option<Instant>,
+    elapsed : Duration
+}
+
+impl Sender {
+    /// Creates a new sender for the given program
+    pub fn new(program : &str) -> Self {
+        Self {
+            lines: program.lines()
+                .map(|l| l.trim())
+                .filter(|l| !(l.is_empty() | l.starts_with(';')))
+                .map(|l| l.to_owned())
+                .collect(),
+            estimates: None,
+
+            acked: 0,
+            pending: false,
+            state: SenderState::Running,
+
+            started: None,
+            elapsed: Duration::ZERO
+        }
+    }
+
+    /// Creates a new sender reading the program from the file at the given `path`
+    pub fn from_file(path : &str) -> Result<Self, crate::Error> {
+        Ok(Self::new(std::fs::read_to_string(path)?.as_str()))
+    }
+
+    /// Sets the estimated cycle time of each line, used to calculate the ETA 
+    /// (otherwise the average time of the lines already acknowledged is used)
+    pub fn set_estimates(&mut self, estimates : Vec<Duration>) -> Result<(), crate::Error> {
+        if estimates.len() != self.lines.len() {
+            return Err(SyError::config(format!("The number of estimates must match the number of lines! (Estimates: {}, Lines: {})", 
+                estimates.len(), self.lines.len())).into());
+        }
+
+        self.estimates = Some(estimates);
+        Ok(())
+    }
+
+    // Lines
+        /// Returns all lines of the program
+        pub fn lines(&self) -> &[String] {
+            &self.lines
+        }
+
+        /// Returns the next line to send with its index, `None` if the sender is not running, the previous line 
+        /// has not been acknowledged yet or the program is finished
+        pub fn next_line(&mut self) -> Option<(usize, &str)> {
+            if (self.state != SenderState::Running) | self.pending {
+                return None;
+            }
+
+            if self.acked >= self.lines.len() {
+                self.state = SenderState::Finished;
+                return None;
+            }
+
+            if self.started.is_none() {
+                self.started = Some(Instant::now());
+            }
+
+            self.pending = true;
+            Some((self.acked, self.lines[self.acked].as_str()))
+        }
+
+        /// Acknowledges the line with the given `index`
+        pub fn ack(&mut self, index : usize) -> Result<(), crate::Error> {
+            if !self.pending | (index != self.acked) {
+                return Err(SyError::interpreter(format!("Line {} is not awaiting an acknowledgement!", index)).into());
+            }
+
+            self.pending = false;
+            self.acked += 1;
+
+            if self.acked >= self.lines.len() {
+                self.pause_timer();
+                self.state = SenderState::Finished;
+            }
+
+            Ok(())
+        }
+    // 
+
+    // State
+        /// Returns the current state of the sender
+        pub fn state(&self) -> SenderState {
+            self.state
+        }
+
+        /// Pauses the sender after the pending line
+        pub fn pause(&mut self) {
+            if self.state == SenderState::Running {
+                self.pause_timer();
+                self.state = SenderState::Paused;
+            }
+        }
+
+        /// Resumes a paused sender
+        pub fn resume(&mut self) {
+            if self.state == SenderState::Paused {
+                self.state = SenderState::Running;
+            }
+        }
+
+        /// Stops the sender, the pending line is treated as not acknowledged
+        pub fn stop(&mut self) {
+            if self.state != SenderState::Finished {
+                self.pause_timer();
+                self.pending = false;
+                self.state = SenderState::Stopped;
+            }
+        }
+
+        /// Resumes the program from the given line `index`
+        pub fn resume_from(&mut self, index : usize) -> Result<(), crate::Error> {
+            if index > self.lines.len() {
+                return Err(SyError::interpreter(format!("Line {} is out of range! (Lines: {})", index, self.lines.len())).into());
+            }
+
+            self.acked = index;
+            self.pending = false;
+            self.state = SenderState::Running;
+            Ok(())
+        }
+
+        fn pause_timer(&mut self) {
+            if let Some(inst) = self.started.take() {
+                self.elapsed += inst.elapsed();
+            }
+        }
+    // 
+
+    // Progress
+        /// Returns the time spent running the program
+        pub fn elapsed(&self) -> Duration {
+            self.elapsed + self.started.map(|i| i.elapsed()).unwrap_or(Duration::ZERO)
+        }
+
+        /// Returns the estimated time remaining
+        pub fn eta(&self) -> Option<Duration> {
+            if let Some(estimates) = &self.estimates {
+                Some(estimates[self.acked ..].iter().sum())
+            } else if self.acked > 0 {
+                Some(self.elapsed() / self.acked as u32 * (self.lines.len() - self.acked) as u32)
+            } else {
+                None
+            }
+        }
+
+        /// Returns a progress report of the sender
+        pub fn progress(&self) -> Progress {
+            Progress {
+                state: self.state,
+                acked: self.acked,
+                total: self.lines.len(),
+                percent: if self.lines.is_empty() {
+                    100.0
+                } else {
+                    self.acked as f32 / self.lines.len() as f32 * 100.0
+                },
+                eta: self.eta()
+            }
+        }
+    //
+
+    /// Sends the next line to the interpreter and acknowledges it once interpreted, returns `None` if no line was sent
+    pub fn send_next<I, G, R, D, S, T, O, const C : usize>(&mut self, intpr : &I, rob : &mut R, desc : &mut D, stat : &mut S) 
+        -> Result<Option<Vec<O>>, crate::Error> 
+    where
+        I : Interpreter<G, R, D, S, T, O, C>,
+        G : SyncActuatorGroup<T, C>,
+        R : Robot<G, T, C>,
+        D : Descriptor<C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        let Some((index, line)) = self.next_line() else {
+            return Ok(None);
+        };
+
+        let res = intpr.interpret(rob, desc, stat, line);
+        self.ack(index)?;
+        Ok(Some(res))
+    }
+}
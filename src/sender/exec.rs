@@ -0,0 +1,430 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+use tokio::sync::Notify;
+
+use crate::{Descriptor, Interpreter, Robot, Station, SyError};
+use crate::gcode::arc::Plane;
+use crate::gcode::emit::Units;
+use crate::gcode::motion::Motion;
+use crate::gcode::target::work_offset;
+use crate::gcode::word::{has_code, parse_line, Word};
+use crate::robs::SpeedOverride;
+use crate::sender::{Progress, Sender};
+use crate::traj::VelocityProfile;
+
+/// The state of a program execution, see `ExecControl`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecState {
+    /// Executing lines
+    #[default]
+    Running,
+    /// Suspended, can be resumed
+    Paused,
+    /// Aborted, the execution cannot be resumed
+    Aborted
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    state : Mutex<ExecState>,
+    held : Mutex<Option<f32>>,
+    notify : Notify,
+    speed : SpeedOverride
+}
+
+/// A cloneable handle to pause, resume and abort a program execution from other tasks
+///
+/// Executions are suspended between lines. Movements executed with the speed override of the handle (e.g.
+/// `Trajectory::execute_scaled` or robots sharing it, see `with_speed_override()`) are held at the next segment as well
+#[derive(Clone, Debug, Default)]
+pub struct ExecControl {
+    inner : Arc<Inner>
+}
+
+impl ExecControl {
+    /// Creates a new handle in the running state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new handle in the running state sharing the speed override of the robot (see 
+    /// `Robot::speed_override()`), so pausing the execution holds the movements of the robot as well
+    pub fn with_speed_override(speed : SpeedOverride) -> Self {
+        Self {
+            inner: Arc::new(Inner { speed, ..Default::default() })
+        }
+    }
+
+    /// Returns the current state
+    pub fn state(&self) -> ExecState {
+        *self.inner.state.lock().unwrap()
+    }
+
+    /// Returns the speed override held at `0.0` while paused
+    pub fn speed_override(&self) -> &SpeedOverride {
+        &self.inner.speed
+    }
+
+    /// Pauses the execution
+    pub fn pause(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+
+        if *state == ExecState::Running {
+            *state = ExecState::Paused;
+            *self.inner.held.lock().unwrap() = Some(self.inner.speed.get());
+            self.inner.speed.set(0.0).ok();
+        }
+    }
+
+    /// Resumes a paused execution
+    pub fn resume(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+
+        if *state == ExecState::Paused {
+            *state = ExecState::Running;
+            self.restore_speed();
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    /// Aborts the execution
+    pub fn abort(&self) {
+        *self.inner.state.lock().unwrap() = ExecState::Aborted;
+        self.restore_speed();
+        self.inner.notify.notify_waiters();
+    }
+
+    fn restore_speed(&self) {
+        if let Some(factor) = self.inner.held.lock().unwrap().take() {
+            self.inner.speed.set(factor).ok();
+        }
+    }
+
+    /// Waits until the execution is running, returns an error if it has been aborted
+    pub async fn wait_running(&self) -> Result<(), crate::Error> {
+        loop {
+            let notified = self.inner.notify.notified();
+
+            match self.state() {
+                ExecState::Running => return Ok(()),
+                ExecState::Aborted => return Err(SyError::safety("The program execution has been aborted!").into()),
+                ExecState::Paused => notified.await
+            }
+        }
+    }
+}
+
+/// The modal state of a gcode program, kept across lines
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModalState {
+    /// The motion mode of the lines (`G0` - `G3`)
+    pub motion : Motion,
+    /// Relative positioning (`G91`), absolute otherwise (`G90`)
+    pub relative : bool,
+    /// The plane selected for arcs (`G17` - `G19`)
+    pub plane : Plane,
+    /// The units of the program (`G20` / `G21`)
+    pub units : Units,
+    /// The last feed rate given (`F`), in units of the program per minute
+    pub feed : Option<f32>,
+    /// The last tool selected (`T`)
+    pub tool : Option<usize>,
+    /// The work offset frame selected (`G54` - `G59`), `None` for machine coordinates (`G53`), see `work_offset()`
+    pub work_offset : Option<&'static str>
+}
+
+impl ModalState {
+    /// Updates the state with the words of a line. Fails without changing the state if a `G` or `T` word is not a 
+    /// non-negative integer, as codes like `G91.1` or `T-1` would be misread otherwise
+    pub fn update(&mut self, words : &[Word]) -> Result<(), crate::Error> {
+        for word in words.iter().filter(|w| (w.letter == 'G') | (w.letter == 'T')) {
+            if let Some(value) = word.num() {
+                if (value < 0.0) | (value.fract() != 0.0) {
+                    return Err(SyError::interpreter(format!("Unsupported code {}{}! (Codes must be non-negative integers)", 
+                        word.letter, value)).into());
+                }
+            }
+        }
+
+        for word in words {
+            let Some(value) = word.num() else {
+                continue;
+            };
+
+            match (word.letter, value as u32) {
+                ('G', 20) => self.units = Units::Inches,
+                ('G', 21) => self.units = Units::Millimeters,
+                ('G', 90) => self.relative = false,
+                ('G', 91) => self.relative = true,
+                ('G', code) => if let Some(plane) = Plane::from_gcode(code) {
+                    self.plane = plane;
+                } else if let Some(motion) = Motion::from_gcode(code) {
+                    self.motion = motion;
+                },
+                ('F', _) => self.feed = Some(value),
+                ('T', tool) => self.tool = Some(tool as usize),
+                _ => { }
+            }
+        }
+
+        if let Some(offset) = work_offset(words) {
+            self.work_offset = offset;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the TCP speed of the feed rate in millimeters per second, `None` if no feed rate has been given
+    pub fn feed_speed(&self) -> Option<Velocity> {
+        self.feed.map(|feed| Velocity(self.units.to_mm(feed) / 60.0))
+    }
+}
+
+/// Breakpoints of an `Executor`, the execution is paused before a matching line is executed
+#[derive(Clone, Debug, Default)]
+pub struct Breakpoints {
+    /// Indices of the lines (as counted by the `Sender`)
+    pub lines : BTreeSet<usize>,
+    /// M-codes (e.g. `6` for tool changes)
+    pub mcodes : BTreeSet<u32>
+}
+
+impl Breakpoints {
+    /// Checks if the line with the given index and words hits a breakpoint
+    pub fn hit(&self, index : usize, words : &[Word]) -> bool {
+        self.lines.contains(&index) 
+            | words.iter().any(|w| (w.letter == 'M') & w.num().map_or(false, |m| self.mcodes.contains(&(m as u32))))
+    }
+}
+
+/// State of an execution for inspection between steps, e.g. by a debugger front end
+#[derive(Clone, Debug)]
+pub struct ExecSnapshot {
+    /// The index of the last line executed
+    pub line : Option<usize>,
+    /// The text of the last line executed
+    pub text : Option<String>,
+    /// The modal state after the last line
+    pub modal : ModalState,
+    /// The progress of the execution
+    pub progress : Progress,
+    /// The planned velocity profile of the last line, if previews are enabled
+    pub profile : Option<VelocityProfile>
+}
+
+/// The result of a single line executed by an `Executor`
+#[derive(Clone, Debug)]
+pub struct LineResult<O> {
+    /// The index of the line
+    pub line : usize,
+    /// The output of the interpreter
+    pub output : Vec<O>,
+    /// The planned velocity profile of the line, if previews are enabled (see `Executor::preview`)
+    pub profile : Option<VelocityProfile>
+}
+
+impl<O : serde::Serialize> LineResult<O> {
+    /// Returns the result as JSON, `{ "line": .., "output": [..], "profile": { "time": [..], "speed": [..] } }`, the
+    /// profile is `null` if there is none (see `VelocityProfile::to_json()`)
+    pub fn to_json(&self) -> Result<serde_json::Value, crate::Error> {
+        Ok(serde_json::json!({
+            "line": self.line,
+            "output": serde_json::to_value(&self.output)?,
+            "profile": self.profile.as_ref().map(VelocityProfile::to_json)
+        }))
+    }
+}
+
+/// Executes a program line by line with an interpreter, controlled by an `ExecControl`
+///
+/// `M0` pauses the execution after the line, `M1` only if optional stops are enabled
+pub struct Executor {
+    sender : Sender,
+    control : ExecControl,
+    modal : ModalState,
+    last : Option<(usize, String)>,
+    profile : Option<VelocityProfile>,
+
+    /// Wheiter `M1` pauses the execution
+    pub optional_stop : bool,
+    /// Pauses the execution after every line
+    pub single_step : bool,
+    /// The breakpoints of the execution
+    pub breakpoints : Breakpoints,
+    /// Requests the planned velocity profile of every line from the interpreter, see `Interpreter::velocity_profile`
+    pub preview : bool
+}
+
+impl Executor {
+    /// Creates a new executor for the lines of the given sender
+    pub fn new(sender : Sender, control : ExecControl) -> Self {
+        Self {
+            sender,
+            control,
+            modal: ModalState::default(),
+            last: None,
+            profile: None,
+
+            optional_stop: false,
+            single_step: false,
+            breakpoints: Breakpoints::default(),
+            preview: false
+        }
+    }
+
+    /// Returns the control handle of the execution
+    pub fn control(&self) -> &ExecControl {
+        &self.control
+    }
+
+    /// Returns the modal state after the last line executed
+    pub fn modal(&self) -> &ModalState {
+        &self.modal
+    }
+
+    /// Returns the sender of the execution
+    pub fn sender(&self) -> &Sender {
+        &self.sender
+    }
+
+    /// Returns a progress report of the execution
+    pub fn progress(&self) -> Progress {
+        self.sender.progress()
+    }
+
+    /// Returns the state of the execution for inspection
+    pub fn snapshot(&self) -> ExecSnapshot {
+        ExecSnapshot {
+            line: self.last.as_ref().map(|(i, _)| *i),
+            text: self.last.as_ref().map(|(_, l)| l.clone()),
+            modal: self.modal.clone(),
+            progress: self.progress(),
+            profile: self.profile.clone()
+        }
+    }
+
+    /// Returns the planned velocity profile of the last line, if previews are enabled
+    pub fn profile(&self) -> Option<&VelocityProfile> {
+        self.profile.as_ref()
+    }
+
+    /// Stops the sender and returns the error of the invalid line with the given index
+    fn reject(&mut self, index : usize, err : crate::Error) -> crate::Error {
+        self.sender.stop();
+        SyError::interpreter(format!("Line {} is invalid! ({})", index, err)).into()
+    }
+
+    /// Waits until the execution may continue and returns the next line with its index, `None` if the program is
+    /// finished
+    async fn next(&mut self) -> Result<Option<(usize, String)>, crate::Error> {
+        if let Err(err) = self.control.wait_running().await {
+            self.sender.stop();
+            return Err(err);
+        }
+
+        self.sender.resume();
+        Ok(self.sender.next_line().map(|(i, l)| (i, l.to_owned())))
+    }
+
+    /// Executes the next line, updating the modal state and handling `M0` / `M1`. Returns the output of the
+    /// interpreter together with the planned velocity profile of the line, `None` if the program is finished
+    pub async fn step<I, G, R, D, S, T, O, const C : usize>(&mut self, intpr : &I, rob : &mut R, desc : &mut D, stat : &mut S)
+        -> Result<Option<LineResult<O>>, crate::Error>
+    where
+        I : Interpreter<G, R, D, S, T, O, C>,
+        G : SyncActuatorGroup<T, C>,
+        R : Robot<G, T, C>,
+        D : Descriptor<C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if let Some(Err(err)) = rob.cancel_token().map(|c| c.check()) {
+            self.sender.stop();
+            return Err(err);
+        }
+
+        let Some((index, line)) = self.next().await? else {
+            return Ok(None);
+        };
+
+        // Invalid lines stop the execution before they are acknowledged
+        let words = match parse_line(&line) {
+            Ok(words) => words,
+            Err(err) => return Err(self.reject(index, err))
+        };
+
+        if self.breakpoints.hit(index, &words) {
+            self.control.pause();
+            self.sender.pause();
+
+            if let Err(err) = self.control.wait_running().await {
+                self.sender.stop();
+                return Err(err);
+            }
+
+            self.sender.resume();
+        }
+
+        if let Err(err) = self.modal.update(&words) {
+            return Err(self.reject(index, err));
+        }
+
+        self.profile = if self.preview {
+            intpr.velocity_profile(rob, desc, &line)
+        } else {
+            None
+        };
+
+        let output = intpr.interpret(rob, desc, stat, &line);
+        self.sender.ack(index)?;
+        self.last = Some((index, line));
+
+        let stop = has_code(&words, 'M', 0) | (has_code(&words, 'M', 1) & self.optional_stop);
+        if stop | self.single_step {
+            self.control.pause();
+            self.sender.pause();
+        }
+
+        Ok(Some(LineResult { line: index, output, profile: self.profile.clone() }))
+    }
+
+    /// Executes the whole program in its own scope (see `Robot::push_scope()`), `on_progress` is called after every line.
+    /// The tool mounted is verified before the first line (see `Station::verify_tool()`), the tool usage is stored in
+    /// the station after every line (see `Station::store_tool_usage()`)
+    pub async fn run<I, G, R, D, S, T, O, const C : usize, F>(&mut self, intpr : &I, rob : &mut R, desc : &mut D, stat : &mut S,
+        mut on_progress : F) -> Result<Vec<O>, crate::Error>
+    where
+        I : Interpreter<G, R, D, S, T, O, C>,
+        S : Station<G, T, C, Robot = R>,
+        G : SyncActuatorGroup<T, C>,
+        R : Robot<G, T, C>,
+        D : Descriptor<C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        F : FnMut(&Progress)
+    {
+        let mut outputs = Vec::new();
+
+        stat.verify_tool(rob)?;
+
+        // Offsets and speed limits of the program must not leak into the next one
+        rob.push_scope();
+
+        let res = loop {
+            match self.step(intpr, rob, desc, stat).await {
+                Ok(Some(res)) => {
+                    outputs.extend(res.output);
+                    stat.store_tool_usage(rob);
+                    on_progress(&self.progress());
+                },
+                Ok(None) => break Ok(()),
+                Err(err) => break Err(err)
+            }
+        };
+
+        rob.pop_scope();
+        res.map(|_| outputs)
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use serde::{Serialize, Deserialize};
+
+use crate::SyError;
+use crate::sender::Sender;
+
+/// File extension of the programs stored
+const EXTENSION : &str = "gcode";
+
+/// Information about a program in a `ProgramStore`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredProgram {
+    /// Name of the program
+    pub name : String,
+    /// Size of the program in bytes
+    pub size : u64,
+    /// Time of the last modification (seconds since the UNIX epoch)
+    pub modified : u64
+}
+
+/// Filesystem-backed storage of named gcode programs, each program is stored as `<name>.gcode` in the directory
+#[derive(Clone, Debug)]
+pub struct ProgramStore {
+    dir : PathBuf
+}
+
+impl ProgramStore {
+    /// Opens the store in the given directory, the directory is created if it does not exist
+    pub fn new<P : Into<PathBuf>>(dir : P) -> Result<Self, crate::Error> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the path of the program with the given name, names may only contain alphanumeric characters, `-` and `_`
+    fn path(&self, name : &str) -> Result<PathBuf, crate::Error> {
+        if name.is_empty() | !name.chars().all(|c| c.is_ascii_alphanumeric() | (c == '-') | (c == '_')) {
+            return Err(SyError::config(format!("Invalid program name '{}'! Only alphanumeric characters, '-' and '_' are allowed", 
+                name)).into());
+        }
+
+        Ok(self.dir.join(format!("{}.{}", name, EXTENSION)))
+    }
+
+    /// Stores a program, replacing the program with the same name
+    pub fn save(&self, name : &str, program : &str) -> Result<(), crate::Error> {
+        Ok(std::fs::write(self.path(name)?, program)?)
+    }
+
+    /// Loads the program with the given name
+    pub fn load(&self, name : &str) -> Result<String, crate::Error> {
+        let path = self.path(name)?;
+
+        if !path.exists() {
+            return Err(SyError::config(format!("No program named '{}' has been stored!", name)).into());
+        }
+
+        Ok(std::fs::read_to_string(path)?)
+    }
+
+    /// Creates a sender for the program with the given name
+    pub fn sender(&self, name : &str) -> Result<Sender, crate::Error> {
+        Ok(Sender::new(&self.load(name)?))
+    }
+
+    /// Removes the program with the given name
+    pub fn remove(&self, name : &str) -> Result<(), crate::Error> {
+        Ok(std::fs::remove_file(self.path(name)?)?)
+    }
+
+    /// Lists all the programs stored, sorted by name
+    pub fn list(&self) -> Result<Vec<StoredProgram>, crate::Error> {
+        let mut programs = Vec::new();
+
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+
+            if path.extension().map_or(true, |ext| ext != EXTENSION) {
+                continue;
+            }
+
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            let meta = std::fs::metadata(&path)?;
+
+            programs.push(StoredProgram {
+                name: name.to_owned(),
+                size: meta.len(),
+                modified: meta.modified()?.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+            });
+        }
+
+        programs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(programs)
+    }
+}
@@ -1,7 +1,59 @@
 use syact::math::movements::DefinedActuator;
 use syact::{SyncActuator, SyncActuatorGroup};
 
-use crate::Robot;
+use crate::{Robot, SyError};
+use crate::events::StateEvent;
+use crate::robs::tool::ToolIdent;
+
+// Submodules
+    mod actor;
+    pub use actor::{Actor, ActorStatus, Addr, RestartPolicy, Supervisor};
+
+    mod calib;
+    pub use calib::{Calibration, CalibStep, Operator};
+
+    mod calib_history;
+    pub use calib_history::{CalibEntry, CalibHistory, CalibSet};
+
+    mod calendar;
+    pub use calendar::{CalendarTime, CronRule, ScheduledJob, ScheduleRule, Scheduler};
+
+    mod cell;
+    pub use cell::{sync, RobotCell};
+
+    mod counters;
+    pub use counters::{CounterEvent, PartCounts, ProductionCounters};
+
+    mod drift;
+    pub use drift::DriftCompensation;
+
+    mod external;
+    pub use external::{ExternalAxis, ExternalKind, FrameTracking};
+
+    mod handover;
+    pub use handover::{Handover, HandoverState, HandoverStep};
+
+    mod history;
+    pub use history::{JobHistory, JobQuery, JobRecord, JobResult};
+
+    mod limits;
+    pub use limits::{LimitMonitor, LimitReaction, LimitSwitch};
+
+    mod overrides;
+    pub use overrides::OverrideInput;
+
+    mod recipe;
+    pub use recipe::{Recipe, RecipeBook};
+
+    mod step_check;
+    pub use step_check::{RefSwitch, StepCheck, StepCheckResult};
+
+    mod toolchange;
+    pub use toolchange::{change_tool, RackSlot, ToolRack};
+
+    mod usage;
+    pub use usage::{MaintenanceThresholds, ToolUsage, ToolUsageTracker};
+// 
 
 /// A station defines the environment of a stationary robot
 #[allow(async_fn_in_trait)]
@@ -16,6 +68,124 @@ where
     /// Start calibrating the station and robot
     async fn calibrate(&mut self, rob : &mut Self::Robot) -> Result<(), crate::Error>;
 
-    /// Drive to the home position, often includes calling `calibrate()`
+    /// Drive to the home position, often includes calling `calibrate()`. Implementations should call 
+    /// `Robot::set_homed()` after a successful homing. Call it with `home_checked()`
     async fn home(&mut self, rob : &mut Self::Robot) -> Result<(), crate::Error>;
+
+    /// Homes the robot (see `home()`), homing is rejected while any axis is masked (see `Robot::set_masked()`). The 
+    /// homing is aborted and the robot halted if the token of the robot is cancelled (see `Robot::cancel_token()`)
+    async fn home_checked(&mut self, rob : &mut Self::Robot) -> Result<(), crate::Error> {
+        rob.check_unmasked()?;
+
+        let Some(cancel) = rob.cancel_token().cloned() else {
+            return self.home(rob).await;
+        };
+
+        cancel.check()?;
+        let res = cancel.guard(self.home(rob)).await;
+
+        if cancel.is_cancelled() {
+            rob.halt()?;
+            rob.update()?;
+        }
+
+        res
+    }
+
+    /// Returns wheiter the safety interlock of the station (e.g. closed enclosure) is satisfied, 
+    /// tools like lasers are not allowed to fire otherwise. Stations without interlock are never satisfied
+    fn interlock(&self) -> bool {
+        false
+    }
+
+    // I/O
+        /// Sets the named output of the station
+        fn set_output(&mut self, name : &str, _state : bool) -> Result<(), crate::Error> {
+            Err(SyError::config(format!("The station has no output named '{}'!", name)).into())
+        }
+
+        /// Reads the named input of the station
+        fn input(&mut self, name : &str) -> Result<bool, crate::Error> {
+            Err(SyError::config(format!("The station has no input named '{}'!", name)).into())
+        }
+
+        /// Reads the named analog input of the station, the raw value is passed on as is
+        fn analog_input(&mut self, name : &str) -> Result<f32, crate::Error> {
+            Err(SyError::config(format!("The station has no analog input named '{}'!", name)).into())
+        }
+    // 
+
+    // Tool identification
+        /// Returns the device identifying the tool mounted, `None` if the station has no such device
+        fn tool_ident(&mut self) -> Option<&mut dyn ToolIdent> {
+            None
+        }
+
+        /// Verifies that the tool mounted matches the tool selected by the robot, should be called after a 
+        /// manual tool change before moving the robot
+        fn verify_tool(&mut self, rob : &Self::Robot) -> Result<(), crate::Error> {
+            if let Some(ident) = self.tool_ident() {
+                ident.verify(rob.get_tool_id())
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Returns the tool rack of the station used by `change_tool()`, `None` if the station has none
+        fn tool_rack(&self) -> Option<&ToolRack> {
+            None
+        }
+    // 
+
+    // Tuning
+        /// Activates the named motion tuning profile, see `TuningSet::activate`
+        fn activate_tuning(&mut self, _rob : &mut Self::Robot, name : &str) -> Result<(), crate::Error> {
+            Err(SyError::config(format!("The station has no tuning profile named '{}'!", name)).into())
+        }
+    // 
+
+    // Tool usage
+        /// Returns a reference to the usage tracker kept by the station (e.g. for tools shared between multiple 
+        /// robots), `None` if the usage is only tracked by the robot (see `Robot::tool_usage()`)
+        fn tool_usage(&self) -> Option<&ToolUsageTracker> {
+            None
+        }
+
+        /// Returns a mutable reference to the usage tracker kept by the station, see `tool_usage()`
+        fn tool_usage_mut(&mut self) -> Option<&mut ToolUsageTracker> {
+            None
+        }
+
+        /// Moves the usage recorded by the robot into the tracker of the station, the robot keeps its usage if the 
+        /// station has no tracker. Publishes a `StateEvent::Maintenance` for the tools crossing their maintenance 
+        /// thresholds (see `ToolUsageTracker::newly_due()`). Called after every line of a program
+        fn store_tool_usage(&mut self, rob : &mut Self::Robot) {
+            let due = match self.tool_usage_mut() {
+                Some(tracker) => {
+                    if let Some(recorded) = rob.tool_usage_mut() {
+                        tracker.merge(recorded.take_usages());
+                    }
+
+                    tracker.newly_due()
+                },
+                None => rob.tool_usage_mut().map(ToolUsageTracker::newly_due).unwrap_or_default()
+            };
+
+            if !due.is_empty() {
+                rob.events().publish(StateEvent::Maintenance { tools: due });
+            }
+        }
+    // 
+
+    // Production
+        /// Returns a reference to the production counters of the station, `None` if the station counts no parts
+        fn counters(&self) -> Option<&ProductionCounters> {
+            None
+        }
+
+        /// Returns a mutable reference to the production counters of the station, see `counters()`
+        fn counters_mut(&mut self) -> Option<&mut ProductionCounters> {
+            None
+        }
+    // 
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::SyError;
+
+/// An actor of a station (peripheral, I/O, safety ...), handling messages on its own task
+/// 
+/// Actors are supervised by a `Supervisor`, a failing or panicking actor is restarted without affecting the others. 
+/// The robot itself keeps running in its own motion loop (see [crate::cmd]) and communicates with actors by messages
+pub trait Actor : Send + 'static {
+    /// The type of messages handled by the actor
+    type Msg : Send + 'static;
+
+    /// Called when the actor is (re)started
+    fn started(&mut self) -> Result<(), crate::Error> {
+        Ok(())
+    }
+
+    /// Handles a single message, returning an error causes the actor to be restarted by its supervisor
+    fn handle(&mut self, msg : Self::Msg) -> Result<(), crate::Error>;
+}
+
+/// Defines how a `Supervisor` reacts to a failing actor
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// The actor is not restarted
+    Never,
+    /// The actor is restarted at most `max_restarts` times
+    Limited(usize),
+    /// The actor is always restarted
+    Always
+}
+
+/// The status of a supervised actor
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActorStatus {
+    /// The actor is running, with the number of restarts so far
+    Running(usize),
+    /// The actor failed and is not restarted anymore
+    Failed(String),
+    /// All the addresses of the actor have been dropped
+    Stopped
+}
+
+/// Address of an actor, used to send messages to it
+pub struct Addr<M> {
+    tx : mpsc::UnboundedSender<M>
+}
+
+impl<M> Clone for Addr<M> {
+    fn clone(&self) -> Self {
+        Self { tx: self.tx.clone() }
+    }
+}
+
+impl<M> Addr<M> {
+    /// Sends a message to the actor
+    pub fn send(&self, msg : M) -> Result<(), crate::Error> {
+        self.tx.send(msg).map_err(|_| SyError::remote("The actor has been stopped!").into())
+    }
+}
+
+/// Supervises a set of actors, restarting them according to their `RestartPolicy`
+#[derive(Default)]
+pub struct Supervisor {
+    status : Arc<Mutex<HashMap<String, ActorStatus>>>,
+    handles : Vec<JoinHandle<()>>
+}
+
+impl Supervisor {
+    /// Creates a new supervisor without any actors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns a new supervised actor with the given `name`, the `factory` is used to create the actor on every (re)start
+    pub fn spawn<A, F>(&mut self, name : &str, factory : F, policy : RestartPolicy) -> Addr<A::Msg> 
+    where
+        A : Actor,
+        F : Fn() -> A + Send + 'static
+    {
+        let (tx, rx) = mpsc::unbounded_channel::<A::Msg>();
+        let rx = Arc::new(tokio::sync::Mutex::new(rx));
+        let status = self.status.clone();
+        let name = name.to_owned();
+
+        status.lock().unwrap().insert(name.clone(), ActorStatus::Running(0));
+
+        self.handles.push(tokio::spawn(async move {
+            let mut restarts = 0;
+
+            loop {
+                let task_rx = rx.clone();
+                let mut actor = factory();
+
+                let res = tokio::spawn(async move {
+                    actor.started().map_err(|err| err.to_string())?;
+
+                    let mut rx = task_rx.lock().await;
+                    while let Some(msg) = rx.recv().await {
+                        actor.handle(msg).map_err(|err| err.to_string())?;
+                    }
+
+                    Ok::<(), String>(())
+                }).await;
+
+                let err = match res {
+                    Ok(Ok(())) => {
+                        status.lock().unwrap().insert(name, ActorStatus::Stopped);
+                        return;
+                    },
+                    Ok(Err(err)) => err,
+                    Err(join_err) => format!("The actor panicked! ({})", join_err)
+                };
+
+                let restart = match policy {
+                    RestartPolicy::Never => false,
+                    RestartPolicy::Limited(max) => restarts < max,
+                    RestartPolicy::Always => true
+                };
+
+                if !restart {
+                    status.lock().unwrap().insert(name, ActorStatus::Failed(err));
+                    return;
+                }
+
+                restarts += 1;
+                status.lock().unwrap().insert(name.clone(), ActorStatus::Running(restarts));
+            }
+        }));
+
+        Addr { tx }
+    }
+
+    /// Returns the status of the actor with the given `name`
+    pub fn status(&self, name : &str) -> Option<ActorStatus> {
+        self.status.lock().unwrap().get(name).cloned()
+    }
+
+    /// Aborts all the actors
+    pub fn shutdown(&mut self) {
+        for handle in self.handles.drain(..) {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        self.shutdown()
+    }
+}
@@ -0,0 +1,262 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Interpreter, Robot, Station, SyError};
+use crate::sender::ProgramStore;
+use crate::stat::{JobRecord, JobResult};
+
+/// Name of the operator recorded for scheduled jobs
+const OPERATOR : &str = "scheduler";
+
+// Time
+    /// A point in time split into its calendar fields (UTC)
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct CalendarTime {
+        /// Minute of the hour
+        pub minute : u8,
+        /// Hour of the day
+        pub hour : u8,
+        /// Day of the month, starting at `1`
+        pub day : u8,
+        /// Month, starting at `1`
+        pub month : u8,
+        /// Day of the week, `0` is sunday
+        pub weekday : u8
+    }
+
+    impl CalendarTime {
+        /// Splits the given time (seconds since the UNIX epoch) into its fields
+        pub fn from_unix(secs : u64) -> Self {
+            let days = (secs / 86400) as i64;
+            let rem = secs % 86400;
+
+            // Civil date from days since the epoch (H. Hinnant)
+            let z = days + 719468;
+            let era = z.div_euclid(146097);
+            let doe = z - era * 146097;
+            let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+            let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+            let mp = (5 * doy + 2) / 153;
+
+            Self {
+                minute: ((rem / 60) % 60) as u8,
+                hour: (rem / 3600) as u8,
+                day: (doy - (153 * mp + 2) / 5 + 1) as u8,
+                month: (if mp < 10 { mp + 3 } else { mp - 9 }) as u8,
+                weekday: ((days + 4).rem_euclid(7)) as u8
+            }
+        }
+    }
+//
+
+// Rules
+    /// A cron-like rule with the fields `minute hour day month weekday`, each field is either `*`, a value, a range
+    /// (`1-5`), a list (`0,30`) or a step (`*/15`). E.g. `0 22 * * 1-5` for 22:00 on every workday
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(try_from = "String", into = "String")]
+    pub struct CronRule {
+        expr : String,
+        fields : [Vec<u8>; 5]
+    }
+
+    impl CronRule {
+        const RANGES : [(u8, u8); 5] = [ (0, 59), (0, 23), (1, 31), (1, 12), (0, 6) ];
+
+        fn parse_field(field : &str, (min, max) : (u8, u8)) -> Result<Vec<u8>, crate::Error> {
+            let mut values = Vec::new();
+
+            for part in field.split(',') {
+                let (range, step) = match part.split_once('/') {
+                    Some((range, step)) => (range, step.parse::<u8>()?),
+                    None => (part, 1)
+                };
+
+                let (start, end) = match range {
+                    "*" => (min, max),
+                    _ => match range.split_once('-') {
+                        Some((a, b)) => (a.parse()?, b.parse()?),
+                        None => { let v = range.parse()?; (v, v) }
+                    }
+                };
+
+                if (start < min) | (end > max) | (start > end) | (step == 0) {
+                    return Err(SyError::config(format!("Invalid cron field '{}'! (Range: {} - {})", field, min, max)).into());
+                }
+
+                values.extend((start ..= end).step_by(step as usize));
+            }
+
+            values.sort_unstable();
+            values.dedup();
+            Ok(values)
+        }
+
+        /// Parses a rule
+        pub fn parse(expr : &str) -> Result<Self, crate::Error> {
+            let parts : Vec<&str> = expr.split_whitespace().collect();
+
+            if parts.len() != 5 {
+                return Err(SyError::config(format!("A cron rule requires 5 fields! (Rule: '{}')", expr)).into());
+            }
+
+            let mut fields : [Vec<u8>; 5] = Default::default();
+            for i in 0 .. 5 {
+                fields[i] = Self::parse_field(parts[i], Self::RANGES[i])
+                    .map_err(|err| SyError::config(format!("Invalid cron rule '{}'! ({})", expr, err)))?;
+            }
+
+            Ok(Self { expr: expr.to_owned(), fields })
+        }
+
+        /// Returns wheiter the rule matches the given time
+        pub fn matches(&self, time : &CalendarTime) -> bool {
+            [ time.minute, time.hour, time.day, time.month, time.weekday ].iter()
+                .zip(&self.fields)
+                .all(|(v, field)| field.contains(v))
+        }
+    }
+
+    impl TryFrom<String> for CronRule {
+        type Error = String;
+
+        fn try_from(expr : String) -> Result<Self, Self::Error> {
+            Self::parse(&expr).map_err(|err| err.to_string())
+        }
+    }
+
+    impl From<CronRule> for String {
+        fn from(rule : CronRule) -> Self {
+            rule.expr
+        }
+    }
+
+    /// When a scheduled job is started
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum ScheduleRule {
+        /// Once at the given time (seconds since the UNIX epoch)
+        At(u64),
+        /// Every minute matching the rule
+        Cron(CronRule)
+    }
+//
+
+/// A stored program started by the `Scheduler`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// Name of the program in the `ProgramStore`
+    pub program : String,
+    /// When the program is started
+    pub rule : ScheduleRule,
+    /// Wheiter the job is active
+    #[serde(default = "default_enabled")]
+    pub enabled : bool,
+
+    /// The minute the job has been started the last time (minutes since the UNIX epoch)
+    #[serde(default)]
+    last : Option<u64>
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ScheduledJob {
+    /// Creates a new enabled job
+    pub fn new<S : Into<String>>(program : S, rule : ScheduleRule) -> Self {
+        Self { program: program.into(), rule, enabled: true, last: None }
+    }
+
+    /// Returns wheiter the job is due at the given time and has not been started in this minute yet
+    pub fn is_due(&self, now : u64) -> bool {
+        let minute = now / 60;
+
+        if !self.enabled | (self.last == Some(minute)) {
+            return false;
+        }
+
+        match &self.rule {
+            ScheduleRule::At(time) => (*time <= now) & self.last.is_none(),
+            ScheduleRule::Cron(rule) => rule.matches(&CalendarTime::from_unix(now))
+        }
+    }
+}
+
+/// Starts stored programs at configured times for unattended runs, call `run_due()` periodically (at least once per
+/// minute). Every job is prechecked before start: the interlock of the station must be satisfied, the emergency stop
+/// must be released and the robot must be homed if required
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Scheduler {
+    /// The jobs scheduled
+    pub jobs : Vec<ScheduledJob>
+}
+
+impl Scheduler {
+    /// Creates a new empty scheduler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a job
+    pub fn add(&mut self, job : ScheduledJob) {
+        self.jobs.push(job);
+    }
+
+    /// Checks the conditions required for an unattended start
+    pub fn precheck<S, R, G, T, const C : usize>(rob : &R, stat : &S) -> Result<(), crate::Error>
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if !stat.interlock() {
+            return Err(SyError::safety("The interlock of the station is not satisfied!").into());
+        }
+
+        if let Some(estop) = rob.estop() {
+            estop.check()?;
+        }
+
+        if rob.vars().require_homing & !rob.vars().homed {
+            return Err(SyError::safety("The robot has to be homed before scheduled jobs!").into());
+        }
+
+        Ok(())
+    }
+
+    /// Runs all jobs due at the given time one after another, returning a record for each of them. Jobs failing the
+    /// precheck are recorded as failed without moving the robot
+    pub fn run_due<I, G, R, D, S, T, O, const C : usize>(&mut self, now : SystemTime, intpr : &I, store : &ProgramStore,
+        rob : &mut R, desc : &mut D, stat : &mut S) -> Vec<JobRecord>
+    where
+        I : Interpreter<G, R, D, S, T, O, C>,
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        D : Descriptor<C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        let secs = now.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let mut records = Vec::new();
+
+        for job in self.jobs.iter_mut().filter(|job| job.is_due(secs)) {
+            job.last = Some(secs / 60);
+
+            let start = SystemTime::now();
+            let res = Self::precheck::<S, R, G, T, C>(rob, stat)
+                .and_then(|_| intpr.run_program(store, &job.program, rob, desc, stat, |_| { }));
+
+            let result = match res {
+                Ok(_) => JobResult::Completed,
+                Err(err) => JobResult::Failed(err.to_string())
+            };
+
+            records.push(JobRecord::finish(&job.program, start, result, Some(OPERATOR.to_owned())));
+        }
+
+        records
+    }
+}
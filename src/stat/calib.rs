@@ -0,0 +1,148 @@
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Robot, Station, SyError};
+
+/// The operator channel, used to prompt the operator during guided workflows
+pub trait Operator {
+    /// Shows a message to the operator
+    fn inform(&mut self, msg : &str) -> Result<(), crate::Error>;
+
+    /// Prompts the operator with a message and waits for a confirmation, returns `false` if the operator cancels
+    fn confirm(&mut self, msg : &str) -> Result<bool, crate::Error>;
+}
+
+/// The steps of a `Calibration`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CalibStep {
+    /// Homing the robot
+    #[default]
+    Home,
+    /// Touching the reference point with the given index
+    TouchReference(usize),
+    /// Verifying the repeatability by touching the first reference point again
+    VerifyRepeatability(usize),
+    /// Writing the offsets back to the angle configurations
+    WriteOffsets,
+    /// Calibration finished
+    Done
+}
+
+/// A step-by-step calibration flow
+/// 
+/// 1. The robot is homed by the station
+/// 2. The operator jogs the robot to each reference point and confirms
+/// 3. The robot is homed again and the first reference point is touched `repeats` times to verify repeatability
+/// 4. The averaged offsets are written back to the angle configurations of the robot
+pub struct Calibration<const C : usize> {
+    /// The expected `Phi` values at each reference point
+    pub references : Vec<[Phi; C]>,
+    /// Number of times the first reference is touched again to verify repeatability
+    pub repeats : usize,
+    /// The maximum spread allowed between repeated touches of a reference point (per axis)
+    pub tolerance : f32,
+
+    measured : Vec<[Phi; C]>,
+    step : CalibStep
+}
+
+impl<const C : usize> Calibration<C> {
+    /// Creates a new calibration flow
+    pub fn new(references : Vec<[Phi; C]>, repeats : usize, tolerance : f32) -> Self {
+        Self {
+            references,
+            repeats,
+            tolerance,
+
+            measured: Vec::new(),
+            step: CalibStep::Home
+        }
+    }
+
+    /// Returns the current step of the calibration
+    pub fn step(&self) -> CalibStep {
+        self.step
+    }
+
+    /// Calculates the offsets (expected - measured) averaged over all reference points
+    pub fn offsets(&self) -> [Delta; C] {
+        let mut offsets = [Delta::ZERO; C];
+        let n = self.references.len().min(self.measured.len());
+
+        for i in 0 .. C {
+            let sum : f32 = (0 .. n).map(|r| self.references[r][i].0 - self.measured[r][i].0).sum();
+            offsets[i] = Delta(sum / n.max(1) as f32);
+        }
+
+        offsets
+    }
+
+    async fn touch<S, G, T>(&mut self, rob : &mut S::Robot, op : &mut dyn Operator, msg : &str) -> Result<[Phi; C], crate::Error> 
+    where
+        S : Station<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if op.confirm(msg)? {
+            Ok(rob.phis())
+        } else {
+            Err(SyError::safety("Calibration cancelled by the operator!").into())
+        }
+    }
+
+    /// Runs the calibration flow, returning the offsets written to the robot
+    pub async fn run<S, G, T>(&mut self, stat : &mut S, rob : &mut S::Robot, op : &mut dyn Operator) -> Result<[Delta; C], crate::Error> 
+    where
+        S : Station<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if self.references.is_empty() {
+            return Err(SyError::config("The calibration requires at least one reference point!").into());
+        }
+
+        self.measured.clear();
+
+        // Home
+        self.step = CalibStep::Home;
+        op.inform("Homing the robot ...")?;
+        stat.home_checked(rob).await?;
+
+        // References
+        for i in 0 .. self.references.len() {
+            self.step = CalibStep::TouchReference(i);
+            let phis = self.touch::<S, G, T>(rob, op, &format!("Jog the robot to reference point {} and confirm", i)).await?;
+            self.measured.push(phis);
+        }
+
+        // Repeatability
+        for r in 0 .. self.repeats {
+            self.step = CalibStep::VerifyRepeatability(r);
+            stat.home_checked(rob).await?;
+
+            let phis = self.touch::<S, G, T>(rob, op, "Jog the robot to reference point 0 again and confirm").await?;
+
+            for i in 0 .. C {
+                let spread = (phis[i].0 - self.measured[0][i].0).abs();
+                if spread > self.tolerance {
+                    return Err(SyError::component(format!("Repeatability check failed for axis {}! (Spread: {}, Tolerance: {})", 
+                        i, spread, self.tolerance)).into());
+                }
+            }
+        }
+
+        // Write back
+        self.step = CalibStep::WriteOffsets;
+        let offsets = self.offsets();
+
+        for (conf, offset) in rob.ang_confs_mut().iter_mut().zip(offsets.iter()) {
+            conf.offset = Delta(conf.offset.0 + offset.0);
+        }
+
+        self.step = CalibStep::Done;
+        op.inform("Calibration done")?;
+
+        Ok(offsets)
+    }
+}
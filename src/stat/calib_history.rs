@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Robot, SyError};
+use crate::rcs::Position;
+
+/// The result of a calibration: offsets, frames and link corrections
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CalibSet {
+    /// The offsets of the angle configurations of the robot
+    pub offsets : Vec<f32>,
+    /// The frames measured, by their path in the world object
+    #[serde(default)]
+    pub frames : HashMap<String, Position>,
+    /// Corrections of the link lengths
+    #[serde(default)]
+    pub links : Vec<f32>
+}
+
+impl CalibSet {
+    /// Captures the offsets currently used by the robot
+    pub fn capture<R, G, T, const C : usize>(rob : &R) -> Self
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        Self {
+            offsets: rob.ang_confs().iter().map(|conf| conf.offset.0).collect(),
+            frames: HashMap::new(),
+            links: Vec::new()
+        }
+    }
+
+    /// Writes the offsets back to the angle configurations of the robot, frames and links have to be applied by the caller
+    pub fn apply<R, G, T, const C : usize>(&self, rob : &mut R) -> Result<(), crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if self.offsets.len() != C {
+            return Err(SyError::config(format!("The calibration set does not match the robot! (Offsets: {}, Axes: {})", 
+                self.offsets.len(), C)).into());
+        }
+
+        for (conf, offset) in rob.ang_confs_mut().iter_mut().zip(&self.offsets) {
+            conf.offset = Delta(*offset);
+        }
+
+        Ok(())
+    }
+}
+
+/// A calibration stored in the `CalibHistory`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CalibEntry {
+    /// Id of the entry, increasing with every calibration
+    pub id : u64,
+    /// Time of the calibration (seconds since the UNIX epoch)
+    pub time : u64,
+    /// Who or what triggered the calibration (operator, scheduled check, rollback ...)
+    pub trigger : String,
+    /// The calibration result
+    pub set : CalibSet
+}
+
+/// Persistent calibration history, stored as a JSON-lines file (one entry per line). The history is append-only,
+/// rollbacks are stored as new entries
+#[derive(Clone, Debug)]
+pub struct CalibHistory {
+    path : PathBuf
+}
+
+impl CalibHistory {
+    /// Opens the history at the given path, the file is created with the first entry
+    pub fn new<P : Into<PathBuf>>(path : P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Loads all the entries of the history in chronological order
+    pub fn load(&self) -> Result<Vec<CalibEntry>, crate::Error> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = std::fs::File::open(&self.path)?;
+        let mut entries = Vec::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Stores a new calibration result, returning the entry created
+    pub fn record<S : Into<String>>(&self, trigger : S, set : CalibSet) -> Result<CalibEntry, crate::Error> {
+        let entry = CalibEntry {
+            id: self.latest()?.map_or(0, |e| e.id + 1),
+            time: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            trigger: trigger.into(),
+            set
+        };
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+        Ok(entry)
+    }
+
+    /// Returns the newest entry
+    pub fn latest(&self) -> Result<Option<CalibEntry>, crate::Error> {
+        Ok(self.load()?.pop())
+    }
+
+    /// Returns the entry with the given id
+    pub fn get(&self, id : u64) -> Result<Option<CalibEntry>, crate::Error> {
+        Ok(self.load()?.into_iter().find(|e| e.id == id))
+    }
+
+    /// Rolls back to the calibration with the given id: the offsets are applied to the robot and the set is stored as
+    /// a new entry. Returns the set, so the caller can apply the frames and link corrections
+    pub fn rollback<R, G, T, const C : usize>(&self, id : u64, rob : &mut R) -> Result<CalibSet, crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        let entry = self.get(id)?.ok_or_else(|| SyError::config(format!("No calibration with id {} in the history!", id)))?;
+
+        entry.set.apply(rob)?;
+        self.record(format!("Rollback to {}", id), entry.set.clone())?;
+
+        Ok(entry.set)
+    }
+}
@@ -0,0 +1,152 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::Poll;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Interpreter, Robot, SyError};
+
+/// Multiple robots of a station with their descriptors, addressed by name or index
+///
+/// Lines for the interpreter can be addressed to a robot with a prefix, either its name (`left: G1 X10`) or its index
+/// (`R1: G1 X10`). Lines without prefix are addressed to the first robot
+pub struct RobotCell<R, D> {
+    names : Vec<String>,
+    units : Vec<(R, D)>
+}
+
+impl<R, D> Default for RobotCell<R, D> {
+    fn default() -> Self {
+        Self { names: Vec::new(), units: Vec::new() }
+    }
+}
+
+impl<R, D> RobotCell<R, D> {
+    /// Creates a new empty cell
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a robot with its descriptor, the names must be unique
+    pub fn add<S : Into<String>>(&mut self, name : S, rob : R, desc : D) -> Result<usize, crate::Error> {
+        let name = name.into();
+
+        if self.index(&name).is_some() {
+            return Err(SyError::config(format!("A robot named '{}' is already part of the cell!", name)).into());
+        }
+
+        self.names.push(name);
+        self.units.push((rob, desc));
+        Ok(self.units.len() - 1)
+    }
+
+    /// Returns the number of robots
+    pub fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    /// Returns wheiter the cell has no robots
+    pub fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// Returns the names of the robots
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the index of the robot with the given address, either its name or `R<index>`
+    pub fn index(&self, addr : &str) -> Option<usize> {
+        if let Some(i) = self.names.iter().position(|n| n == addr) {
+            return Some(i);
+        }
+
+        addr.strip_prefix('R')
+            .and_then(|i| i.parse::<usize>().ok())
+            .filter(|i| *i < self.units.len())
+    }
+
+    /// Returns the robot and descriptor with the given index
+    pub fn unit_mut(&mut self, index : usize) -> Option<(&mut R, &mut D)> {
+        self.units.get_mut(index).map(|(rob, desc)| (rob, desc))
+    }
+
+    /// Returns all the robots and descriptors, e.g. to start movements of all robots for `sync()`
+    pub fn units_mut(&mut self) -> &mut [(R, D)] {
+        &mut self.units
+    }
+
+    /// Splits the address prefix off a line, returning the index of the robot and the rest of the line
+    pub fn split_addr<'a>(&self, line : &'a str) -> Result<(usize, &'a str), crate::Error> {
+        let Some((addr, rest)) = line.split_once(':') else {
+            return Ok((0, line));
+        };
+
+        // Comments may contain colons as well
+        if addr.contains(';') | addr.contains('(') | addr.trim().contains(' ') {
+            return Ok((0, line));
+        }
+
+        let index = self.index(addr.trim())
+            .ok_or_else(|| SyError::interpreter(format!("No robot with the address '{}' in the cell! (Robots: {:?})", 
+                addr.trim(), self.names)))?;
+
+        Ok((index, rest.trim_start()))
+    }
+
+    /// Interprets a line with the robot it is addressed to
+    pub fn interpret<I, G, S, T, O, const C : usize>(&mut self, intpr : &I, stat : &mut S, line : &str)
+        -> Result<Vec<O>, crate::Error>
+    where
+        I : Interpreter<G, R, D, S, T, O, C>,
+        G : SyncActuatorGroup<T, C>,
+        R : Robot<G, T, C>,
+        D : Descriptor<C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if self.units.is_empty() {
+            return Err(SyError::config("The cell has no robots!").into());
+        }
+
+        let (index, code) = self.split_addr(line)?;
+        let (rob, desc) = &mut self.units[index];
+
+        Ok(intpr.interpret(rob, desc, stat, code))
+    }
+}
+
+/// Barrier for movements of multiple robots, drives all the movements concurrently and waits until every one of them
+/// has finished. Returns the first error after all movements have stopped
+pub async fn sync<F>(motions : Vec<F>) -> Result<(), crate::Error>
+where
+    F : Future<Output = Result<(), crate::Error>>
+{
+    let mut motions : Vec<Option<Pin<Box<F>>>> = motions.into_iter().map(|m| Some(Box::pin(m))).collect();
+    let mut error = None;
+
+    core::future::poll_fn(|cx| {
+        for slot in motions.iter_mut() {
+            if let Some(motion) = slot {
+                if let Poll::Ready(res) = motion.as_mut().poll(cx) {
+                    *slot = None;
+
+                    if let Err(err) = res {
+                        error.get_or_insert(err);
+                    }
+                }
+            }
+        }
+
+        if motions.iter().all(Option::is_none) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }).await;
+
+    match error {
+        Some(err) => Err(err),
+        None => Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+
+/// Events that change the production counters of a station
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CounterEvent {
+    /// A part has been started
+    Attempted,
+    /// A part has been completed successfully
+    Completed,
+    /// A part has been rejected
+    Rejected,
+    /// A new batch with the given ID has been started, resets the batch counters
+    Batch(String)
+}
+
+/// Counters of a single batch or shift
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PartCounts {
+    /// Number of parts started
+    pub attempted : u64,
+    /// Number of parts completed successfully
+    pub completed : u64,
+    /// Number of parts rejected
+    pub rejected : u64
+}
+
+impl PartCounts {
+    /// Applies a counter event to the counts, batch events are ignored
+    pub fn count(&mut self, event : &CounterEvent) {
+        match event {
+            CounterEvent::Attempted => self.attempted += 1,
+            CounterEvent::Completed => self.completed += 1,
+            CounterEvent::Rejected => self.rejected += 1,
+            CounterEvent::Batch(_) => { }
+        }
+    }
+
+    /// Returns the ratio of completed parts to all finished parts, `None` if no part has been finished yet
+    pub fn yield_ratio(&self) -> Option<f32> {
+        let finished = self.completed + self.rejected;
+
+        if finished == 0 {
+            None
+        } else {
+            Some(self.completed as f32 / finished as f32)
+        }
+    }
+}
+
+/// Production counters maintained by the station, persisted with the station state
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProductionCounters {
+    /// ID of the current batch, `None` if no batch has been started
+    pub batch_id : Option<String>,
+    /// Counts of the current batch
+    pub batch : PartCounts,
+    /// Counts since the last reset (e.g. the start of the shift)
+    pub total : PartCounts
+}
+
+impl ProductionCounters {
+    /// Creates new empty counters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a counter event, used by programs and scripts
+    pub fn count(&mut self, event : &CounterEvent) {
+        if let CounterEvent::Batch(id) = event {
+            self.start_batch(id.clone());
+        } else {
+            self.batch.count(event);
+            self.total.count(event);
+        }
+    }
+
+    /// Starts a new batch with the given ID, resetting the batch counts
+    pub fn start_batch<S : Into<String>>(&mut self, id : S) {
+        self.batch_id = Some(id.into());
+        self.batch = PartCounts::default();
+    }
+
+    /// Resets all the counters, e.g. at the start of a shift
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the counters as JSON, used for metrics and APIs
+    pub fn get_json(&self) -> serde_json::Value {
+        json!({
+            "batch_id": self.batch_id,
+            "batch": self.batch,
+            "total": self.total,
+            "yield": self.total.yield_ratio()
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use glam::Vec3;
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Robot, SyError};
+use crate::prog::ApproachRetract;
+use crate::rcs::{Point, Position, WorldObj};
+
+/// Compensation of slow drift of a workpiece frame (thermal growth, fixture settling) during long jobs
+///
+/// The frame is re-probed periodically (see `is_due()`), each measurement passed to `update()` moves the frame
+/// towards the measured position by at most `max_step`, so single bad probes cannot cause large jumps. Drift beyond
+/// `max_total` is rejected as an error
+#[derive(Clone, Debug)]
+pub struct DriftCompensation {
+    /// Path of the frame in the world object
+    pub frame : String,
+    /// The nominal position of the frame, as calibrated
+    pub nominal : Vec3,
+    /// The interval between two probes
+    pub interval : Duration,
+    /// The maximum correction applied per update
+    pub max_step : f32,
+    /// The maximum total correction allowed
+    pub max_total : f32,
+
+    offset : Vec3,
+    last : Option<Instant>
+}
+
+impl DriftCompensation {
+    /// Creates a new compensation without any correction, the first probe is due immediately
+    pub fn new<S : Into<String>>(frame : S, nominal : Vec3, interval : Duration, max_step : f32, max_total : f32) -> Self {
+        Self {
+            frame: frame.into(),
+            nominal,
+            interval,
+            max_step,
+            max_total,
+
+            offset: Vec3::ZERO,
+            last: None
+        }
+    }
+
+    /// Returns the current correction
+    pub fn offset(&self) -> Vec3 {
+        self.offset
+    }
+
+    /// Returns wheiter the next probe is due
+    pub fn is_due(&self) -> bool {
+        self.last.map_or(true, |last| last.elapsed() >= self.interval)
+    }
+
+    /// Updates the correction with the measured position of the frame, returns the new correction
+    pub fn update(&mut self, measured : Vec3) -> Result<Vec3, crate::Error> {
+        let drift = measured - self.nominal;
+
+        if drift.length() > self.max_total {
+            return Err(SyError::safety(format!("The drift of frame '{}' exceeds the maximum correction! (Drift: {:?}, Max: {})",
+                self.frame, drift.to_array(), self.max_total)).into());
+        }
+
+        self.offset += (drift - self.offset).clamp_length_max(self.max_step);
+        self.last = Some(Instant::now());
+        Ok(self.offset)
+    }
+
+    /// Probes the frame with the robot: moves to the `contact` pose with the approach of the `strategy`, reads the 
+    /// measured position of the frame with `measure` (e.g. from a touch probe), retracts and updates the correction 
+    /// with the measurement (see `update()`). The robot retracts even if the measurement fails
+    pub async fn probe<R, G, T, D, M, const C : usize>(&mut self, rob : &mut R, desc : &mut D, contact : &Position, 
+        strategy : &ApproachRetract, measure : M) -> Result<Vec3, crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>,
+        M : FnOnce() -> Result<Vec3, crate::Error>
+    {
+        strategy.approach_to(rob, desc, contact).await?;
+        let measured = measure();
+        strategy.retract_from(rob, desc, contact).await?;
+
+        self.update(measured?)
+    }
+
+    /// Corrects a target given in the coordinates of the frame's parent
+    pub fn apply(&self, target : Vec3) -> Vec3 {
+        target + self.offset
+    }
+
+    /// Moves the frame in the world object to its corrected position, so all targets relative to it follow
+    pub fn apply_frame(&self, wobj : &WorldObj) -> Result<(), crate::Error> {
+        let point = wobj.req_point(self.frame.as_str())?;
+        *point.borrow_mut().pos_mut() = self.nominal + self.offset;
+        Ok(())
+    }
+
+    /// Resets the correction, e.g. after a new calibration of the frame
+    pub fn reset(&mut self) {
+        self.offset = Vec3::ZERO;
+        self.last = None;
+    }
+}
@@ -0,0 +1,161 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use glam::{Mat3, Vec3};
+
+use crate::rcs::{Point, Position, WorldObj};
+
+/// The movement of an `ExternalAxis`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExternalKind {
+    /// A linear axis (e.g. a conveyor) moving the frame along the direction given
+    Linear { dir : Vec3 },
+    /// A rotary axis (e.g. a turntable) rotating the frame around the axis given, the value is in radians
+    Rotary { axis : Vec3 }
+}
+
+/// An axis of the station not driven by the robot (e.g. a conveyor or turntable), whose position moves a frame of the
+/// world object. Targets defined relative to that frame follow the workpiece
+pub struct ExternalAxis {
+    /// The movement of the axis
+    pub kind : ExternalKind,
+    /// Path of the frame moved in the world object
+    pub frame : String,
+    /// Pose of the frame at the value `0.0`
+    pub base : Position,
+
+    source : Box<dyn FnMut() -> Result<f32, crate::Error>>,
+    value : f32,
+    velocity : f32,
+    last : Option<Instant>
+}
+
+impl ExternalAxis {
+    /// Creates a new external axis, `source` reads the current value of the axis (e.g. an encoder)
+    pub fn new<S, F>(kind : ExternalKind, frame : S, base : Position, source : F) -> Self
+    where
+        S : Into<String>,
+        F : FnMut() -> Result<f32, crate::Error> + 'static
+    {
+        Self {
+            kind,
+            frame: frame.into(),
+            base,
+
+            source: Box::new(source),
+            value: 0.0,
+            velocity: 0.0,
+            last: None
+        }
+    }
+
+    /// Returns the value of the last reading
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns the velocity estimated from the last two readings
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    /// Reads the current value of the axis, updating the velocity estimate
+    pub fn read(&mut self) -> Result<f32, crate::Error> {
+        let value = (self.source)()?;
+        let now = Instant::now();
+
+        if let Some(last) = self.last {
+            let dt = now.duration_since(last).as_secs_f32();
+
+            if dt > 0.0 {
+                self.velocity = (value - self.value) / dt;
+            }
+        }
+
+        self.value = value;
+        self.last = Some(now);
+        Ok(value)
+    }
+
+    /// Returns the value predicted `dt` after the last reading, assuming a constant velocity
+    pub fn predict(&self, dt : Duration) -> f32 {
+        self.value + self.velocity * dt.as_secs_f32()
+    }
+
+    /// Returns the pose of the frame at the given value
+    pub fn pose_at(&self, value : f32) -> Position {
+        pose_at(self.kind, &self.base, value)
+    }
+
+    /// Moves the frame in the world object to the pose at the given value
+    pub fn set_frame(&self, wobj : &WorldObj, value : f32) -> Result<(), crate::Error> {
+        let point = wobj.req_point(self.frame.as_str())?;
+        let pose = self.pose_at(value);
+        let mut point = point.borrow_mut();
+
+        *point.pos_mut() = *pose.pos();
+        *point.ori_mut() = *pose.ori();
+        Ok(())
+    }
+
+    /// Reads the axis and moves the frame in the world object accordingly
+    pub fn update(&mut self, wobj : &WorldObj) -> Result<(), crate::Error> {
+        let value = self.read()?;
+        self.set_frame(wobj, value)
+    }
+
+    /// Returns a snapshot of the axis at the last reading, tracked by the robot to resolve targets given relative to 
+    /// the frame while it moves (see `Vars::tracking`)
+    pub fn tracking(&self) -> FrameTracking {
+        FrameTracking {
+            frame: self.frame.clone(),
+            kind: self.kind,
+            base: self.base.clone(),
+            value: self.value,
+            velocity: self.velocity,
+            read: self.last.unwrap_or_else(Instant::now)
+        }
+    }
+}
+
+/// Snapshot of an `ExternalAxis` tracked by the robot, the pose of the frame is predicted from the last reading 
+/// assuming a constant velocity
+/// 
+/// While a frame is tracked, the cartesian targets of the robot are given relative to it and resolved at the time 
+/// they are reached, so the TCP follows the workpiece during the movement (see `Robot::phis_for_tracked()`)
+#[derive(Clone, Debug)]
+pub struct FrameTracking {
+    /// Path of the moving frame in the world object
+    pub frame : String,
+
+    kind : ExternalKind,
+    base : Position,
+    value : f32,
+    velocity : f32,
+    read : Instant
+}
+
+impl FrameTracking {
+    /// Returns the pose of the frame predicted `dt` seconds from now
+    pub fn pose_in(&self, dt : f32) -> Position {
+        let value = self.value + self.velocity * (self.read.elapsed().as_secs_f32() + dt);
+        pose_at(self.kind, &self.base, value)
+    }
+
+    /// Converts a pose given relative to the frame into the global system, `dt` seconds from now
+    pub fn track(&self, local : &Position, dt : f32) -> Position {
+        self.pose_in(dt).compose(local)
+    }
+
+    /// Converts a global pose into the frame at the current time
+    pub fn untrack(&self, global : &Position) -> Position {
+        self.pose_in(0.0).inverse().compose(global)
+    }
+}
+
+fn pose_at(kind : ExternalKind, base : &Position, value : f32) -> Position {
+    match kind {
+        ExternalKind::Linear { dir } => Position::new_ori(*base.pos() + dir.normalize() * value, *base.ori()),
+        ExternalKind::Rotary { axis } => Position::new_ori(*base.pos(), *base.ori() * Mat3::from_axis_angle(axis.normalize(), value))
+    }
+}
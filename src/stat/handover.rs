@@ -0,0 +1,134 @@
+use core::future::Future;
+use core::time::Duration;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+use tokio::time::timeout;
+
+use crate::{ErrorKind, Robot, SyError};
+
+/// The states of a `Handover`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HandoverState {
+    /// Not started yet
+    #[default]
+    Idle,
+    /// Both robots move to their handover poses
+    Approaching,
+    /// The receiving robot grips the part, the giving robot still holds it
+    Gripping,
+    /// The giving robot releases the part
+    Releasing,
+    /// Handover completed, the receiving robot holds the part
+    Done,
+    /// Handover failed in the given state, the part is held by the giver (or by both robots if failed after gripping)
+    Failed(HandoverStep)
+}
+
+/// The steps of a handover that can fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandoverStep {
+    /// Moving to the handover poses
+    Approach,
+    /// Gripping by the receiver
+    Grip,
+    /// Releasing by the giver
+    Release
+}
+
+/// Coordinated handover of a part between two robots of a station
+/// 
+/// The state machine is interlocked: the giver only releases after the grip of the receiver has been confirmed, every 
+/// step is limited by the timeout given
+#[derive(Clone, Debug)]
+pub struct Handover<const A : usize, const B : usize> {
+    /// Handover pose of the giving robot
+    pub giver_pose : [Phi; A],
+    /// Handover pose of the receiving robot, matched with the giver pose
+    pub receiver_pose : [Phi; B],
+    /// Speed factor of the approach
+    pub speed_f : Factor,
+    /// Maximum time each step may take
+    pub timeout : Duration,
+
+    state : HandoverState
+}
+
+impl<const A : usize, const B : usize> Handover<A, B> {
+    /// Creates a new handover between the two poses
+    pub fn new(giver_pose : [Phi; A], receiver_pose : [Phi; B], speed_f : Factor, timeout : Duration) -> Self {
+        Self {
+            giver_pose,
+            receiver_pose,
+            speed_f,
+            timeout,
+
+            state: HandoverState::Idle
+        }
+    }
+
+    /// Returns the current state of the handover
+    pub fn state(&self) -> HandoverState {
+        self.state
+    }
+
+    fn fail(&mut self, step : HandoverStep, err : crate::Error) -> crate::Error {
+        self.state = HandoverState::Failed(step);
+        SyError::new(ErrorKind::of(&err), format!("Handover failed at step {:?}! ({})", step, err)).into()
+    }
+
+    async fn step<F : Future<Output = Result<(), crate::Error>>>(&mut self, step : HandoverStep, fut : F) -> Result<(), crate::Error> {
+        match timeout(self.timeout, fut).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(err)) => Err(self.fail(step, err)),
+            Err(_) => Err(self.fail(step, SyError::safety("Timeout").into()))
+        }
+    }
+
+    /// Runs the handover. The tools of both robots are used as grippers, `grip_confirmed` is polled after the receiver 
+    /// has activated its tool and must return `true` once the part is held (e.g. by reading a gripper sensor)
+    pub async fn run<RA, GA, TA, RB, GB, TB, F>(&mut self, giver : &mut RA, receiver : &mut RB, mut grip_confirmed : F) 
+    -> Result<(), crate::Error> 
+    where
+        RA : Robot<GA, TA, A>,
+        GA : SyncActuatorGroup<TA, A>,
+        TA : SyncActuator + DefinedActuator + ?Sized + 'static,
+        RB : Robot<GB, TB, B>,
+        GB : SyncActuatorGroup<TB, B>,
+        TB : SyncActuator + DefinedActuator + ?Sized + 'static,
+        F : FnMut() -> bool
+    {
+        let (giver_pose, receiver_pose, speed_f) = (self.giver_pose, self.receiver_pose, self.speed_f);
+
+        self.state = HandoverState::Approaching;
+        self.step(HandoverStep::Approach, async {
+            let (res_a, res_b) = tokio::join!(
+                giver.move_abs_j(giver_pose, speed_f), 
+                receiver.move_abs_j(receiver_pose, speed_f)
+            );
+            res_a?;
+            res_b
+        }).await?;
+
+        self.state = HandoverState::Gripping;
+        self.step(HandoverStep::Grip, async {
+            receiver.activate_tool()?;
+
+            while !grip_confirmed() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+
+            Ok(())
+        }).await?;
+
+        self.state = HandoverState::Releasing;
+        self.step(HandoverStep::Release, async {
+            giver.deactivate_tool()?;
+            Ok(())
+        }).await?;
+
+        self.state = HandoverState::Done;
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+/// The result of a job
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum JobResult {
+    /// The job has been completed successfully
+    Completed,
+    /// The job has been cancelled
+    Cancelled,
+    /// The job failed with the given error
+    Failed(String)
+}
+
+/// A record of a completed job
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobRecord {
+    /// Name of the program run
+    pub program : String,
+    /// Start time (seconds since the UNIX epoch)
+    pub start : u64,
+    /// End time (seconds since the UNIX epoch)
+    pub end : u64,
+    /// Result of the job
+    pub result : JobResult,
+    /// Cycle time in seconds
+    pub cycle_time : f32,
+    /// The operator that started the job
+    pub operator : Option<String>,
+    /// Diagnostics attached to the job, e.g. the report of a simulation run before
+    #[serde(default)]
+    pub diagnostics : Vec<String>
+}
+
+impl JobRecord {
+    /// Creates a new record for a job started at `start` that ends now
+    pub fn finish(program : &str, start : SystemTime, result : JobResult, operator : Option<String>) -> Self {
+        let end = SystemTime::now();
+
+        Self {
+            program: program.to_owned(),
+            start: unix_secs(start),
+            end: unix_secs(end),
+            result,
+            cycle_time: end.duration_since(start).map(|d| d.as_secs_f32()).unwrap_or(0.0),
+            operator,
+            diagnostics: Vec::new()
+        }
+    }
+}
+
+fn unix_secs(time : SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Filter for querying a `JobHistory`, `None` values match all records
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct JobQuery {
+    /// Only records of this program
+    pub program : Option<String>,
+    /// Only records started after this time (seconds since the UNIX epoch)
+    pub since : Option<u64>,
+    /// Only failed or cancelled jobs
+    pub failed_only : bool,
+    /// Maximum number of records returned (the newest ones)
+    pub limit : Option<usize>
+}
+
+impl JobQuery {
+    /// Checks if the given record matches the query
+    pub fn matches(&self, record : &JobRecord) -> bool {
+        self.program.as_ref().map_or(true, |p| *p == record.program)
+            & self.since.map_or(true, |s| record.start >= s)
+            & (!self.failed_only | (record.result != JobResult::Completed))
+    }
+}
+
+/// Persistent job history, stored as a JSON-lines file (one record per line)
+#[derive(Clone, Debug)]
+pub struct JobHistory {
+    path : PathBuf
+}
+
+impl JobHistory {
+    /// Opens the history at the given path, the file is created with the first record
+    pub fn new<P : Into<PathBuf>>(path : P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Appends a record to the history
+    pub fn record(&self, record : &JobRecord) -> Result<(), crate::Error> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    /// Loads all the records of the history
+    pub fn load(&self) -> Result<Vec<JobRecord>, crate::Error> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = std::fs::File::open(&self.path)?;
+        let mut records = Vec::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                records.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Queries the history, returning the matching records in chronological order
+    pub fn query(&self, query : &JobQuery) -> Result<Vec<JobRecord>, crate::Error> {
+        let mut records : Vec<JobRecord> = self.load()?.into_iter()
+            .filter(|r| query.matches(r))
+            .collect();
+
+        if let Some(limit) = query.limit {
+            let skip = records.len().saturating_sub(limit);
+            records.drain(.. skip);
+        }
+
+        Ok(records)
+    }
+}
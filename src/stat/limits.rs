@@ -0,0 +1,183 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::time::Duration;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Robot, SyError};
+use crate::catalog::ERR_LIMIT_SWITCH;
+
+/// A movement of the robot guarded by `LimitMonitor::guard_move()`
+pub type GuardedMove<'a> = Pin<Box<dyn Future<Output = Result<(), crate::Error>> + 'a>>;
+
+/// Reaction to a limit switch triggering during motion
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LimitReaction {
+    /// Only report the trigger, the movement continues
+    Report,
+    /// Stop the movement, further movements are allowed
+    #[default]
+    Stop,
+    /// Stop the movement and halt the axis of the switch, the axis is masked (see `Robot::set_masked()`) so only the
+    /// other axes can be moved until it is unmasked again. Switches without axis stop the whole robot
+    StopAxis,
+    /// Stop the movement and enter the error state, further movements are rejected until `LimitMonitor::reset()`
+    Error
+}
+
+/// A limit switch or endstop monitored during motion
+#[derive(Clone, Debug)]
+pub struct LimitSwitch {
+    /// Name of the station input the switch is connected to
+    pub input : String,
+    /// The axis the switch belongs to, `None` for switches of the station (e.g. door switches)
+    pub axis : Option<usize>,
+    /// The reaction when the switch triggers
+    pub reaction : LimitReaction
+}
+
+impl LimitSwitch {
+    /// Creates a new limit switch
+    pub fn new<S : Into<String>>(input : S, axis : Option<usize>, reaction : LimitReaction) -> Self {
+        Self { input: input.into(), axis, reaction }
+    }
+}
+
+/// Monitors limit switches continuously while the robot moves
+#[derive(Clone, Debug)]
+pub struct LimitMonitor {
+    /// The switches monitored
+    pub switches : Vec<LimitSwitch>,
+    /// The interval the switches are polled in
+    pub poll : Duration,
+
+    triggered : Vec<usize>,
+    error : bool
+}
+
+impl LimitMonitor {
+    /// Creates a new monitor
+    pub fn new(switches : Vec<LimitSwitch>, poll : Duration) -> Self {
+        Self {
+            switches,
+            poll,
+
+            triggered: Vec::new(),
+            error: false
+        }
+    }
+
+    /// Returns the switches that have triggered during the last guarded movement
+    pub fn triggered(&self) -> Vec<&LimitSwitch> {
+        self.triggered.iter().map(|i| &self.switches[*i]).collect()
+    }
+
+    /// Returns wheiter the monitor is in the error state
+    pub fn is_error(&self) -> bool {
+        self.error
+    }
+
+    /// Leaves the error state, should be called after the cause has been resolved
+    pub fn reset(&mut self) {
+        self.error = false;
+        self.triggered.clear();
+    }
+
+    fn error(switch : &LimitSwitch) -> crate::Error {
+        let msg = match switch.axis {
+            Some(axis) => format!("Limit switch '{}' of axis {} triggered!", switch.input, axis),
+            None => format!("Limit switch '{}' triggered!", switch.input)
+        };
+
+        SyError::safety(msg).with_detail(ERR_LIMIT_SWITCH).with_param("input", &switch.input).into()
+    }
+
+    /// Runs the movement `fut` while polling the switches using `input` (e.g. `Station::input`). The movement is 
+    /// dropped if a switch with a `Stop`, `StopAxis` or `Error` reaction triggers, the components have to be halted 
+    /// afterwards with `halt()`, see `guard_move()`
+    pub async fn guard<T, F, I>(&mut self, mut input : I, fut : F) -> Result<T, crate::Error> 
+    where
+        F : Future<Output = Result<T, crate::Error>>,
+        I : FnMut(&str) -> Result<bool, crate::Error>
+    {
+        if self.error {
+            return Err(SyError::safety("A limit switch has triggered, the monitor has to be reset!").into());
+        }
+
+        self.triggered.clear();
+        tokio::pin!(fut);
+
+        let mut interval = tokio::time::interval(self.poll);
+
+        loop {
+            tokio::select! {
+                res = &mut fut => return res,
+                _ = interval.tick() => {
+                    for (i, switch) in self.switches.iter().enumerate() {
+                        if !input(&switch.input)? | self.triggered.contains(&i) {
+                            continue;
+                        }
+
+                        self.triggered.push(i);
+
+                        match switch.reaction {
+                            LimitReaction::Report => { },
+                            LimitReaction::Stop | LimitReaction::StopAxis => return Err(Self::error(switch)),
+                            LimitReaction::Error => {
+                                self.error = true;
+                                return Err(Self::error(switch));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Halts the robot after a guarded movement has been aborted by the switches triggered: `StopAxis` switches halt 
+    /// and mask their axis (see `Robot::halt_axis()`), `Stop` and `Error` switches halt all the components (see 
+    /// `Robot::halt()`). The positions of the robot are updated afterwards
+    pub fn halt<R, G, T, const C : usize>(&self, rob : &mut R) -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        for switch in self.triggered() {
+            match (switch.reaction, switch.axis) {
+                (LimitReaction::Report, _) => { },
+                (LimitReaction::StopAxis, Some(axis)) => {
+                    rob.halt_axis(axis)?;
+                    rob.set_masked(axis, true)?;
+                },
+                _ => rob.halt()?
+            }
+        }
+
+        rob.update()
+    }
+
+    /// Runs the movement created by `mv` like `guard()` and halts the robot explicitly (see `halt()`) if a switch 
+    /// has aborted it, e.g. `monitor.guard_move(&mut rob, input, |rob| Box::pin(rob.move_j(deltas, speed_f)))`
+    pub async fn guard_move<R, G, T, I, M, const C : usize>(&mut self, rob : &mut R, input : I, mv : M) -> Result<(), crate::Error>
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        I : FnMut(&str) -> Result<bool, crate::Error>,
+        M : for<'a> FnOnce(&'a mut R) -> GuardedMove<'a>
+    {
+        let res = self.guard(input, mv(rob)).await;
+
+        if let Err(err) = &res {
+            if !self.triggered.is_empty() {
+                if let Err(halt_err) = self.halt(rob) {
+                    return Err(SyError::safety(format!("{} (Halting failed: {})", err, halt_err)).into());
+                }
+            }
+        }
+
+        res
+    }
+}
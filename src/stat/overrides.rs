@@ -0,0 +1,59 @@
+use core::time::Duration;
+
+use crate::robs::SpeedOverride;
+
+/// Maps an analog station input (e.g. a potentiometer or encoder on the cell) to a `SpeedOverride`
+#[derive(Clone, Debug)]
+pub struct OverrideInput {
+    /// Name of the analog input
+    pub input : String,
+    /// Raw input value mapped to the override factor `0.0`
+    pub raw_min : f32,
+    /// Raw input value mapped to the maximum override factor
+    pub raw_max : f32,
+    /// The maximum override factor reachable with the input
+    pub factor_max : f32,
+    /// Changes of the factor smaller than this are ignored, to filter noise of the input
+    pub deadband : f32
+}
+
+impl OverrideInput {
+    /// Creates a new mapping of the raw range to the factors `0.0` to `factor_max`
+    pub fn new<S : Into<String>>(input : S, raw_min : f32, raw_max : f32, factor_max : f32) -> Self {
+        Self {
+            input: input.into(),
+            raw_min,
+            raw_max,
+            factor_max: factor_max.clamp(0.0, SpeedOverride::MAX),
+            deadband: 0.01
+        }
+    }
+
+    /// Converts a raw input value into an override factor
+    pub fn factor(&self, raw : f32) -> f32 {
+        let range = self.raw_max - self.raw_min;
+        if range == 0.0 {
+            return 0.0;
+        }
+
+        ((raw - self.raw_min) / range).clamp(0.0, 1.0) * self.factor_max
+    }
+
+    /// Samples the input continuously with the given `period` using `read` (e.g. `Station::analog_input`) and applies 
+    /// it to the override. Runs until reading the input fails
+    pub async fn follow<F>(&self, ovr : &SpeedOverride, period : Duration, mut read : F) -> Result<(), crate::Error>
+    where
+        F : FnMut(&str) -> Result<f32, crate::Error>
+    {
+        let mut interval = tokio::time::interval(period);
+
+        loop {
+            interval.tick().await;
+
+            let factor = self.factor(read(&self.input)?);
+            if (factor - ovr.get()).abs() >= self.deadband {
+                ovr.set(factor)?;
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Robot, SyError};
+use crate::prog::{Args, Program, ProgramTemplate};
+
+/// A named unit combining a program with its tool requirements, frame selections and parameter presets
+#[derive(Clone)]
+pub struct Recipe<const C : usize> {
+    /// Name of the recipe
+    pub name : String,
+    /// The program run by the recipe
+    pub program : ProgramTemplate<C>,
+    /// Tool ids required by the recipe
+    pub tools : Vec<usize>,
+    /// Paths of the frames (points in the world object) the recipe relies on
+    pub frames : Vec<String>,
+    /// Parameter presets, can be overwritten at start
+    pub presets : Args
+}
+
+impl<const C : usize> Recipe<C> {
+    /// Creates a new recipe without requirements or presets
+    pub fn new<S : Into<String>>(name : S, program : ProgramTemplate<C>) -> Self {
+        Self {
+            name: name.into(),
+            program,
+            tools: Vec::new(),
+            frames: Vec::new(),
+            presets: Args::new()
+        }
+    }
+
+    /// Adds a required tool
+    pub fn tool(mut self, tool_id : usize) -> Self {
+        self.tools.push(tool_id);
+        self
+    }
+
+    /// Adds a required frame
+    pub fn frame<S : Into<String>>(mut self, path : S) -> Self {
+        self.frames.push(path.into());
+        self
+    }
+
+    /// Sets the parameter presets
+    pub fn presets(mut self, presets : Args) -> Self {
+        self.presets = presets;
+        self
+    }
+
+    /// Merges the presets with the given `overrides` and instantiates the program
+    pub fn instantiate(&self, overrides : &Args) -> Result<Program<C>, crate::Error> {
+        let mut args = self.presets.clone();
+        args.values.extend(overrides.values.iter().map(|(k, v)| (k.clone(), *v)));
+
+        self.program.instantiate(&args)
+    }
+
+    /// Validates the recipe before start: all the tools required must be present, all the frames must exist and the 
+    /// program generated must be valid. Returns the program ready to run
+    pub fn prepare<R, G, T, D>(&self, rob : &R, desc : &D, overrides : &Args) -> Result<Program<C>, crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        D : Descriptor<C>
+    {
+        for tool_id in &self.tools {
+            if *tool_id >= rob.get_tools().len() {
+                return Err(SyError::config(format!("Recipe '{}' requires tool {}, which is not present! (Tools: {})", 
+                    self.name, tool_id, rob.get_tools().len())).into());
+            }
+        }
+
+        for frame in &self.frames {
+            if desc.world_obj().point(frame.as_str()).is_none() {
+                return Err(SyError::config(format!("Recipe '{}' requires the frame '{}', which does not exist!", 
+                    self.name, frame)).into());
+            }
+        }
+
+        let prog = self.instantiate(overrides)?;
+        prog.validate(rob, desc).map_err(|(i, err)| format!("Step {} of recipe '{}' is invalid! ({})", i, self.name, err))?;
+
+        Ok(prog)
+    }
+}
+
+/// A collection of recipes with one of them selected, stored by the station
+#[derive(Clone, Default)]
+pub struct RecipeBook<const C : usize> {
+    recipes : HashMap<String, Recipe<C>>,
+    selected : Option<String>
+}
+
+impl<const C : usize> RecipeBook<C> {
+    /// Creates a new empty recipe book
+    pub fn new() -> Self {
+        Self {
+            recipes: HashMap::new(),
+            selected: None
+        }
+    }
+
+    /// Adds a recipe, replacing any recipe with the same name
+    pub fn add(&mut self, recipe : Recipe<C>) {
+        self.recipes.insert(recipe.name.clone(), recipe);
+    }
+
+    /// Returns the recipe with the given name
+    pub fn get(&self, name : &str) -> Option<&Recipe<C>> {
+        self.recipes.get(name)
+    }
+
+    /// Returns the names of all recipes, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names : Vec<&str> = self.recipes.keys().map(|n| n.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    /// Selects a recipe by name
+    pub fn select(&mut self, name : &str) -> Result<&Recipe<C>, crate::Error> {
+        let recipe = self.recipes.get(name).ok_or_else(|| SyError::config(format!("No recipe named '{}'!", name)))?;
+        self.selected = Some(name.to_owned());
+        Ok(recipe)
+    }
+
+    /// Returns the recipe selected
+    pub fn selected(&self) -> Option<&Recipe<C>> {
+        self.selected.as_ref().and_then(|name| self.recipes.get(name))
+    }
+}
@@ -0,0 +1,174 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Robot, Station, SyError};
+use crate::events::StateEvent;
+
+/// A reference switch re-touched by a `StepCheck`, e.g. the measurement switch of an axis
+#[derive(Clone, Debug)]
+pub struct RefSwitch {
+    /// Name of the station input the switch is connected to
+    pub input : String,
+    /// The axis moved against the switch
+    pub axis : usize,
+    /// The `Gamma` value the switch is expected to trigger at
+    pub expected : Gamma,
+    /// The distance driven between two polls of the switch, the sign gives the direction towards the switch
+    pub step : Delta,
+    /// The maximum distance searched for the switch
+    pub search : f32,
+    /// The maximum deviation between the expected and the actual trigger `Gamma` allowed
+    pub tolerance : f32
+}
+
+/// The result of re-touching a `RefSwitch`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepCheckResult {
+    /// The axis checked
+    pub axis : usize,
+    /// The `Gamma` value the switch was expected to trigger at
+    pub expected : Gamma,
+    /// The `Gamma` value the switch actually triggered at
+    pub actual : Gamma,
+    /// Wheiter the deviation is within the tolerance of the switch
+    pub ok : bool
+}
+
+impl StepCheckResult {
+    /// The accumulated step loss, `actual - expected`
+    pub fn deviation(&self) -> f32 {
+        self.actual.0 - self.expected.0
+    }
+}
+
+/// Periodic verification of open-loop axes, detecting accumulated step loss during long jobs by re-touching reference
+/// switches and comparing the `Gamma` values they trigger at with the expected ones
+#[derive(Clone, Debug)]
+pub struct StepCheck {
+    /// The switches touched
+    pub switches : Vec<RefSwitch>,
+    /// The interval between two checks
+    pub interval : Duration,
+
+    last : Option<Instant>,
+    results : Vec<StepCheckResult>
+}
+
+impl StepCheck {
+    /// Creates a new check, the first check is due immediately
+    pub fn new(switches : Vec<RefSwitch>, interval : Duration) -> Self {
+        Self {
+            switches,
+            interval,
+
+            last: None,
+            results: Vec::new()
+        }
+    }
+
+    /// Returns wheiter the next check is due
+    pub fn is_due(&self) -> bool {
+        self.last.map_or(true, |last| last.elapsed() >= self.interval)
+    }
+
+    /// Returns the results of the last check
+    pub fn results(&self) -> &[StepCheckResult] {
+        &self.results
+    }
+
+    /// Drives the axis of the switch towards it until it triggers, returns to the start afterwards
+    async fn touch<S, R, G, T, const C : usize>(switch : &RefSwitch, rob : &mut R, stat : &mut S, speed_f : Factor)
+        -> Result<Gamma, crate::Error>
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if (switch.axis >= C) | (switch.step.0 == 0.0) {
+            return Err(SyError::config(format!("Invalid reference switch '{}'! (Axis: {}, Step: {})",
+                switch.input, switch.axis, switch.step.0)).into());
+        }
+
+        let start = rob.gammas();
+        let mut deltas = [Delta::ZERO; C];
+        deltas[switch.axis] = switch.step;
+
+        let mut actual = None;
+
+        while (rob.gammas()[switch.axis].0 - start[switch.axis].0).abs() < switch.search {
+            if stat.input(&switch.input)? {
+                actual = Some(rob.gammas()[switch.axis]);
+                break;
+            }
+
+            rob.drive_j(deltas, speed_f).await?;
+        }
+
+        rob.drive_j(sub_unit_arrays(start, rob.gammas()), speed_f).await?;
+
+        actual.ok_or_else(|| SyError::safety(format!("Reference switch '{}' not found within {}!",
+            switch.input, switch.search)).into())
+    }
+
+    /// Touches all the switches, publishing a `StateEvent::StepLoss` for every axis outside of the tolerance. The
+    /// robot is returned to its position after each switch
+    pub async fn run<S, R, G, T, const C : usize>(&mut self, rob : &mut R, stat : &mut S, speed_f : Factor)
+        -> Result<&[StepCheckResult], crate::Error>
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        self.results.clear();
+
+        for switch in &self.switches {
+            let actual = Self::touch(switch, rob, stat, speed_f).await?;
+
+            let res = StepCheckResult {
+                axis: switch.axis,
+                expected: switch.expected,
+                actual,
+                ok: (actual.0 - switch.expected.0).abs() <= switch.tolerance
+            };
+
+            if !res.ok {
+                rob.events().publish(StateEvent::StepLoss { axis: res.axis, deviation: res.deviation() });
+            }
+
+            self.results.push(res);
+        }
+
+        self.last = Some(Instant::now());
+        Ok(&self.results)
+    }
+
+    /// Runs the check if it is due, returns an error if step loss has been detected on any axis
+    pub async fn run_if_due<S, R, G, T, const C : usize>(&mut self, rob : &mut R, stat : &mut S, speed_f : Factor)
+        -> Result<(), crate::Error>
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        if !self.is_due() {
+            return Ok(());
+        }
+
+        let failed : Vec<String> = self.run(rob, stat, speed_f).await?.iter().filter(|r| !r.ok)
+            .map(|r| format!("axis {}: {}", r.axis, r.deviation()))
+            .collect();
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(SyError::safety(format!("Step loss detected! ({})", failed.join(", "))).into())
+        }
+    }
+}
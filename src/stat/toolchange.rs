@@ -0,0 +1,240 @@
+use glam::Vec3;
+use serde::{Serialize, Deserialize};
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Descriptor, PushMsg, Robot, Station, SyError};
+use crate::rcs::{Point, Position};
+
+/// A slot of the tool rack holding a single tool
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RackSlot {
+    /// Id of the tool stored in the slot
+    pub tool_id : usize,
+    /// Position of the flange (TCP without tool) when the tool is coupled in the slot
+    pub pos : [f32; 3],
+    /// Offset of the approach position to `pos`, the slot is entered and left along this offset
+    pub approach : [f32; 3]
+}
+
+impl RackSlot {
+    /// Returns the approach position of the slot
+    pub fn approach_pos(&self) -> Vec3 {
+        Vec3::from(self.pos) + Vec3::from(self.approach)
+    }
+}
+
+/// The tool rack of a station used for automatic tool changes, see `change_tool()`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ToolRack {
+    /// The slots of the rack
+    pub slots : Vec<RackSlot>,
+    /// Name of the station output actuating the tool coupling, `true` locks the tool
+    pub coupling : String,
+    /// Position of the TCP relative to the last segment without any tool, the vector of the tool is added on top
+    pub flange : [f32; 3],
+    /// TCP speed of the linear moves entering and leaving a slot
+    #[serde(default = "ToolRack::default_slot_speed")]
+    pub slot_speed : f32,
+    /// Maximum distance between the waypoints of the linear moves entering and leaving a slot
+    #[serde(default = "ToolRack::default_slot_accuracy")]
+    pub slot_accuracy : f32
+}
+
+impl Default for ToolRack {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            coupling: String::new(),
+            flange: [ 0.0; 3 ],
+            slot_speed: Self::default_slot_speed(),
+            slot_accuracy: Self::default_slot_accuracy()
+        }
+    }
+}
+
+impl ToolRack {
+    fn default_slot_speed() -> f32 {
+        20.0
+    }
+
+    fn default_slot_accuracy() -> f32 {
+        1.0
+    }
+
+    /// Returns the slot storing the given tool
+    pub fn slot(&self, tool_id : usize) -> Option<&RackSlot> {
+        self.slots.iter().find(|slot| slot.tool_id == tool_id)
+    }
+
+    /// Returns the slot storing the given tool or an error if the rack has none
+    pub fn req_slot(&self, tool_id : usize) -> Result<&RackSlot, crate::Error> {
+        self.slot(tool_id).ok_or_else(|| SyError::config(format!("The tool rack has no slot for tool {}!", tool_id)).into())
+    }
+}
+
+/// Moves the robot in joint space until the flange reaches the given position, used for travelling to the approach 
+/// position of a slot
+async fn move_to<R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, pos : Vec3, speed_f : Factor)
+    -> Result<(), crate::Error>
+where
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    let phis = desc.phis_for_pos(Position::from_vec3(pos))?;
+    rob.valid_phis(&phis)?;
+    rob.move_abs_j(phis, speed_f).await?;
+    desc.update(rob, &phis)
+}
+
+/// Moves the TCP linearly by the given distance, keeping its orientation, so slots are entered and left along their 
+/// approach offset (see `ToolRack::slot_speed`)
+async fn move_slot<R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, rack : &ToolRack, distance : Vec3)
+    -> Result<(), crate::Error>
+where
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    rob.move_l(desc, distance, rack.slot_accuracy, Velocity(rack.slot_speed)).await
+}
+
+/// Sets the TCP of the descriptor to the flange of the rack plus the vector of the given tool
+fn set_tcp<D : Descriptor<C>, const C : usize>(desc : &mut D, rack : &ToolRack, tool_vec : Vec3) {
+    *desc.tcp().borrow_mut().pos_mut() = Vec3::from(rack.flange) + tool_vec;
+}
+
+/// Moves the flange into the slot, releases the coupling and leaves the slot along its approach offset
+async fn release<S, R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, stat : &mut S, rack : &ToolRack, 
+    slot : &RackSlot) -> Result<(), crate::Error>
+where
+    S : Station<G, T, C, Robot = R>,
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    // Slot positions are given for the flange
+    set_tcp(desc, rack, Vec3::ZERO);
+
+    let distance = Vec3::from(slot.pos) - *rob.tcp_pose(desc).pos();
+    move_slot(rob, desc, rack, distance).await?;
+    stat.set_output(&rack.coupling, false)?;
+
+    rob.set_tool_id(None);
+    move_slot(rob, desc, rack, Vec3::from(slot.approach)).await
+}
+
+/// Enters the slot from its approach position, locks the coupling, leaves the slot with the tool and verifies it
+async fn pick_up<S, R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, stat : &mut S, rack : &ToolRack, 
+    slot : &RackSlot) -> Result<(), crate::Error>
+where
+    S : Station<G, T, C, Robot = R>,
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    move_slot(rob, desc, rack, -Vec3::from(slot.approach)).await?;
+    stat.set_output(&rack.coupling, true)?;
+
+    let tool_vec = rob.set_tool_id(Some(slot.tool_id))
+        .map(|tool| tool.vec())
+        .ok_or_else(|| SyError::config(format!("The robot has no tool with the id {}!", slot.tool_id)))?;
+    set_tcp(desc, rack, tool_vec);
+
+    // The distance is independent of the TCP, so the tool is moved out along the approach offset too
+    move_slot(rob, desc, rack, Vec3::from(slot.approach)).await?;
+    stat.verify_tool(rob)
+}
+
+/// Changes the tool of the robot automatically using the tool rack of the station
+///
+/// The tool currently mounted is put back into its slot, afterwards the new tool is picked up. The robot travels to
+/// the approach position of a slot in joint space, the slot itself is entered and left linearly along its approach 
+/// offset. If the pickup fails, the new tool is released in its slot again and the robot is left without tool. The TCP
+/// of the descriptor is updated with the vector of the tool mounted and a `PushMsg::ToolChange` is pushed to all 
+/// remotes of the robot (see `Robot::push_msg()`) whenever the tool changed, even if the change failed. Passing `None` 
+/// only puts the current tool away
+pub async fn change_tool<S, R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, stat : &mut S,
+    tool_id : Option<usize>, speed_f : Factor) -> Result<(), crate::Error>
+where
+    S : Station<G, T, C, Robot = R>,
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    let rack = stat.tool_rack().cloned()
+        .ok_or_else(|| SyError::config("The station has no tool rack configured!"))?;
+
+    let old = rob.get_tool_id();
+
+    if old == tool_id {
+        return Ok(());
+    }
+
+    if let Some(id) = tool_id {
+        rack.req_slot(id)?;
+
+        if id >= rob.get_tools().len() {
+            return Err(SyError::config(format!("The robot has no tool with the id {}!", id)).into());
+        }
+    }
+
+    let res = exchange(rob, desc, stat, &rack, old, tool_id, speed_f).await;
+    let new = rob.get_tool_id();
+
+    if new != old {
+        rob.push_msg(PushMsg::ToolChange { old, new })?;
+    }
+
+    res
+}
+
+async fn exchange<S, R, G, T, D, const C : usize>(rob : &mut R, desc : &mut D, stat : &mut S, rack : &ToolRack,
+    old : Option<usize>, tool_id : Option<usize>, speed_f : Factor) -> Result<(), crate::Error>
+where
+    S : Station<G, T, C, Robot = R>,
+    R : Robot<G, T, C>,
+    G : SyncActuatorGroup<T, C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static,
+    D : Descriptor<C>
+{
+    // Put away the current tool
+    if let Some(current) = old {
+        let slot = rack.req_slot(current)?;
+
+        if let Some(simple) = rob.get_tool_mut().and_then(|t| t.simple_tool_mut()) {
+            simple.deactivate();
+        }
+
+        set_tcp(desc, rack, Vec3::ZERO);
+        move_to(rob, desc, slot.approach_pos(), speed_f).await?;
+        release(rob, desc, stat, rack, slot).await?;
+    }
+
+    // Pick up the new tool
+    if let Some(id) = tool_id {
+        let slot = rack.req_slot(id)?;
+
+        set_tcp(desc, rack, Vec3::ZERO);
+        move_to(rob, desc, slot.approach_pos(), speed_f).await?;
+
+        if let Err(err) = pick_up(rob, desc, stat, rack, slot).await {
+            // The robot must not continue with a tool that is not (properly) mounted, so it is put back
+            return match release(rob, desc, stat, rack, slot).await {
+                Ok(()) => Err(err),
+                Err(rec) => Err(SyError::safety(
+                    format!("The recovery of a failed tool pickup failed! (Pickup: {}, Recovery: {})", err, rec)
+                ).into())
+            };
+        }
+    }
+
+    Ok(())
+}
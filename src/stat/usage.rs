@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{Identity, PushMsg, PushRemote};
+
+/// Usage statistics of a single tool
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ToolUsage {
+    /// Total time the tool has been engaged (active) in seconds
+    pub engaged_time : f32,
+    /// Number of activations of the tool
+    pub activations : u64,
+    /// Distance travelled with the tool equipped in millimeters
+    pub travel : f32
+}
+
+/// Maintenance thresholds for a tool, `None` values are ignored
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MaintenanceThresholds {
+    /// Maximum engaged time in seconds
+    pub engaged_time : Option<f32>,
+    /// Maximum number of activations
+    pub activations : Option<u64>,
+    /// Maximum travel distance in millimeters
+    pub travel : Option<f32>
+}
+
+impl MaintenanceThresholds {
+    /// Checks if any of the thresholds has been exceeded by the given usage
+    pub fn exceeded(&self, usage : &ToolUsage) -> bool {
+        self.engaged_time.map_or(false, |t| usage.engaged_time >= t)
+            | self.activations.map_or(false, |a| usage.activations >= a)
+            | self.travel.map_or(false, |t| usage.travel >= t)
+    }
+}
+
+/// Tracks the usage of all the tools of a robot by their tool id. The usage is recorded by the robot (see 
+/// `Robot::tool_usage()`) and stored in the tracker of the station (see `Station::store_tool_usage()`)
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ToolUsageTracker {
+    /// Usage statistics for each tool id
+    pub usages : HashMap<usize, ToolUsage>,
+    /// Maintenance thresholds for each tool id
+    pub thresholds : HashMap<usize, MaintenanceThresholds>,
+
+    #[serde(skip)]
+    active_since : HashMap<usize, Instant>,
+    #[serde(skip)]
+    warned : HashSet<usize>
+}
+
+impl ToolUsageTracker {
+    /// Creates a new empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the usage statistics of the given tool
+    pub fn usage(&self, tool_id : usize) -> Option<&ToolUsage> {
+        self.usages.get(&tool_id)
+    }
+
+    /// Sets the maintenance thresholds of the given tool
+    pub fn set_thresholds(&mut self, tool_id : usize, thresholds : MaintenanceThresholds) {
+        self.thresholds.insert(tool_id, thresholds);
+    }
+
+    // Tracking
+        /// Records an activation of the tool, starts tracking the engaged time
+        pub fn activate(&mut self, tool_id : usize) {
+            self.usages.entry(tool_id).or_default().activations += 1;
+            self.active_since.entry(tool_id).or_insert_with(Instant::now);
+        }
+
+        /// Records a deactivation of the tool, adds the time since the activation to the engaged time
+        pub fn deactivate(&mut self, tool_id : usize) {
+            if let Some(inst) = self.active_since.remove(&tool_id) {
+                self.usages.entry(tool_id).or_default().engaged_time += inst.elapsed().as_secs_f32();
+            }
+        }
+
+        /// Adds a travel distance to the given tool
+        pub fn add_travel(&mut self, tool_id : usize, distance : f32) {
+            self.usages.entry(tool_id).or_default().travel += distance.abs();
+        }
+
+        /// Resets the usage statistics of the tool, e.g. after maintenance has been done. A running activation is 
+        /// counted from the reset on
+        pub fn reset(&mut self, tool_id : usize) {
+            self.usages.remove(&tool_id);
+            self.warned.remove(&tool_id);
+
+            if let Some(inst) = self.active_since.get_mut(&tool_id) {
+                *inst = Instant::now();
+            }
+        }
+
+        /// Takes the usage recorded so far, e.g. to store it in the tracker of the station. Running activations keep
+        /// being tracked
+        pub fn take_usages(&mut self) -> HashMap<usize, ToolUsage> {
+            core::mem::take(&mut self.usages)
+        }
+
+        /// Adds the usage recorded by another tracker (see `take_usages()`)
+        pub fn merge(&mut self, usages : HashMap<usize, ToolUsage>) {
+            for (tool_id, recorded) in usages {
+                let usage = self.usages.entry(tool_id).or_default();
+                usage.engaged_time += recorded.engaged_time;
+                usage.activations += recorded.activations;
+                usage.travel += recorded.travel;
+            }
+        }
+    // 
+
+    // Maintenance
+        /// Returns wheiter the tool requires maintenance or not
+        pub fn maintenance_due(&self, tool_id : usize) -> bool {
+            if let (Some(usage), Some(thresholds)) = (self.usages.get(&tool_id), self.thresholds.get(&tool_id)) {
+                thresholds.exceeded(usage)
+            } else {
+                false
+            }
+        }
+
+        /// Returns the ids of all tools that require maintenance
+        pub fn tools_due(&self) -> Vec<usize> {
+            let mut ids : Vec<usize> = self.usages.keys().copied()
+                .filter(|id| self.maintenance_due(*id))
+                .collect();
+            ids.sort();
+            ids
+        }
+
+        /// Returns the ids of the tools that crossed their maintenance thresholds since the last call, each tool is 
+        /// only returned once until it is reset (see `reset()`)
+        pub fn newly_due(&mut self) -> Vec<usize> {
+            let due : Vec<usize> = self.tools_due().into_iter()
+                .filter(|id| !self.warned.contains(id))
+                .collect();
+
+            self.warned.extend(due.iter().copied());
+            due
+        }
+
+        /// Pushes a `PushMsg::ToolMaintenance` warning to all the given remotes if any tool requires maintenance, the
+        /// message is namespaced with the given identity (see `PushRemote::push_other_from()`)
+        pub fn push_warnings(&self, remotes : &mut [Box<dyn PushRemote>], identity : Option<&Identity>) -> Result<(), crate::Error> {
+            let tools = self.tools_due();
+
+            if tools.is_empty() {
+                return Ok(());
+            }
+
+            for remote in remotes {
+                remote.push_other_from(identity, PushMsg::ToolMaintenance { tools: tools.clone() })?;
+            }
+
+            Ok(())
+        }
+    //
+}
@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use glam::Vec3;
+use serde::{Serialize, Deserialize};
+use syunit::*;
+
+// Submodules
+    mod router;
+    pub use router::{ChannelConfig, LoadSource, TelemetryMsg, TelemetryRouter, TelemetrySink};
+// 
+
+/// A single telemetry sample
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TelemetrySample {
+    /// Time of the sample in seconds, relative to the creation of the buffer
+    pub time : f32,
+    /// The `Phi` values of the robot
+    pub phis : Vec<f32>,
+    /// The position of the TCP
+    pub tcp : [f32; 3],
+    /// The forces applied to each component, if known
+    pub forces : Option<Vec<f32>>
+}
+
+impl TelemetrySample {
+    /// Returns the header line of the CSV format for `C` components
+    pub fn csv_header(comps : usize) -> String {
+        let mut header = String::from("time");
+        
+        for i in 0 .. comps {
+            header.push_str(&format!(",phi_{}", i));
+        }
+
+        header.push_str(",tcp_x,tcp_y,tcp_z");
+
+        for i in 0 .. comps {
+            header.push_str(&format!(",force_{}", i));
+        }
+
+        header
+    }
+
+    /// Returns the sample as CSV line, missing forces are left empty
+    pub fn to_csv(&self) -> String {
+        let mut line = format!("{}", self.time);
+
+        for phi in &self.phis {
+            line.push_str(&format!(",{}", phi));
+        }
+
+        line.push_str(&format!(",{},{},{}", self.tcp[0], self.tcp[1], self.tcp[2]));
+
+        if let Some(forces) = &self.forces {
+            for force in forces {
+                line.push_str(&format!(",{}", force));
+            }
+        } else {
+            line.push_str(&",".repeat(self.phis.len()));
+        }
+
+        line
+    }
+}
+
+/// In-memory ring buffer keeping the last `span` of high-rate telemetry, so front ends can fetch "what just happened"
+/// 
+/// Filled by a `TelemetryRouter` with every tick, see `TelemetryRouter::set_buffer()`
+#[derive(Clone, Debug)]
+pub struct TelemetryBuffer {
+    samples : VecDeque<TelemetrySample>,
+    start : Instant,
+
+    /// The timespan kept in the buffer
+    pub span : Duration
+}
+
+impl TelemetryBuffer {
+    /// Creates a new buffer keeping the samples of the last `span`
+    pub fn new(span : Duration) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            start: Instant::now(),
+            span
+        }
+    }
+
+    /// Returns the time in seconds since the creation of the buffer, used as time base for the samples
+    pub fn now(&self) -> f32 {
+        self.start.elapsed().as_secs_f32()
+    }
+
+    /// Records a new sample at the current time, dropping all samples older than `span`
+    pub fn record<const C : usize>(&mut self, phis : &[Phi; C], tcp : Vec3, forces : Option<&[Force; C]>) {
+        let time = self.now();
+
+        self.samples.push_back(TelemetrySample {
+            time,
+            phis: phis.iter().map(|p| p.0).collect(),
+            tcp: tcp.to_array(),
+            forces: forces.map(|f| f.iter().map(|f| f.0).collect())
+        });
+
+        let min_time = time - self.span.as_secs_f32();
+        while self.samples.front().map_or(false, |s| s.time < min_time) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns all samples in the buffer
+    pub fn samples(&self) -> &VecDeque<TelemetrySample> {
+        &self.samples
+    }
+
+    /// Returns all samples between `from` and `to` (seconds, see `now()`)
+    pub fn window(&self, from : f32, to : f32) -> Vec<&TelemetrySample> {
+        self.samples.iter().filter(|s| (s.time >= from) & (s.time <= to)).collect()
+    }
+
+    /// Returns all samples of the last `duration`
+    pub fn last(&self, duration : Duration) -> Vec<&TelemetrySample> {
+        let now = self.now();
+        self.window(now - duration.as_secs_f32(), now)
+    }
+
+    /// Returns the samples between `from` and `to` as JSON array
+    pub fn window_json(&self, from : f32, to : f32) -> Result<String, crate::Error> {
+        Ok(serde_json::to_string(&self.window(from, to))?)
+    }
+
+    /// Returns the samples between `from` and `to` as CSV table
+    pub fn window_csv(&self, from : f32, to : f32) -> String {
+        let window = self.window(from, to);
+        let comps = window.first().map_or(0, |s| s.phis.len());
+
+        let mut csv = TelemetrySample::csv_header(comps);
+        for sample in window {
+            csv.push('\n');
+            csv.push_str(&sample.to_csv());
+        }
+
+        csv
+    }
+}
@@ -0,0 +1,306 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use glam::Vec3;
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use syunit::*;
+
+use crate::{Identity, SyError};
+use crate::desc::Kinematic;
+use crate::events::{StateEvent, Subscription};
+use crate::rcs::Point;
+use crate::telemetry::TelemetryBuffer;
+
+/// A client receiving telemetry messages, e.g. a websocket connection of a server
+pub trait TelemetrySink {
+    /// Sends a message serialized as JSON to the client. Clients returning an error are unsubscribed
+    fn send(&mut self, msg : &str) -> Result<(), crate::Error>;
+}
+
+/// Source of the component loads sent to the clients
+pub type LoadSource<const C : usize> = Box<dyn FnMut() -> Option<[Force; C]>>;
+
+/// The telemetry content chosen by a subscriber of a `TelemetryRouter`, rates are given in Hz. Channels without rate
+/// are not sent at all
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelConfig {
+    /// Rate of the positions (`Phi` values and TCP)
+    #[serde(default)]
+    pub positions : Option<f32>,
+    /// Rate of the component loads
+    #[serde(default)]
+    pub loads : Option<f32>,
+    /// Wheiter state events (tool changes, errors, warnings) are sent as soon as they occur
+    #[serde(default)]
+    pub events : bool
+}
+
+impl ChannelConfig {
+    /// Configuration sending only the state events
+    pub fn events_only() -> Self {
+        Self { positions: None, loads: None, events: true }
+    }
+
+    fn due(rate : Option<f32>, last : Option<Instant>, now : Instant) -> bool {
+        match (rate, last) {
+            (Some(rate), _) if rate <= 0.0 => false,
+            (Some(_), None) => true,
+            (Some(rate), Some(last)) => now.saturating_duration_since(last).as_secs_f32() >= (1.0 / rate) - 1e-4,
+            (None, _) => false
+        }
+    }
+}
+
+/// A message sent by a `TelemetryRouter`, serialized as JSON with the channel as tag
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "channel", rename_all = "snake_case")]
+pub enum TelemetryMsg {
+    /// The position of the robot
+    Positions {
+        /// Time in seconds, relative to the start of the router
+        time : f32,
+        /// The `Phi` values of the robot
+        phis : Vec<f32>,
+        /// The position of the TCP
+        tcp : [f32; 3]
+    },
+    /// The loads of each component
+    Loads {
+        /// Time in seconds, relative to the start of the router
+        time : f32,
+        /// The loads
+        loads : Vec<f32>
+    },
+    /// A state event of the robot
+    Event {
+        /// Time in seconds, relative to the start of the router
+        time : f32,
+        /// The event
+        event : serde_json::Value
+    }
+}
+
+impl TelemetryMsg {
+    /// Creates an event message, position updates are no events and return `None`
+    pub fn event<const C : usize>(time : f32, event : &StateEvent<C>) -> Option<Self> {
+        let event = match event {
+            StateEvent::Phis(_) => return None,
+            StateEvent::ToolChange { old, new } => json!({ "type": "tool_change", "old": old, "new": new }),
+            StateEvent::Error(err) => json!({ "type": "error", "msg": err }),
+            StateEvent::LimitWarning { axis, distance } => json!({ "type": "limit_warning", "axis": axis, "distance": distance }),
+            StateEvent::StepLoss { axis, deviation } => json!({ "type": "step_loss", "axis": axis, "deviation": deviation }),
+            StateEvent::JogLimit { axis } => json!({ "type": "jog_limit", "axis": axis }),
+            StateEvent::TargetSnapped { correction } => json!({ "type": "target_snapped", "correction": correction.to_array() }),
+            StateEvent::Maintenance { tools } => json!({ "type": "maintenance", "tools": tools })
+        };
+
+        Some(Self::Event { time, event })
+    }
+
+    /// Serializes the message as JSON, adding the identity of the sender as `source` field if given (see 
+    /// `PushMsg::to_json_from()`)
+    pub fn to_json_from(&self, identity : Option<&Identity>) -> Result<String, crate::Error> {
+        let mut value = serde_json::to_value(self)?;
+
+        if let (Some(identity), Some(obj)) = (identity, value.as_object_mut()) {
+            obj.insert("source".to_owned(), serde_json::to_value(identity)?);
+        }
+
+        Ok(serde_json::to_string(&value)?)
+    }
+}
+
+struct Subscriber {
+    id : usize,
+    sink : Box<dyn TelemetrySink>,
+    config : ChannelConfig,
+    last_positions : Option<Instant>,
+    last_loads : Option<Instant>
+}
+
+/// Central distribution of telemetry to multiple clients, every client chooses its own content and rates (e.g.
+/// positions at 50 Hz, loads at 5 Hz or events only) instead of one global cadence
+pub struct TelemetryRouter<const C : usize> {
+    subscribers : Vec<Subscriber>,
+    loads : Option<LoadSource<C>>,
+    buffer : Option<Arc<Mutex<TelemetryBuffer>>>,
+    next_id : usize,
+    start : Instant,
+
+    /// The interval the router checks for due channels, limits the maximum rate of all channels
+    pub tick : Duration,
+    /// The identity added to all messages as `source` field, see `TelemetryMsg::to_json_from()`
+    pub identity : Option<Identity>
+}
+
+impl<const C : usize> TelemetryRouter<C> {
+    /// Creates a new router without subscribers, checking for due channels every `tick`
+    pub fn new(tick : Duration) -> Self {
+        Self {
+            subscribers: Vec::new(),
+            loads: None,
+            buffer: None,
+            next_id: 0,
+            start: Instant::now(),
+
+            tick,
+            identity: None
+        }
+    }
+
+    /// Sets the source of the component loads
+    pub fn set_load_source(&mut self, loads : LoadSource<C>) {
+        self.loads = Some(loads);
+    }
+
+    /// Records the positions and loads of every tick into the given buffer (see `run()`), the buffer is shared with the 
+    /// front ends fetching the recent telemetry
+    pub fn set_buffer(&mut self, buffer : Arc<Mutex<TelemetryBuffer>>) {
+        self.buffer = Some(buffer);
+    }
+
+    /// Returns the time in seconds since the creation of the router, used as time base for the messages
+    pub fn now(&self) -> f32 {
+        self.start.elapsed().as_secs_f32()
+    }
+
+    // Subscribers
+        /// Subscribes a new client with the given configuration, returns the id of the subscriber
+        pub fn subscribe(&mut self, sink : Box<dyn TelemetrySink>, config : ChannelConfig) -> usize {
+            let id = self.next_id;
+            self.next_id += 1;
+
+            self.subscribers.push(Subscriber { id, sink, config, last_positions: None, last_loads: None });
+            id
+        }
+
+        /// Unsubscribes a client, returns wheiter the client has been subscribed
+        pub fn unsubscribe(&mut self, id : usize) -> bool {
+            let len = self.subscribers.len();
+            self.subscribers.retain(|sub| sub.id != id);
+            self.subscribers.len() != len
+        }
+
+        /// Changes the configuration of a client, e.g. when it requests another rate
+        pub fn configure(&mut self, id : usize, config : ChannelConfig) -> Result<(), crate::Error> {
+            let sub = self.subscribers.iter_mut().find(|sub| sub.id == id)
+                .ok_or_else(|| SyError::config(format!("No telemetry subscriber with the id {}!", id)))?;
+
+            sub.config = config;
+            Ok(())
+        }
+
+        /// Returns the configuration of a client
+        pub fn config(&self, id : usize) -> Option<&ChannelConfig> {
+            self.subscribers.iter().find(|sub| sub.id == id).map(|sub| &sub.config)
+        }
+
+        /// Returns the number of clients subscribed
+        pub fn clients(&self) -> usize {
+            self.subscribers.len()
+        }
+    //
+
+    // Publishing
+        /// Sends the message to all subscribers selected, clients failing are unsubscribed
+        fn send<F : FnMut(&mut Subscriber) -> bool>(&mut self, msg : &TelemetryMsg, mut select : F) -> Result<(), crate::Error> {
+            let json = msg.to_json_from(self.identity.as_ref())?;
+            self.subscribers.retain_mut(|sub| !select(sub) || sub.sink.send(&json).is_ok());
+            Ok(())
+        }
+
+        /// Sends the positions to all clients whose positions channel is due at `now`
+        pub fn publish_positions(&mut self, now : Instant, phis : &[Phi; C], tcp : Vec3) -> Result<(), crate::Error> {
+            if !self.subscribers.iter().any(|sub| ChannelConfig::due(sub.config.positions, sub.last_positions, now)) {
+                return Ok(());
+            }
+
+            let msg = TelemetryMsg::Positions {
+                time: now.saturating_duration_since(self.start).as_secs_f32(),
+                phis: phis.iter().map(|p| p.0).collect(),
+                tcp: tcp.to_array()
+            };
+
+            self.send(&msg, |sub| {
+                let due = ChannelConfig::due(sub.config.positions, sub.last_positions, now);
+                if due {
+                    sub.last_positions = Some(now);
+                }
+                due
+            })
+        }
+
+        /// Reads the load source and sends the loads to all clients whose loads channel is due at `now`. The source is
+        /// only read if at least one client is due
+        pub fn publish_loads(&mut self, now : Instant) -> Result<(), crate::Error> {
+            if !self.subscribers.iter().any(|sub| ChannelConfig::due(sub.config.loads, sub.last_loads, now)) {
+                return Ok(());
+            }
+
+            let Some(loads) = self.loads.as_mut().and_then(|src| src()) else {
+                return Ok(());
+            };
+
+            let msg = TelemetryMsg::Loads {
+                time: now.saturating_duration_since(self.start).as_secs_f32(),
+                loads: loads.iter().map(|f| f.0).collect()
+            };
+
+            self.send(&msg, |sub| {
+                let due = ChannelConfig::due(sub.config.loads, sub.last_loads, now);
+                if due {
+                    sub.last_loads = Some(now);
+                }
+                due
+            })
+        }
+
+        /// Records a sample into the buffer of the router (see `set_buffer()`), the load source is read for every sample
+        pub fn record(&mut self, phis : &[Phi; C], tcp : Vec3) {
+            let Some(buffer) = &self.buffer else {
+                return;
+            };
+
+            let loads = self.loads.as_mut().and_then(|src| src());
+            buffer.lock().unwrap().record(phis, tcp, loads.as_ref());
+        }
+
+        /// Sends the event immediately to all clients subscribed to events
+        pub fn publish_event(&mut self, event : &StateEvent<C>) -> Result<(), crate::Error> {
+            match TelemetryMsg::event(self.now(), event) {
+                Some(msg) => self.send(&msg, |sub| sub.config.events),
+                None => Ok(())
+            }
+        }
+    //
+
+    /// Runs the router until the event bus of the robot is dropped. The position of the TCP is calculated with the
+    /// given kinematic, which should match the kinematic of the descriptor. Every tick is recorded into the buffer of 
+    /// the router, if set
+    pub async fn run<K : Kinematic<C>>(&mut self, mut events : Subscription<C>, mut kin : K) -> Result<(), crate::Error> {
+        let mut interval = tokio::time::interval(self.tick);
+        let mut phis = kin.phis();
+
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(StateEvent::Phis(new)) => phis = new,
+                    Some(event) => self.publish_event(&event)?,
+                    None => return Ok(())
+                },
+                _ = interval.tick() => {
+                    let now = Instant::now();
+
+                    kin.update(&phis)?;
+                    let tcp = kin.calculate_end();
+                    let tcp = Vec3::new(tcp.x(), tcp.y(), tcp.z());
+
+                    self.record(&phis, tcp);
+                    self.publish_positions(now, &phis, tcp)?;
+                    self.publish_loads(now)?;
+                }
+            }
+        }
+    }
+}
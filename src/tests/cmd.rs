@@ -0,0 +1,70 @@
+use syunit::*;
+
+use crate::ErrorKind;
+use crate::cancel::CancelToken;
+use crate::cmd::{channel, AuthSender, AuthTable, Command, ControlLock, Role};
+
+#[test]
+fn auth_rejects_credentials() {
+    let mut table = AuthTable::new()
+        .token("op", Role::Operator)
+        .token("view", Role::ReadOnly);
+
+    assert_eq!(table.authenticate("op").unwrap().role, Role::Operator);
+    assert_eq!(table.authenticate("view").unwrap().role, Role::ReadOnly);
+    assert_eq!(ErrorKind::of(&table.authenticate("guess").unwrap_err()), ErrorKind::Access);
+
+    // Revoked tokens are rejected, sessions created before stay valid
+    let session = table.authenticate("op").unwrap();
+    table.revoke("op");
+    assert_eq!(ErrorKind::of(&table.authenticate("op").unwrap_err()), ErrorKind::Access);
+    assert!(ControlLock::new().acquire(&session).is_ok());
+}
+
+#[tokio::test]
+async fn auth_requires_permissions() {
+    use crate::Robot;
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::tests::TestXYRobot;
+
+    let table = AuthTable::new()
+        .token("op-a", Role::Operator)
+        .token("op-b", Role::Operator)
+        .token("view", Role::ReadOnly);
+    let lock = ControlLock::new();
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut desc = LinearXYDescriptor::new();
+    let (sender, receiver) = channel::<2>(4, CancelToken::new());
+
+    let viewer = AuthSender::new(sender.clone(), table.authenticate("view").unwrap(), lock.clone());
+    let op_a = AuthSender::new(sender.clone(), table.authenticate("op-a").unwrap(), lock.clone());
+    let op_b = AuthSender::new(sender, table.authenticate("op-b").unwrap(), lock.clone());
+
+    let cmd = Command::MoveJ { deltas: [ Delta(1.0), Delta(1.0) ], speed_f: Factor::MAX };
+
+    let clients = async move {
+        // Read-only clients can only query
+        assert_eq!(ErrorKind::of(&viewer.acquire().unwrap_err()), ErrorKind::Access);
+        assert_eq!(ErrorKind::of(&viewer.send(cmd.clone()).await.unwrap_err()), ErrorKind::Access);
+        assert_eq!(ErrorKind::of(&viewer.stop().await.unwrap_err()), ErrorKind::Access);
+        assert!(viewer.query_phis().await.is_ok());
+
+        // Motion requires the control lock, which is held by one session at a time
+        assert_eq!(ErrorKind::of(&op_a.send(cmd.clone()).await.unwrap_err()), ErrorKind::Access);
+        op_a.acquire().unwrap();
+        assert_eq!(ErrorKind::of(&op_b.acquire().unwrap_err()), ErrorKind::Access);
+        assert_eq!(ErrorKind::of(&op_b.send(cmd.clone()).await.unwrap_err()), ErrorKind::Access);
+        op_a.send(cmd.clone()).await.unwrap();
+
+        // Disconnecting releases the lock
+        drop(op_a);
+        op_b.acquire().unwrap();
+        op_b.send(cmd).await.unwrap();
+    };
+
+    tokio::join!(receiver.run(&mut rob, &mut desc), clients);
+
+    assert_eq!(lock.holder(), None);
+    assert!(rob.gammas()[0].0 > 0.0);
+}
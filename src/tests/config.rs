@@ -0,0 +1,185 @@
+use syunit::*;
+
+use crate::config::AngleConfig;
+
+#[test]
+fn angle_config_roundtrip() {
+    let confs = [
+        AngleConfig::EMPTY,
+        AngleConfig::new(Delta(0.5), true),
+        AngleConfig { offset: Delta(-1.0), counter: false, ratio: 2.5 },
+        AngleConfig { offset: Delta(0.25), counter: true, ratio: 0.1 }
+    ];
+
+    for conf in confs {
+        let gamma = Gamma(1.2);
+        let phi = conf.phi_from_gamma(gamma);
+        assert!((conf.gamma_from_phi(phi).0 - gamma.0).abs() < 1e-5, "Roundtrip failed for {:?}", conf);
+    }
+}
+
+#[test]
+fn angle_config_degrees() {
+    let conf : AngleConfig = serde_json::from_str(r#"{ "offset": 90.0, "unit": "degrees" }"#).unwrap();
+    assert!((conf.offset.0 - core::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    assert_eq!(conf.ratio, 1.0);
+}
+
+#[test]
+fn conf_select() {
+    use crate::config::{ConfSelect, Elbow, KinConf};
+
+    let up = KinConf::default();
+    let down = KinConf { elbow: Elbow::Down, ..Default::default() };
+    let solutions = [ (up, [ Phi(1.0), Phi(-1.0) ]), (down, [ Phi(0.2), Phi(0.5) ]) ];
+
+    let current = [ Phi(0.0), Phi(0.0) ];
+    assert_eq!(ConfSelect::MinTravel.select(&solutions, &current).unwrap().0, down);
+    assert_eq!(ConfSelect::Prefer(up).select(&solutions, &current).unwrap().0, up);
+
+    // Unavailable configurations fall back to the minimal travel
+    let flip = KinConf { wrist: crate::config::Wrist::Flip, ..Default::default() };
+    assert_eq!(ConfSelect::Prefer(flip).select(&solutions, &[ Phi(1.0), Phi(-1.0) ]).unwrap().0, up);
+}
+
+#[test]
+#[cfg(feature = "urdf")]
+fn urdf_import() {
+    use crate::config::CompKind;
+    use crate::config::urdf::parse_urdf;
+
+    let scaffold = parse_urdf(r#"
+        <robot name="arm">
+            <link name="base"/>
+            <link name="upper"><inertial><mass value="1.5"/></inertial></link>
+            <link name="slide"><inertial><mass value="0.5"/></inertial></link>
+            <link name="flange"><inertial><mass value="0.25"/></inertial></link>
+            <joint name="shoulder" type="revolute">
+                <parent link="base"/><child link="upper"/>
+                <origin xyz="0 0 0.1"/><axis xyz="0 1 0"/>
+                <limit lower="-1.5" upper="1.5" velocity="2.0"/>
+            </joint>
+            <joint name="extend" type="prismatic">
+                <parent link="upper"/><child link="slide"/>
+                <origin xyz="0.3 0 0"/><axis xyz="1 0 0"/>
+                <limit lower="0" upper="0.2" velocity="0.1"/>
+            </joint>
+            <joint name="tool" type="fixed">
+                <parent link="slide"/><child link="flange"/>
+                <origin xyz="0.05 0 0"/>
+            </joint>
+        </robot>
+    "#).unwrap();
+
+    assert_eq!(scaffold.name, "arm");
+    assert_eq!(scaffold.anchor, glam::Vec3::new(0.0, 0.0, 100.0));
+    assert_eq!(scaffold.comps.len(), 2);
+
+    let (shoulder, extend) = (&scaffold.comps[0], &scaffold.comps[1]);
+    assert_eq!(shoulder.kind, CompKind::GearJoint);
+    assert_eq!((shoulder.min, shoulder.max, shoulder.mass), (Some(-1.5), Some(1.5), 1.5));
+    assert!((shoulder.dim - glam::Vec3::new(300.0, 0.0, 0.0)).length() < 1e-3);
+
+    // Prismatic limits and fixed links
+    assert_eq!(extend.kind, CompKind::LinearAxis);
+    assert_eq!((extend.min, extend.max), (Some(0.0), Some(200.0)));
+    assert!((extend.mass - 0.75).abs() < 1e-6);
+    assert!((extend.dim - glam::Vec3::new(50.0, 0.0, 0.0)).length() < 1e-3);
+
+    // Branches are rejected
+    assert!(parse_urdf(r#"
+        <robot name="tree">
+            <joint name="a" type="revolute"><parent link="base"/><child link="l1"/></joint>
+            <joint name="b" type="revolute"><parent link="base"/><child link="l2"/></joint>
+        </robot>
+    "#).is_err());
+}
+
+#[test]
+fn model_export() {
+    use crate::{Identity, Robot};
+    use crate::config::RobotModel;
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::robs::{SoftLimit, SoftLimits};
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let desc = LinearXYDescriptor::new();
+
+    rob.set_identity(Some(Identity::new("gantry")));
+    rob.vars_mut().soft_limits = Some(SoftLimits::new([
+        SoftLimit::new(Some(Phi(0.0)), Some(Phi(500.0)), 10.0, 0.2), SoftLimit::NONE
+    ]));
+    rob.set_limits(&[ None, Some(Gamma(-10.0)) ], &[ Some(Gamma(600.0)), Some(Gamma(200.0)) ]);
+    rob.set_omega_max([ Velocity(50.0), Velocity(20.0) ]);
+
+    let model = RobotModel::from_robot(&rob, &desc);
+    assert_eq!(model.name, "gantry");
+    assert_eq!(model.segments.len(), 2);
+    assert!(!model.segments[0].rotary);
+    assert_eq!((model.segments[0].min, model.segments[0].max), (Some(0.0), Some(500.0)));
+    assert_eq!((model.segments[1].min, model.segments[1].max), (Some(-10.0), Some(200.0)));
+    assert_eq!(model.segments[1].vel, Some(20.0));
+    assert_eq!(model.segments[1].axis, [ 0.0, 1.0, 0.0 ]);
+    assert!(model.tool.is_none());
+
+    let json : RobotModel = serde_json::from_str(&model.export_json().unwrap()).unwrap();
+    assert_eq!(json, model);
+
+    let urdf = model.export_urdf();
+    assert!(urdf.contains("<robot name=\"gantry\">"));
+    assert_eq!(urdf.matches("type=\"prismatic\"").count(), 2);
+    assert!(urdf.contains("lower=\"0\" upper=\"0.5\""));
+    assert!(urdf.contains("velocity=") & !urdf.contains("velocity=\"0\""));
+
+    #[cfg(feature = "urdf")]
+    {
+        let scaffold = crate::config::urdf::parse_urdf(&urdf).unwrap();
+        assert_eq!(scaffold.comps.len(), 2);
+        assert_eq!(scaffold.comps[0].max, Some(500.0));
+    }
+
+    let escaped = RobotModel { name: "a<b & \"c\"".to_owned(), ..model }.export_urdf();
+    assert!(escaped.contains("<robot name=\"a&lt;b &amp; &quot;c&quot;\">"));
+}
+
+#[test]
+fn conf_formats() {
+    use crate::config::{AngleUnit, ConfFormat};
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Limit {
+        vel : f32
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Comp {
+        name : String,
+        limit : Limit
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Conf {
+        unit : AngleUnit,
+        comps : Vec<Comp>
+    }
+
+    assert_eq!(ConfFormat::from_path("arm.conf.toml").unwrap(), ConfFormat::Toml);
+    assert_eq!(ConfFormat::from_path("arm.YML").unwrap(), ConfFormat::Yaml);
+    assert!(ConfFormat::from_path("arm.conf").is_err());
+
+    let json = r#"{ "unit": "degrees", "comps": [ { "name": "base", "limit": { "vel": 2.0 } } ] }"#;
+    let conf : Conf = ConfFormat::Json.parse(json).unwrap();
+    assert_eq!(conf.unit, AngleUnit::Degrees);
+
+    // Errors reference the offending key
+    let err = ConfFormat::Json.parse::<Conf>(r#"{ "unit": "degrees", "comps": [ { "name": "base", "limit": { "vel": "fast" } } ] }"#)
+        .unwrap_err();
+    assert!(err.to_string().contains("comps[0].limit.vel"), "{}", err);
+
+    #[cfg(feature = "toml")]
+    assert_eq!(ConfFormat::Toml.parse::<Conf>("unit = \"degrees\"\n\n[[comps]]\nname = \"base\"\nlimit = { vel = 2.0 }\n").unwrap(), conf);
+
+    #[cfg(feature = "yaml")]
+    assert_eq!(ConfFormat::Yaml.parse::<Conf>("unit: degrees\ncomps:\n  - name: base\n    limit:\n      vel: 2.0\n").unwrap(), conf);
+}
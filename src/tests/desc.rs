@@ -0,0 +1,142 @@
+use glam::Vec3;
+use syunit::*;
+
+use crate::Descriptor;
+use crate::desc::IkSolver;
+use crate::desc::common::LinearXYZDescriptor;
+use crate::rcs::Position;
+
+#[test]
+fn ik_solver_linear() {
+    let mut desc = LinearXYZDescriptor::new();
+    let target = Vec3::new(10.0, -5.0, 20.0);
+
+    let phis = desc.phis_for_pos_iter(Position::from_vec3(target), &IkSolver::default()).unwrap();
+    let expected = desc.phis_for_pos(Position::from_vec3(target)).unwrap();
+
+    for i in 0 .. 3 {
+        assert!((phis[i].0 - expected[i].0).abs() < 0.01, "Axis {}: {} != {}", i, phis[i].0, expected[i].0);
+    }
+}
+
+#[test]
+fn jacobian_linear() {
+    let mut desc = LinearXYZDescriptor::new();
+    let phis = [ Phi(1.0), Phi(2.0), Phi(3.0) ];
+
+    let cols = desc.jacobian(&phis).unwrap();
+    for (col, axis) in cols.iter().zip([ Vec3::X, Vec3::Y, Vec3::Z ]) {
+        assert!((*col - axis).length() < 1e-2, "{:?} != {:?}", col, axis);
+    }
+
+    let omegas = [ Velocity(1.0), Velocity(-2.0), Velocity(0.5) ];
+    let vel = desc.vel_from_omegas(&phis, &omegas).unwrap();
+    let back = desc.omegas_from_vel(&phis, vel).unwrap();
+
+    for i in 0 .. 3 {
+        assert!((back[i].0 - omegas[i].0).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn envelope_linear() {
+    use crate::desc::Envelope;
+
+    let mut desc = LinearXYZDescriptor::new();
+    let limits = [ (Phi(0.0), Phi(100.0)); 3 ];
+
+    let env = Envelope::sample(desc.kinematic_mut(), &limits, 11, 10.0).unwrap();
+    assert!(env.contains(Vec3::new(50.0, 50.0, 50.0)));
+    assert!(!env.contains(Vec3::new(200.0, 0.0, 0.0)));
+    assert!(env.check(Vec3::new(-100.0, 0.0, 0.0)).is_err());
+
+    assert!(desc.reachable(Position::from_vec3(Vec3::new(10.0, 20.0, 30.0))));
+}
+
+#[test]
+fn triangle_out_of_reach() {
+    use crate::ErrorKind;
+    use crate::rcs::math::{calc_triangle_checked, closest_reachable, ReachMode};
+
+    let target = Vec3::new(300.0, 0.0, 0.0);
+
+    let err = calc_triangle_checked(100.0, 100.0, 300.0, target, ReachMode::Strict).unwrap_err();
+    assert_eq!(err.max_reach, 200.0);
+    assert_eq!(ErrorKind::of(&err.into()), ErrorKind::Kinematics);
+
+    // Fully stretched
+    let (alpha, _, gamma) = calc_triangle_checked(100.0, 100.0, 300.0, target, ReachMode::Closest).unwrap();
+    assert!(alpha.is_finite() & gamma.is_finite());
+    assert!((gamma - core::f32::consts::PI).abs() < 1e-3);
+
+    // Obtuse angles opposite of the side `a`
+    let (alpha, beta, gamma) = calc_triangle_checked(200.0, 50.0, 180.0, target, ReachMode::Strict).unwrap();
+    assert!(alpha > core::f32::consts::FRAC_PI_2);
+    assert!((alpha + beta + gamma - core::f32::consts::PI).abs() < 1e-3);
+
+    let closest = closest_reachable(Vec3::ZERO, target, 0.0, 200.0);
+    assert!((closest - Vec3::new(200.0, 0.0, 0.0)).length() < 1e-3);
+}
+
+#[test]
+fn singularity_guard() {
+    use crate::desc::SingularityGuard;
+
+    let mut desc = LinearXYZDescriptor::new();
+    let m = desc.manipulability(&[ Phi(1.0), Phi(2.0), Phi(3.0) ]).unwrap();
+    assert!((m - 1.0).abs() < 1e-2);
+
+    // Two parallel axes
+    assert!(crate::desc::manipulability(&[ Vec3::X, Vec3::X, Vec3::Z ]) < 1e-6);
+
+    let guard = SingularityGuard::new(0.5, 0.1);
+    assert_eq!(guard.factor(1.0), Some(1.0));
+    assert_eq!(guard.factor(0.25), Some(0.5));
+    assert_eq!(guard.factor(0.05), None);
+}
+
+#[test]
+fn orientation_dof() {
+    use core::f32::consts::FRAC_PI_2;
+
+    use glam::Quat;
+
+    use crate::ErrorKind;
+    use crate::rcs::math::{ori_deviation, swing_twist, OriDof};
+
+    let twist = Quat::from_rotation_z(0.5);
+    let tilt = Quat::from_rotation_x(FRAC_PI_2);
+
+    let pose = Position::new_quat(Vec3::new(1.0, 2.0, 3.0), tilt * twist);
+    assert!(pose.quat().angle_between(tilt * twist) < 1e-4);
+
+    let (swing, angle) = swing_twist(tilt * twist);
+    assert!((angle - 0.5).abs() < 1e-4);
+    assert!(swing.angle_between(tilt) < 1e-4);
+    assert!(ori_deviation(twist, OriDof::Twist) < 1e-4);
+
+    // Gantries cannot rotate the tool
+    let mut desc = LinearXYZDescriptor::new();
+    assert!(desc.phis_for_pos(Position::new(1.0, 2.0, 3.0)).is_ok());
+
+    let err = desc.phis_for_pos(Position::new_quat(Vec3::ZERO, twist)).unwrap_err();
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Kinematics);
+    assert!(desc.deco_for_ori(&Position::zero()).unwrap().is_empty());
+
+    // The iterative IK checks the orientation as well
+    assert!(desc.phis_for_pos_iter(Position::new_quat(Vec3::ZERO, twist), &IkSolver::default()).is_err());
+}
+
+#[test]
+fn snap_marginal_targets() {
+    use crate::rcs::math::snap_to_reachable;
+
+    // Sphere with radius 100
+    let reachable = |pos : Vec3| pos.length() <= 100.0;
+    let target = Vec3::new(100.2, 0.0, 0.0);
+
+    let snapped = snap_to_reachable(target, Vec3::ZERO, 0.5, reachable).unwrap();
+    assert!((snapped - Vec3::new(100.0, 0.0, 0.0)).length() < 1e-3);
+
+    assert!(snap_to_reachable(Vec3::new(101.0, 0.0, 0.0), Vec3::ZERO, 0.5, reachable).is_none());
+}
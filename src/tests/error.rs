@@ -0,0 +1,79 @@
+use crate::{ErrorKind, SyError};
+use crate::catalog::{Catalog, Message};
+
+#[test]
+fn catalog_render() {
+    use crate::catalog::PROMPT_TOOL_CHANGE;
+
+    let de : Catalog = serde_json::from_str(r#"{ "lang": "de", "messages": { "1000": "Werkzeug {tool} einsetzen" } }"#).unwrap();
+    let msg = Message::new(PROMPT_TOOL_CHANGE).param("tool", "Gripper");
+
+    assert_eq!(de.render(&msg), "Werkzeug Gripper einsetzen");
+    assert_eq!(Catalog::english().render(&msg), "Please mount the tool Gripper");
+
+    let err = SyError::safety("Door open").with_param("door", 2);
+    assert_eq!(err.message().code, ErrorKind::Safety.code());
+    assert_eq!(err.message().get("door"), Some("2"));
+    assert_eq!(Catalog::english().render_error(&err.into()), "Safety stop: Door open");
+
+    // Missing templates fall back to the code
+    assert!(de.render(&Message::new(600)).starts_with("[E600]"));
+}
+
+#[test]
+fn catalog_render_details() {
+    use syunit::*;
+
+    use crate::Robot;
+    use crate::catalog::ERR_MASKED_AXIS;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    rob.set_masked(1, true).unwrap();
+    let err = rob.check_masked(&[ Delta(0.0), Delta(2.0) ]).unwrap_err();
+
+    // The localized template only uses the parameters of the error, not the english description
+    let de = Catalog::new("de")
+        .with(ERR_MASKED_AXIS, "Achse {axis} ist gesperrt (Weg: {delta})")
+        .with(ErrorKind::Safety.code(), "Sicherheitsstopp");
+
+    assert_eq!(de.render_error(&err), "Achse 1 ist gesperrt (Weg: 2)");
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Safety);
+
+    // Catalogs without the specific template fall back to the template of the kind
+    let de = Catalog::new("de").with(ErrorKind::Safety.code(), "Sicherheitsstopp");
+    assert_eq!(de.render_error(&err), "Sicherheitsstopp");
+}
+
+#[test]
+fn error_kinds() {
+    use crate::cancel::CancelToken;
+    use crate::robs::SpeedOverride;
+
+    let err = SpeedOverride::new().set(5.0).unwrap_err();
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Config);
+
+    let token = CancelToken::new();
+    token.cancel();
+    assert_eq!(ErrorKind::of(&token.check().unwrap_err()), ErrorKind::Safety);
+
+    // Conversions keep the kind and the message
+    let sy = SyError::from_error(&err);
+    let crate::Error::Sy(inner) = &err else { panic!("Errors of the crate are `SyError`s") };
+    assert_eq!((sy.kind, sy.msg.as_str()), (ErrorKind::Config, inner.msg.as_str()));
+
+    // Errors of other types are sorted into their variants, IO errors are only remote failures for connections
+    let io : crate::Error = std::io::Error::new(std::io::ErrorKind::BrokenPipe, "Closed").into();
+    assert!(matches!(io, crate::Error::Io(_)));
+    assert_eq!(SyError::from_error(&io).kind, ErrorKind::Remote);
+
+    let file : crate::Error = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file").into();
+    assert_eq!(file.kind(), ErrorKind::Other);
+
+    let json : crate::Error = serde_json::from_str::<u32>("{").unwrap_err().into();
+    assert!(matches!(json, crate::Error::Json(_)));
+    assert_eq!(json.code(), ErrorKind::Config.code());
+
+    let other : crate::Error = core::fmt::Error.into();
+    assert!(matches!(other, crate::Error::Other(_)));
+}
@@ -0,0 +1,228 @@
+use glam::Vec3;
+
+use crate::gcode::emit::{emit_path, EmitOptions, Units};
+
+#[test]
+fn emit_path_offset_units() {
+    let opts = EmitOptions {
+        units: Units::Inches,
+        work_offset: Some(1),
+        origin: Vec3::new(25.4, 0.0, 0.0),
+        feed: Some(100.0),
+        precision: 2
+    };
+
+    let code = emit_path(&[ Vec3::new(25.4, 0.0, 0.0), Vec3::new(50.8, 25.4, 0.0) ], opts).unwrap();
+    let lines : Vec<&str> = code.lines().collect();
+
+    assert_eq!(lines, [ "G20 G90", "G54", "G0 X0.00 Y0.00 Z0.00", "G1 X1.00 Y1.00 Z0.00 F100.0" ]);
+}
+
+#[test]
+fn editor_syntax() {
+    use crate::gcode::edit::ProgramEditor;
+    use crate::gcode::word::parse_line;
+
+    let words = parse_line("g1 X10 (comment) y-2.5 ; rest").unwrap();
+    assert_eq!(words.len(), 3);
+    assert_eq!((words[0].letter, words[0].num()), ('G', Some(1.0)));
+    assert_eq!((words[2].letter, words[2].num()), ('Y', Some(-2.5)));
+    assert!(parse_line("G1 X10 (comment").is_err());
+
+    let mut editor = ProgramEditor::new("G0 X0\nG1 X10");
+    assert!(editor.set_line(1, "G1 X1O").unwrap().is_some(), "Invalid value");
+    assert!(editor.insert_line(0, "G90").unwrap().is_none());
+    assert_eq!(editor.check().len(), 1);
+    assert_eq!(editor.check()[0].line, 2);
+}
+
+#[test]
+fn target_named_frame() {
+    use crate::gcode::target::resolve_target;
+    use crate::gcode::word::parse_line;
+    use crate::rcs::{PointRef, Position, WorldObj};
+    use crate::sender::ModalState;
+
+    let wobj = WorldObj::zero()
+        .add_point_inline("fixture1", PointRef::new(
+            Position::new(100.0, 50.0, 0.0).to_wo()
+                .add_point_inline("slot", PointRef::new(Position::new(10.0, 0.0, 5.0)))
+        ));
+
+    let words = parse_line("G0 F\"fixture1\" P\"slot\" Z20").unwrap();
+    let modal = ModalState::default();
+    let target = resolve_target(&words, &wobj, Vec3::ZERO, &modal).unwrap();
+    assert_eq!(target, Vec3::new(110.0, 50.0, 20.0));
+
+    assert!(parse_line("G0 F\"fixture1").is_err());
+    assert!(resolve_target(&parse_line("G0 F\"none\"").unwrap(), &wobj, Vec3::ZERO, &modal).is_err());
+
+    // Units and relative coordinates are applied in the frame as for motion lines
+    let mut modal = ModalState::default();
+    let words = parse_line("G20 G91 G0 F\"fixture1\" X1").unwrap();
+    modal.update(&words).unwrap();
+    let target = resolve_target(&words, &wobj, Vec3::new(110.0, 50.0, 0.0), &modal).unwrap();
+    assert!((target - Vec3::new(135.4, 50.0, 0.0)).length() < 1e-4);
+}
+
+#[test]
+fn target_taught_work_offset() {
+    use crate::gcode::target::{resolve_target, work_offset};
+    use crate::gcode::word::parse_line;
+    use crate::rcs::{frame_from_points, WorldObj};
+    use crate::sender::ModalState;
+
+    // Fixture rotated by 90 degrees around Z
+    let mut wobj = WorldObj::zero();
+    wobj.teach_frame("G55", Vec3::new(100.0, 0.0, 0.0), Vec3::new(100.0, 20.0, 0.0), Vec3::new(90.0, 5.0, 0.0)).unwrap();
+
+    let mut modal = ModalState::default();
+    let words = parse_line("G0 G55 X10 Y5 Z0").unwrap();
+    modal.update(&words).unwrap();
+    let target = resolve_target(&words, &wobj, Vec3::ZERO, &modal).unwrap();
+    assert!((target - Vec3::new(95.0, 10.0, 0.0)).length() < 1e-4);
+
+    // Modal selection
+    let words = parse_line("G1 X10 Y0 Z0").unwrap();
+    assert_eq!(work_offset(&words), None);
+    modal.update(&words).unwrap();
+    assert_eq!(modal.work_offset, Some("G55"));
+    let target = resolve_target(&words, &wobj, Vec3::ZERO, &modal).unwrap();
+    assert!((target - Vec3::new(100.0, 10.0, 0.0)).length() < 1e-4);
+    assert_eq!(work_offset(&parse_line("G53").unwrap()), Some(None));
+
+    assert!(frame_from_points(Vec3::ZERO, Vec3::X * 10.0, Vec3::X * 20.0).is_err());
+    let words = parse_line("G0 G54 X0").unwrap();
+    modal.update(&words).unwrap();
+    assert!(resolve_target(&words, &wobj, Vec3::ZERO, &modal).is_err());
+}
+
+#[test]
+fn arc_center_and_radius() {
+    use crate::rcs::{arc_points, ArcCenter, Plane};
+
+    let start = Vec3::new(10.0, 0.0, 0.0);
+    let end = Vec3::new(0.0, 10.0, 0.0);
+
+    for center in [ ArcCenter::Offset(Vec3::new(-10.0, 0.0, 0.0)), ArcCenter::Radius(10.0) ] {
+        let points = arc_points(start, end, center, false, Plane::XY, 1.0).unwrap();
+        assert_eq!(*points.last().unwrap(), end);
+
+        for p in &points {
+            assert!((p.length() - 10.0).abs() < 1e-3, "{:?} is not on the arc", p);
+            assert!((p.x >= -1e-3) & (p.y >= -1e-3), "{:?} is on the wrong side", p);
+        }
+    }
+
+    assert!(arc_points(start, end, ArcCenter::Radius(2.0), false, Plane::XY, 1.0).is_err());
+}
+
+#[test]
+fn motion_arc_words() {
+    use crate::gcode::motion::{motion_points, Motion};
+    use crate::gcode::word::parse_line;
+    use crate::sender::ModalState;
+
+    let start = Vec3::new(10.0, 0.0, 0.0);
+    let mut modal = ModalState::default();
+
+    for line in [ "G3 X0 Y10 I-10 J0", "G3 X0 Y10 R10", "G91 G3 X-10 Y10 R10" ] {
+        let words = parse_line(line).unwrap();
+        modal.update(&words).unwrap();
+        assert_eq!(modal.motion, Motion::ArcCcw);
+
+        let points = motion_points(&words, &modal, start, 1.0).unwrap().unwrap();
+        assert!(points.len() > 10, "The arc of '{}' has not been split", line);
+        assert!((*points.last().unwrap() - Vec3::new(0.0, 10.0, 0.0)).length() < 1e-3);
+        assert!(points.iter().all(|p| (p.length() - 10.0).abs() < 1e-3));
+    }
+
+    // Modal linear movement and lines without coordinates
+    let mut modal = ModalState::default();
+    modal.update(&parse_line("G1 F200").unwrap()).unwrap();
+    assert!(motion_points(&parse_line("F100").unwrap(), &modal, start, 1.0).is_none());
+    assert_eq!(motion_points(&parse_line("Y5").unwrap(), &modal, start, 1.0).unwrap().unwrap(), [ Vec3::new(10.0, 5.0, 0.0) ]);
+
+    modal.update(&parse_line("G2").unwrap()).unwrap();
+    assert!(motion_points(&parse_line("X0 Y10").unwrap(), &modal, start, 1.0).unwrap().is_err(), "Missing center");
+}
+
+#[tokio::test]
+async fn motion_linear_feed() {
+    use syunit::*;
+
+    use crate::Robot;
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::gcode::motion::exec_motion;
+    use crate::gcode::word::parse_line;
+    use crate::sender::ModalState;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut desc = LinearXYDescriptor::new();
+    let mut modal = ModalState::default();
+
+    // Linear movements require a feed rate, given in units per minute
+    let words = parse_line("G1 X2").unwrap();
+    modal.update(&words).unwrap();
+    assert!(exec_motion(&mut rob, &mut desc, &words, &modal, 1.0, Factor::MAX).await.is_err());
+
+    modal.update(&parse_line("G20 F60").unwrap()).unwrap();
+    assert_eq!(modal.feed_speed(), Some(Velocity(25.4)));
+
+    let words = parse_line("G21 G1 X2 F600").unwrap();
+    modal.update(&words).unwrap();
+    assert_eq!(modal.feed_speed(), Some(Velocity(10.0)));
+    assert!(exec_motion(&mut rob, &mut desc, &words, &modal, 1.0, Factor::MAX).await.unwrap());
+    assert!((rob.phis()[0].0 - 2.0).abs() < 1e-3);
+}
+
+#[tokio::test]
+async fn motion_relative_lines() {
+    use syunit::*;
+
+    use crate::{Descriptor, Robot};
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::gcode::motion::exec_motion;
+    use crate::gcode::word::parse_line;
+    use crate::sender::ModalState;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut desc = LinearXYDescriptor::new();
+    let mut modal = ModalState::default();
+
+    // Each increment starts at the position reached by the line before
+    for line in [ "G91 G0 X2 Y1", "X3" ] {
+        let words = parse_line(line).unwrap();
+        modal.update(&words).unwrap();
+        assert!(exec_motion(&mut rob, &mut desc, &words, &modal, 1.0, Factor::MAX).await.unwrap());
+    }
+
+    assert!((rob.phis()[0].0 - 5.0).abs() < 1e-3);
+    assert!((rob.phis()[1].0 - 1.0).abs() < 1e-3);
+    assert!((desc.tcp().pos().x - 5.0).abs() < 1e-3);
+}
+
+#[test]
+fn m220_override() {
+    use crate::gcode::word::parse_line;
+    use crate::gcode::mcode::m220_factor;
+
+    assert!(m220_factor(&parse_line("G1 X10").unwrap()).is_none());
+    assert_eq!(m220_factor(&parse_line("M220 S50").unwrap()).unwrap().unwrap(), 0.5);
+    assert_eq!(m220_factor(&parse_line("M220").unwrap()).unwrap().unwrap(), 1.0);
+    assert!(m220_factor(&parse_line("M220 S250").unwrap()).unwrap().is_err());
+}
+
+#[test]
+fn m42_output_channel() {
+    use crate::gcode::word::parse_line;
+    use crate::gcode::mcode::m42_output;
+
+    assert_eq!(m42_output(&parse_line("M42 P2 S1").unwrap()).unwrap().unwrap(), (2, true));
+    assert_eq!(m42_output(&parse_line("M42 P0 S0").unwrap()).unwrap().unwrap(), (0, false));
+    assert!(m42_output(&parse_line("M42 S1").unwrap()).unwrap().is_err());
+    assert!(m42_output(&parse_line("M42 P-1 S1").unwrap()).unwrap().is_err());
+    assert!(m42_output(&parse_line("M42 P1.5 S1").unwrap()).unwrap().is_err());
+}
@@ -1,3 +1,4 @@
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use syact::math::movements::DefinedActuator;
@@ -10,7 +11,22 @@ use tokio::task::JoinSet;
 
 use crate::config::AngleConfig;
 use crate::prelude::StepperRobot;
-use crate::Robot;
+use crate::{Robot, Station};
+use crate::stat::ToolUsageTracker;
+
+mod cmd;
+mod config;
+mod desc;
+mod error;
+mod gcode;
+mod plan;
+mod prog;
+mod rcs;
+mod robs;
+mod sender;
+mod stat;
+mod telemetry;
+mod traj;
 
 // SimPin
 
@@ -46,6 +62,34 @@ use crate::Robot;
             Ok(())
         }
     }
+
+    /// A PWM output recording all duty cycles written into a shared log
+    pub struct SimPwm {
+        pub duties : Arc<Mutex<Vec<u16>>>
+    }
+
+    impl SimPwm {
+        pub fn new() -> Self {
+            Self {
+                duties: Arc::new(Mutex::new(Vec::new()))
+            }
+        }
+    }
+
+    impl embedded_hal::pwm::ErrorType for SimPwm {
+        type Error = embedded_hal::pwm::ErrorKind;
+    }
+
+    impl embedded_hal::pwm::SetDutyCycle for SimPwm {
+        fn max_duty_cycle(&self) -> u16 {
+            1000
+        }
+
+        fn set_duty_cycle(&mut self, duty : u16) -> Result<(), Self::Error> {
+            self.duties.lock().unwrap().push(duty);
+            Ok(())
+        }
+    }
 // 
 
 #[derive(StepperActuatorGroup)]
@@ -77,6 +121,37 @@ impl TestXYRobot {
     }
 }
 
+#[derive(Default)]
+pub struct TestStation {
+    pub interlock : bool,
+    pub usage : Option<ToolUsageTracker>
+}
+
+impl Station<TestXYRobotComponents, dyn StepperActuator, 2> for TestStation {
+    type Robot = TestXYRobot;
+
+    async fn calibrate(&mut self, _rob : &mut Self::Robot) -> Result<(), crate::Error> {
+        Ok(())
+    }
+
+    async fn home(&mut self, rob : &mut Self::Robot) -> Result<(), crate::Error> {
+        rob.set_homed();
+        Ok(())
+    }
+
+    fn interlock(&self) -> bool {
+        self.interlock
+    }
+
+    fn tool_usage(&self) -> Option<&ToolUsageTracker> {
+        self.usage.as_ref()
+    }
+
+    fn tool_usage_mut(&mut self) -> Option<&mut ToolUsageTracker> {
+        self.usage.as_mut()
+    }
+}
+
 #[tokio::test]
 async fn move_j_test() {
     let mut rob = TestXYRobot::new_simple();
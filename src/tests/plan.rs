@@ -0,0 +1,25 @@
+use syunit::*;
+
+use crate::plan::RrtConnect;
+
+#[test]
+fn rrt_connect_around_wall() {
+    // A wall at x = 0 with a gap at the top
+    let wall = |phis : &[Phi; 2]| ((phis[0].0.abs() < 0.2) & (phis[1].0 < 0.5)).then(|| "Wall".to_owned());
+    let planner = RrtConnect::new([ (Phi(-1.0), Phi(1.0)); 2 ], &wall);
+
+    let start = [ Phi(-0.8), Phi(-0.8) ];
+    let goal = [ Phi(0.8), Phi(-0.8) ];
+    let path = planner.plan(start, goal).unwrap();
+
+    assert_eq!(path.first().unwrap()[0].0, start[0].0);
+    assert_eq!(path.last().unwrap()[0].0, goal[0].0);
+    assert!(path.len() > 2);
+
+    for seg in path.windows(2) {
+        assert!(planner.motion_free(&seg[0], &seg[1]));
+    }
+
+    // Goal inside of the wall
+    assert!(planner.plan(start, [ Phi(0.0), Phi(0.0) ]).is_err());
+}
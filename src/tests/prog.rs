@@ -0,0 +1,48 @@
+use crate::prog::{Args, ParamDecl};
+
+#[test]
+fn args_bind() {
+    let params = [
+        ParamDecl::new("count").range(1.0, 10.0),
+        ParamDecl::new("offset").default_value(2.5)
+    ];
+
+    let args = Args::new().arg("count", 4.0).bind(&params).unwrap();
+    assert_eq!(args.get("count"), Some(4.0));
+    assert_eq!(args.get("offset"), Some(2.5));
+
+    assert!(Args::new().bind(&params).is_err(), "Missing required argument");
+    assert!(Args::new().arg("count", 20.0).bind(&params).is_err(), "Out of range");
+    assert!(Args::new().arg("count", 1.0).arg("speed", 1.0).bind(&params).is_err(), "Unknown argument");
+}
+
+#[test]
+fn program_approach_contacts() {
+    use glam::Vec3;
+    use syunit::*;
+
+    use crate::prog::{ApproachRetract, Program, Step};
+    use crate::rcs::{Point, Position};
+
+    let contact = Position::new(10.0, 0.0, 0.0);
+    let prog : Program<2> = Program::new("pick_place")
+        .pick(&contact)
+        .approach(ApproachRetract::tool_z(20.0, Factor::MAX, Factor::new(0.2)))
+        .place(&contact);
+
+    // Without strategy the contact is moved to directly
+    assert!(matches!(prog.steps[0], Step::MoveP { .. }));
+    assert!(matches!(prog.steps[1], Step::ToolOn));
+
+    // Approach, contact, action, retract
+    assert_eq!(prog.steps.len(), 6);
+    match (&prog.steps[2], &prog.steps[3], &prog.steps[5]) {
+        (Step::MoveP { pos: approach, .. }, Step::MoveP { pos, speed_f }, Step::MoveP { pos: retract, .. }) => {
+            assert_eq!(*approach.pos(), Vec3::new(10.0, 0.0, -20.0));
+            assert_eq!((*pos.pos(), speed_f.0), (*contact.pos(), 0.2));
+            assert_eq!(*retract.pos(), Vec3::new(10.0, 0.0, -20.0));
+        },
+        _ => panic!("Invalid steps")
+    }
+    assert!(matches!(prog.steps[4], Step::ToolOff));
+}
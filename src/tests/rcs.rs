@@ -0,0 +1,56 @@
+use glam::Vec3;
+
+use crate::rcs::Position;
+
+#[test]
+fn frame_tree_transforms() {
+    use core::f32::consts::FRAC_PI_2;
+
+    use glam::Mat3;
+
+    use crate::rcs::{FrameTree, Point, PointRef, WorldObj, ROOT_FRAME};
+
+    let mut tree = FrameTree::new();
+    tree.add("table", None, Position::new_ori(Vec3::new(100.0, 0.0, 0.0), Mat3::from_rotation_z(FRAC_PI_2))).unwrap();
+    tree.add("fixture", Some("table"), Position::new(10.0, 0.0, 0.0)).unwrap();
+    tree.add("camera", Some(ROOT_FRAME), Position::new(0.0, 0.0, 50.0)).unwrap();
+
+    assert!((*tree.global("fixture").unwrap().pos() - Vec3::new(100.0, 10.0, 0.0)).length() < 1e-4);
+    assert!((tree.convert(Vec3::ZERO, "fixture", "camera").unwrap() - Vec3::new(100.0, 10.0, -50.0)).length() < 1e-4);
+
+    // Moving the parent moves the children
+    tree.set_local("table", Position::new(0.0, 0.0, 0.0)).unwrap();
+    assert!((*tree.global("fixture").unwrap().pos() - Vec3::new(10.0, 0.0, 0.0)).length() < 1e-4);
+
+    assert!(tree.add("table", Some("fixture"), Position::zero()).is_err());
+    assert!(tree.add("tool", Some("none"), Position::zero()).is_err());
+    assert!(tree.remove("table").is_err());
+
+    // Frames created from a world object follow its points
+    let wobj = WorldObj::zero()
+        .add_point_inline("rail", PointRef::new(WorldObj::zero()
+            .add_point_inline("carriage", PointRef::new(Position::new(5.0, 0.0, 0.0)))));
+    let tree = FrameTree::from_world_obj(&wobj);
+
+    assert!((*tree.global("rail/carriage").unwrap().pos() - Vec3::new(5.0, 0.0, 0.0)).length() < 1e-4);
+
+    wobj.point("rail").unwrap().borrow_mut().shift(Vec3::new(0.0, 20.0, 0.0));
+    assert!((*tree.global("rail/carriage").unwrap().pos() - Vec3::new(5.0, 20.0, 0.0)).length() < 1e-4);
+}
+
+#[test]
+fn split_linear_pose_len() {
+    use crate::rcs::Point;
+    use crate::rcs::math::split_linear_pose;
+
+    let start = Position::new(0.0, 0.0, 0.0);
+    let end = Position::new(10.0, 0.0, 0.0);
+
+    let poses = split_linear_pose(&start, &end, 3.0).unwrap();
+    assert_eq!(poses.len(), 5);
+    assert_eq!(*poses.last().unwrap().pos(), Vec3::new(10.0, 0.0, 0.0));
+
+    for split_len in [ 0.0, -1.0, f32::NAN ] {
+        assert!(split_linear_pose(&start, &end, split_len).is_err());
+    }
+}
@@ -0,0 +1,563 @@
+use syunit::*;
+
+use crate::robs::{LimitZone, SoftLimit, SoftLimits};
+
+#[test]
+fn soft_limit_margins() {
+    let limits = SoftLimits::new([
+        SoftLimit::new(Some(Phi(-1.0)), Some(Phi(1.0)), 0.2, 0.5),
+        SoftLimit::NONE
+    ]);
+
+    assert_eq!(limits.zone(&[ Phi(0.0), Phi(100.0) ]), LimitZone::Free);
+    assert_eq!(limits.factor(&[ Phi(0.0), Phi(100.0) ]), 1.0);
+
+    assert!(matches!(limits.zone(&[ Phi(0.9), Phi(0.0) ]), LimitZone::Margin { axis: 0, .. }));
+    assert!((limits.factor(&[ Phi(0.9), Phi(0.0) ]) - 0.75).abs() < 1e-5);
+
+    assert_eq!(limits.zone(&[ Phi(-1.1), Phi(0.0) ]), LimitZone::Exceeded { axis: 0 });
+    assert!((limits.factor(&[ Phi(1.0), Phi(0.0) ]) - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn soft_limit_jog_stop() {
+    let limits = SoftLimits::new([
+        SoftLimit::new(Some(Phi(-1.0)), Some(Phi(1.0)), 0.2, 0.5),
+        SoftLimit::NONE
+    ]);
+
+    let step = [ Delta(0.1), Delta(0.1) ];
+    assert_eq!(limits.limit_jog(&[ Phi(0.0), Phi(0.0) ], &step).1, None);
+
+    // The distance is kept in the margin zone, the speed is reduced instead
+    let (deltas, stop) = limits.limit_jog(&[ Phi(0.85), Phi(0.0) ], &step);
+    assert!((deltas[0].0 - 0.1).abs() < 1e-5);
+    assert!((deltas[1].0 - 0.1).abs() < 1e-5);
+    assert_eq!(stop, None);
+    assert!(limits.factor(&[ Phi(0.95), Phi(0.0) ]) < 1.0);
+
+    // Stopping exactly at the limit
+    let (deltas, stop) = limits.limit_jog(&[ Phi(0.95), Phi(0.0) ], &step);
+    assert!((deltas[0].0 - 0.05).abs() < 1e-5);
+    assert_eq!(stop, Some(0));
+
+    let (deltas, stop) = limits.limit_jog(&[ Phi(1.0), Phi(0.0) ], &step);
+    assert_eq!(deltas[0].0, 0.0);
+    assert_eq!(stop, Some(0));
+
+    // Moving away from the limit
+    assert_eq!(limits.limit_jog(&[ Phi(1.0), Phi(0.0) ], &[ Delta(-0.1), Delta(0.0) ]).0[0].0, -0.1);
+}
+
+#[test]
+fn park_strategies() {
+    use crate::desc::common::LinearXYZDescriptor;
+    use crate::robs::{Park, ParkStrategy};
+
+    let mut desc = LinearXYZDescriptor::new();
+    let park = Park::new([ Phi(0.0), Phi(0.0), Phi(100.0) ]);
+    let start = [ Phi(50.0), Phi(0.0), Phi(0.0) ];
+
+    // Free workspace
+    let (strategy, waypoints) = park.plan(start, &mut desc, &|_| None).unwrap();
+    assert_eq!(strategy, ParkStrategy::Direct);
+    assert_eq!(waypoints.len(), 1);
+
+    // A wall blocks low positions between x = 10 and x = 40
+    let wall = |phis : &[Phi; 3]| ((phis[0].0 > 10.0) & (phis[0].0 < 40.0) & (phis[2].0 < 40.0)).then(|| "Wall".to_owned());
+    let (strategy, waypoints) = park.plan(start, &mut desc, &wall).unwrap();
+    assert_eq!(strategy, ParkStrategy::Retract);
+    assert!((waypoints[0][2].0 - 50.0).abs() < 1e-3);
+
+    // Nothing but the park pose itself is free
+    let blocked = |phis : &[Phi; 3]| (phis[2].0 < 100.0).then(|| "Blocked".to_owned());
+    assert!(park.plan(start, &mut desc, &blocked).is_err());
+}
+
+#[test]
+fn axis_masking() {
+    use crate::Robot;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    rob.set_masked(1, true).unwrap();
+    assert!(rob.set_masked(2, true).is_err());
+
+    assert!(rob.check_masked(&[ Delta(10.0), Delta(0.0) ]).is_ok());
+    assert!(rob.check_masked(&[ Delta(10.0), Delta(1.0) ]).is_err());
+
+    let limits = rob.freeze_masked([ (Phi(-100.0), Phi(100.0)); 2 ]);
+    assert_eq!(limits[0].1.0, 100.0);
+    assert_eq!(limits[1].0.0, limits[1].1.0);
+
+    // Homing requires all the axes
+    assert!(rob.check_unmasked().is_err());
+    rob.set_masked(1, false).unwrap();
+    assert!(rob.check_unmasked().is_ok());
+}
+
+#[test]
+fn program_scopes() {
+    use glam::Vec3;
+
+    use crate::Robot;
+    use crate::rcs::{Point, Position};
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    rob.vars_mut().scope.speed_limit = Some(Velocity(50.0));
+
+    rob.push_scope();
+    rob.vars_mut().scope.offset = Vec3::new(10.0, 0.0, 0.0);
+    rob.vars_mut().scope.speed_limit = Some(Velocity(5.0));
+
+    // Absolute targets are shifted by the offset, speeds are limited
+    let target = rob.resolve_target(Position::new(1.0, 2.0, 3.0));
+    assert_eq!(*target.pos(), Vec3::new(11.0, 2.0, 3.0));
+    assert_eq!(rob.limit_speed(Velocity(20.0)).0, 5.0);
+    assert_eq!(rob.limit_speed(Velocity(2.0)).0, 2.0);
+
+    rob.pop_scope();
+    assert_eq!(rob.vars().scope.offset, Vec3::ZERO);
+    assert_eq!(rob.vars().scope.speed_limit.map(|v| v.0), Some(50.0));
+
+    // Unbalanced pops reset to the defaults
+    rob.pop_scope();
+    assert!(rob.vars().scope.speed_limit.is_none());
+}
+
+#[tokio::test]
+async fn motion_backends() {
+    use core::time::Duration;
+    use std::sync::{Arc, Mutex};
+
+    use crate::robs::{BackendConfig, BackendKind, BackendRegistry, MotionBackend};
+
+    struct StepBoard(Arc<Mutex<Vec<i64>>>, Arc<Mutex<usize>>);
+
+    impl MotionBackend for StepBoard {
+        fn send_steps(&mut self, steps : i64, _duration : Duration) -> Result<(), crate::Error> {
+            self.0.lock().unwrap().push(steps);
+            Ok(())
+        }
+
+        fn gamma(&mut self) -> Result<Gamma, crate::Error> {
+            Ok(Gamma(self.0.lock().unwrap().iter().sum::<i64>() as f32 / 10.0))
+        }
+
+        fn stop(&mut self) -> Result<(), crate::Error> {
+            *self.1.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    let steps = Arc::new(Mutex::new(Vec::new()));
+    let stops = Arc::new(Mutex::new(0));
+    let mut registry = BackendRegistry::new();
+
+    let (log, stop_log) = (steps.clone(), stops.clone());
+    registry.register(BackendKind::StepStream, Box::new(move |_| 
+        Ok(Box::new(StepBoard(log.clone(), stop_log.clone())) as Box<dyn MotionBackend>)));
+
+    let stream = BackendConfig { kind: BackendKind::StepStream, steps_per_unit: Some(10.0), tolerance: Some(0.1), ..Default::default() };
+    let mut group = registry.create_group(&[ BackendConfig::default(), stream.clone() ]).unwrap();
+    assert!(!group.is_external(0) & group.is_external(1));
+
+    // Rounding remainders are carried over
+    group.drive_abs([ Gamma::ZERO, Gamma(0.25) ], [ Velocity(1000.0); 2 ], 1.0, None).await.unwrap();
+    group.drive_abs([ Gamma::ZERO, Gamma(0.5) ], [ Velocity(1000.0); 2 ], 1.0, None).await.unwrap();
+    assert_eq!(steps.lock().unwrap().iter().sum::<i64>(), 5);
+    assert_eq!(group.merge_gammas([ Gamma(1.0), Gamma::ZERO ]), [ Gamma(1.0), Gamma(0.5) ]);
+
+    // Aborted movements stop the external components
+    let slow = group.drive_abs([ Gamma::ZERO, Gamma(100.0) ], [ Velocity(1.0); 2 ], 1.0, None);
+    assert!(tokio::time::timeout(Duration::from_millis(10), slow).await.is_err());
+    assert_eq!(*stops.lock().unwrap(), 1);
+
+    // The positions of aborted movements are re-synced with the controller instead of taking over the target
+    *steps.lock().unwrap() = vec![ 500 ];
+    drop(tokio::time::timeout(Duration::from_millis(10), 
+        group.drive_abs([ Gamma::ZERO, Gamma(200.0) ], [ Velocity(1.0); 2 ], 1.0, None)).await);
+    assert_eq!(group.merge_gammas([ Gamma::ZERO; 2 ])[1], Gamma(150.0));
+
+    // Movements with a common duration take as long as given
+    let start = std::time::Instant::now();
+    group.drive_abs_within([ Gamma::ZERO, Gamma(151.0) ], 0.05, None).await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(group.merge_gammas([ Gamma::ZERO; 2 ])[1], Gamma(151.0));
+
+    // Missing factories and incomplete configurations are rejected
+    let position = BackendConfig { kind: BackendKind::Position, ..Default::default() };
+    assert!(registry.create(&position).is_err());
+    assert!(registry.create(&BackendConfig { steps_per_unit: None, ..stream }).is_err());
+}
+
+#[test]
+fn axis_couplings_shared_target() {
+    use syact::{StepperActuatorGroup, StepperConst};
+    use syact::act::{LinearAxis, StepperActuator};
+    use syact::act::stepper::{ComplexStepper, GenericPWM};
+
+    use crate::Robot;
+    use crate::config::{AngleConfig, AxisCoupling};
+    use crate::prelude::StepperRobot;
+    use crate::tests::SimPin;
+
+    #[derive(StepperActuatorGroup)]
+    struct XYZComponents {
+        x : LinearAxis<ComplexStepper<SimPin, SimPin>>,
+        y : LinearAxis<ComplexStepper<SimPin, SimPin>>,
+        z : LinearAxis<ComplexStepper<SimPin, SimPin>>
+    }
+
+    let axis = || LinearAxis::new(
+        ComplexStepper::new(GenericPWM::new(SimPin::new(), SimPin::new()).unwrap(), StepperConst::GEN).unwrap(), 8.0
+    );
+
+    let mut rob : StepperRobot<XYZComponents, dyn StepperActuator, 3> = StepperRobot::new([ AngleConfig::EMPTY; 3 ], 
+        XYZComponents { x: axis(), y: axis(), z: axis() }, vec![]);
+
+    // Both axes move the last one
+    rob.set_couplings(vec![
+        AxisCoupling { source: 0, target: 2, factor: 0.5 },
+        AxisCoupling { source: 1, target: 2, factor: -0.25 }
+    ]).unwrap();
+
+    let phis = [ Phi(2.0), Phi(4.0), Phi(1.0) ];
+    let gammas = rob.gammas_from_phis(phis);
+
+    assert!((gammas[2].0 - 1.0).abs() < 1e-5);
+
+    for (phi, back) in phis.iter().zip(rob.phis_from_gammas(gammas)) {
+        assert!((phi.0 - back.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn speed_override_scaling() {
+    use crate::Robot;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    assert_eq!(rob.scaled_speed_f(Factor::MAX, 0.5), Factor::new(0.5));
+
+    // The override applies without any velocities being configured
+    rob.set_speed_override(0.5).unwrap();
+    assert_eq!(rob.scaled_speed_f(Factor::MAX, 0.5), Factor::new(0.25));
+
+    // Faster overrides are limited to the maximum speed, zero overrides to the minimum
+    rob.set_speed_override(2.0).unwrap();
+    assert_eq!(rob.scaled_speed_f(Factor::MAX, 1.0), Factor::MAX);
+
+    rob.speed_override().unwrap().set(0.0).unwrap();
+    assert_eq!(rob.scaled_speed_f(Factor::MAX, 1.0), Factor::new(0.01));
+}
+
+#[tokio::test]
+async fn speed_override_holds_moves() {
+    use core::time::Duration;
+
+    use crate::Robot;
+    use crate::sender::ExecControl;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let control = ExecControl::with_speed_override(rob.speed_override().unwrap().clone());
+
+    // Pausing the execution holds the synchronous moves used by gcode as well
+    control.pause();
+    let held = tokio::time::timeout(Duration::from_millis(50), rob.move_j_sync([ Delta(1.0), Delta(1.0) ], Factor::MAX)).await;
+    assert!(held.is_err());
+    assert_eq!(rob.gammas(), [ Gamma::ZERO; 2 ]);
+
+    control.resume();
+    rob.move_j_sync([ Delta(1.0), Delta(1.0) ], Factor::MAX).await.unwrap();
+    assert!(rob.gammas()[0].0 > 0.0);
+}
+
+#[test]
+fn speed_factor_for_time() {
+    use crate::Robot;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let deltas = [ Delta(10.0), Delta(5.0) ];
+
+    // Unknown velocities drive at full speed
+    assert_eq!(rob.speed_f_for_time(&deltas, 1.0), Factor::MAX);
+
+    // The first component requires 10 / 2s = 5 units per second of its 20
+    rob.set_omega_max([ Velocity(20.0), Velocity(20.0) ]);
+    assert_eq!(rob.speed_f_for_time(&deltas, 2.0), Factor::new(0.25));
+    assert_eq!(rob.speed_f_for_time(&deltas, 0.1), Factor::MAX);
+}
+
+#[tokio::test]
+async fn cancel_token_aborts_moves() {
+    use crate::Robot;
+    use crate::cancel::CancelToken;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let cancel = CancelToken::new();
+    rob.set_cancel_token(cancel.clone());
+
+    cancel.cancel();
+    assert!(rob.move_j([ Delta(1.0), Delta(1.0) ], Factor::MAX).await.is_err());
+    assert_eq!(rob.gammas(), [ Gamma::ZERO; 2 ]);
+}
+
+#[tokio::test]
+async fn estop_aborts_moves() {
+    use core::time::Duration;
+
+    use crate::{ErrorKind, Robot};
+    use crate::estop::EStop;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let estop = EStop::new();
+    rob.set_estop(estop.clone());
+
+    // A triggered stop rejects new moves
+    estop.trigger("Door opened");
+    let err = rob.move_j_sync([ Delta(1.0), Delta(1.0) ], Factor::MAX).await.unwrap_err();
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Safety);
+    assert_eq!(rob.gammas(), [ Gamma::ZERO; 2 ]);
+
+    // Triggering the stop aborts the running move
+    estop.reset();
+    let trigger = async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        estop.trigger("Light curtain");
+    };
+
+    let (res, _) = tokio::join!(rob.move_abs_j_sync([ Phi(1000.0), Phi(1000.0) ], Factor::MAX), trigger);
+    assert_eq!(ErrorKind::of(&res.unwrap_err()), ErrorKind::Safety);
+    assert!(rob.gammas()[0].0 < 1000.0);
+}
+
+#[tokio::test]
+async fn path_moves_scope_offset() {
+    use glam::Vec3;
+
+    use crate::Robot;
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::rcs::{Linear, Position};
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut desc = LinearXYDescriptor::new();
+
+    rob.push_scope();
+    rob.vars_mut().scope.offset = Vec3::new(10.0, 0.0, 0.0);
+
+    // Absolute path targets are shifted by the offset, relative movements are not
+    rob.move_path(&mut desc, &Linear, Position::new(2.0, 0.0, 0.0), 1.0, Velocity(100.0)).await.unwrap();
+    assert!((rob.phis()[0].0 - 12.0).abs() < 1e-3);
+
+    rob.move_l(&mut desc, Vec3::new(0.0, 1.0, 0.0), 1.0, Velocity(100.0)).await.unwrap();
+    assert!((rob.phis()[0].0 - 12.0).abs() < 1e-3);
+    assert!((rob.phis()[1].0 - 1.0).abs() < 1e-3);
+
+    rob.move_abs_l(&mut desc, Vec3::new(5.0, 1.0, 0.0), 1.0, Velocity(100.0)).await.unwrap();
+    assert!((rob.phis()[0].0 - 15.0).abs() < 1e-3);
+
+    rob.pop_scope();
+}
+
+#[tokio::test]
+async fn laser_gated_during_rapids() {
+    use glam::Vec3;
+
+    use crate::{ErrorKind, Robot};
+    use crate::config::AngleConfig;
+    use crate::estop::EStop;
+    use crate::robs::tool::Laser;
+    use crate::tests::{SimPwm, TestStation, TestXYRobot, TestXYRobotComponents};
+
+    let pwm = SimPwm::new();
+    let duties = pwm.duties.clone();
+
+    let mut rob = TestXYRobot::new([ AngleConfig::EMPTY; 2 ], TestXYRobotComponents::new(), vec![
+        Box::new(Laser::new(pwm, Vec3::Z, 0.1))
+    ]);
+    rob.set_tool_id(Some(0));
+
+    // The laser cannot fire without the interlock of the station
+    let mut stat = TestStation::default();
+    rob.set_laser_power(0.5).unwrap();
+    assert_eq!(ErrorKind::of(&rob.fire_laser(&stat).unwrap_err()), ErrorKind::Safety);
+    assert!(!rob.get_tool().unwrap().laser_tool().unwrap().is_firing());
+
+    stat.interlock = true;
+    rob.fire_laser(&stat).unwrap();
+    assert_eq!(duties.lock().unwrap().last(), Some(&500));
+
+    // Rapids (like `G0`) suspend the beam and restore it afterwards
+    duties.lock().unwrap().clear();
+    rob.move_abs_j_sync([ Phi(1.0), Phi(1.0) ], Factor::MAX).await.unwrap();
+    assert_eq!(*duties.lock().unwrap(), [ 0, 500 ]);
+
+    duties.lock().unwrap().clear();
+    rob.move_j_sync([ Delta(1.0), Delta(1.0) ], Factor::MAX).await.unwrap();
+    assert_eq!(*duties.lock().unwrap(), [ 0, 500 ]);
+
+    // An aborted rapid leaves the laser off
+    let estop = EStop::new();
+    rob.set_estop(estop.clone());
+    estop.trigger("Door opened");
+
+    assert!(rob.move_j_sync([ Delta(1.0), Delta(1.0) ], Factor::MAX).await.is_err());
+    assert!(!rob.get_tool().unwrap().laser_tool().unwrap().is_firing());
+    assert_eq!(duties.lock().unwrap().last(), Some(&0));
+}
+
+#[test]
+fn tool_ident_resistor_ladder() {
+    use core::cell::Cell;
+
+    use crate::ErrorKind;
+    use crate::robs::tool::{ResistorLadder, ToolIdent};
+
+    let voltage = Cell::new(0.0);
+    let mut ladder = ResistorLadder::new(|| Ok(voltage.get()), vec![ 0.25, 0.5, 0.75 ], 1.0, 0.05);
+
+    voltage.set(0.52);
+    assert_eq!(ladder.read_tool_id().unwrap(), Some(1));
+    assert!(ladder.verify(Some(1)).is_ok());
+    assert!(ladder.verify(Some(0)).is_err());
+
+    voltage.set(0.98);
+    assert_eq!(ladder.read_tool_id().unwrap(), None);
+    assert!(ladder.verify(None).is_ok());
+
+    // Voltages between the levels identify no tool
+    voltage.set(0.62);
+    assert_eq!(ErrorKind::of(&ladder.read_tool_id().unwrap_err()), ErrorKind::Component);
+    assert!(ladder.verify(None).is_err());
+}
+
+#[tokio::test]
+async fn gantry_divergence_faults() {
+    use crate::ErrorKind;
+    use crate::robs::{Gantry, GantrySide};
+
+    struct Side {
+        gamma : Gamma,
+        slip : f32,
+        halted : bool
+    }
+
+    impl Side {
+        fn new(slip : f32) -> Self {
+            Self { gamma: Gamma::ZERO, slip, halted: false }
+        }
+    }
+
+    impl GantrySide for Side {
+        fn gamma(&self) -> Gamma {
+            self.gamma
+        }
+
+        async fn drive_rel(&mut self, delta : Delta, _speed_f : Factor) -> Result<(), crate::Error> {
+            self.halted = false;
+            self.gamma = Gamma(self.gamma.0 + delta.0 * (1.0 - self.slip));
+            Ok(())
+        }
+
+        async fn home(&mut self, home : Gamma) -> Result<(), crate::Error> {
+            self.gamma = home;
+            Ok(())
+        }
+
+        fn halt(&mut self) {
+            self.halted = true;
+        }
+    }
+
+    // The secondary side loses a quarter of its steps
+    let mut gantry = Gantry::new(Side::new(0.0), Side::new(0.25), 4.0);
+
+    gantry.drive_rel(Delta(10.0), Factor::MAX).await.unwrap();
+    assert!(!gantry.is_faulted());
+    assert!(!gantry.primary.halted & !gantry.secondary.halted);
+
+    // Diverging further than allowed halts both sides
+    let err = gantry.drive_rel(Delta(10.0), Factor::MAX).await.unwrap_err();
+    assert!(matches!(err, crate::Error::Gantry(ref fault) if fault.primary == Gamma(20.0) && fault.secondary == Gamma(15.0)));
+    assert!(gantry.is_faulted());
+    assert!(gantry.primary.halted & gantry.secondary.halted);
+
+    // Faulted gantries reject movements until homed again
+    assert_eq!(ErrorKind::of(&gantry.drive_abs(Gamma::ZERO, Factor::MAX).await.unwrap_err()), ErrorKind::Safety);
+    assert_eq!(gantry.gamma(), Gamma(20.0));
+
+    gantry.home(Gamma::ZERO).await.unwrap();
+    assert!(!gantry.is_faulted());
+    assert_eq!(gantry.divergence(), 0.0);
+    gantry.drive_abs(Gamma(5.0), Factor::MAX).await.unwrap();
+}
+
+#[tokio::test]
+async fn startup_ramp() {
+    use core::time::Duration;
+
+    use crate::ErrorKind;
+    use crate::robs::{PowerStage, StartupSequence};
+
+    #[derive(Default)]
+    struct Stage {
+        enabled : Vec<usize>,
+        currents : Vec<(usize, f32)>,
+        fail_at : Option<f32>,
+        limits : Vec<bool>
+    }
+
+    impl PowerStage for Stage {
+        fn enable(&mut self, axis : usize) -> Result<(), crate::Error> {
+            self.enabled.push(axis);
+            Ok(())
+        }
+
+        fn set_holding_current(&mut self, axis : usize, factor : f32) -> Result<(), crate::Error> {
+            if self.fail_at.is_some_and(|f| factor >= f) {
+                return Err(crate::SyError::component("Driver overcurrent").into());
+            }
+
+            self.currents.push((axis, factor));
+            Ok(())
+        }
+
+        fn limit_states(&mut self) -> Result<Vec<bool>, crate::Error> {
+            Ok(self.limits.clone())
+        }
+    }
+
+    let seq = StartupSequence {
+        enable_delay: Duration::ZERO,
+        ramp_steps: 4,
+        ramp_time: Duration::from_millis(20),
+        expected_limits: Some(vec![ false, false ]),
+        require_homing: true
+    };
+
+    // Each axis is enabled and ramped up to the full holding current before the next one
+    let mut stage = Stage { limits: vec![ false, false ], ..Default::default() };
+    seq.run(&mut stage, 2).await.unwrap();
+
+    assert_eq!(stage.enabled, [ 0, 1 ]);
+    assert_eq!(stage.currents, [ (0, 0.25), (0, 0.5), (0, 0.75), (0, 1.0), (1, 0.25), (1, 0.5), (1, 0.75), (1, 1.0) ]);
+
+    // A failure during the ramp aborts the sequence, the remaining axes stay disabled
+    let mut stage = Stage { fail_at: Some(0.75), limits: vec![ false, false ], ..Default::default() };
+    let err = seq.run(&mut stage, 2).await.unwrap_err();
+
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Component);
+    assert_eq!(stage.enabled, [ 0 ]);
+    assert_eq!(stage.currents, [ (0, 0.25), (0, 0.5) ]);
+
+    // Unexpected limit switch states after the ramp are a safety error
+    let mut stage = Stage { limits: vec![ true, false ], ..Default::default() };
+    assert_eq!(ErrorKind::of(&seq.run(&mut stage, 2).await.unwrap_err()), ErrorKind::Safety);
+}
@@ -0,0 +1,223 @@
+use std::sync::Mutex;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Descriptor, Interpreter, Robot};
+use crate::sender::{ExecControl, ExecState, Sender, SenderState};
+
+/// Interpreter returning the lines executed
+struct Echo;
+
+impl<G, R, D, S, T, const C : usize> Interpreter<G, R, D, S, T, String, C> for Echo 
+where
+    G : SyncActuatorGroup<T, C>,
+    R : Robot<G, T, C>,
+    D : Descriptor<C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static
+{
+    fn interpret(&self, _rob : &mut R, _desc : &mut D, _stat : &mut S, code : &str) -> Vec<String> {
+        vec![ code.to_owned() ]
+    }
+}
+
+/// Interpreter recording the lines executed
+#[derive(Default)]
+struct Log {
+    lines : Mutex<Vec<String>>
+}
+
+impl Log {
+    fn len(&self) -> usize {
+        self.lines.lock().unwrap().len()
+    }
+}
+
+impl<G, R, D, S, T, const C : usize> Interpreter<G, R, D, S, T, (), C> for Log 
+where
+    G : SyncActuatorGroup<T, C>,
+    R : Robot<G, T, C>,
+    D : Descriptor<C>,
+    T : SyncActuator + DefinedActuator + ?Sized + 'static
+{
+    fn interpret(&self, _rob : &mut R, _desc : &mut D, _stat : &mut S, code : &str) -> Vec<()> {
+        self.lines.lock().unwrap().push(code.to_owned());
+        Vec::new()
+    }
+}
+
+/// Waits until the execution has been paused, checks the number of lines `executed` until then and resumes it
+async fn resume_paused(control : &ExecControl, log : &Log, executed : usize) {
+    while control.state() != ExecState::Paused {
+        tokio::task::yield_now().await;
+    }
+
+    assert_eq!(log.len(), executed);
+    control.resume();
+}
+
+#[test]
+fn sender_pause_resume() {
+    let mut sender = Sender::new("G0 X10\n; comment\n\nG0 Y10\nG0 Z10");
+    assert_eq!(sender.lines().len(), 3);
+
+    let (index, line) = sender.next_line().unwrap();
+    assert_eq!((index, line), (0, "G0 X10"));
+    assert!(sender.next_line().is_none(), "Must wait for acknowledgement");
+    sender.ack(0).unwrap();
+
+    sender.pause();
+    assert!(sender.next_line().is_none());
+    sender.resume();
+
+    let (index, _) = sender.next_line().unwrap();
+    sender.stop();
+    assert_eq!(sender.progress().acked, 1);
+
+    sender.resume_from(index).unwrap();
+    for i in 1 .. 3 {
+        assert_eq!(sender.next_line().unwrap().0, i);
+        sender.ack(i).unwrap();
+    }
+
+    assert_eq!(sender.state(), SenderState::Finished);
+    assert_eq!(sender.progress().percent, 100.0);
+}
+
+#[test]
+fn exec_control_modal_state() {
+    use crate::gcode::arc::Plane;
+    use crate::gcode::word::parse_line;
+    use crate::sender::ModalState;
+
+    let control = ExecControl::new();
+    control.speed_override().set(0.5).unwrap();
+
+    control.pause();
+    assert_eq!(control.state(), ExecState::Paused);
+    assert_eq!(control.speed_override().get(), 0.0);
+
+    control.resume();
+    assert_eq!(control.state(), ExecState::Running);
+    assert_eq!(control.speed_override().get(), 0.5);
+
+    let mut modal = ModalState::default();
+    modal.update(&parse_line("G91 G18 F200 T2").unwrap()).unwrap();
+    assert!(modal.relative);
+    assert_eq!((modal.plane, modal.feed, modal.tool), (Plane::XZ, Some(200.0), Some(2)));
+
+    // Non-integral and negative codes are rejected without changing the state
+    for line in [ "G90 G91.1", "G-1", "T1.5", "G90 T-2" ] {
+        assert!(modal.update(&parse_line(line).unwrap()).is_err(), "'{}' has been accepted", line);
+    }
+    assert!(modal.relative);
+    assert_eq!(modal.tool, Some(2));
+}
+
+#[tokio::test]
+async fn executor_rejects_invalid_lines() {
+    use crate::ErrorKind;
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::sender::Executor;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut desc = LinearXYDescriptor::new();
+
+    for program in [ "G4 P0\nG0 F\"fixture\nG4 P0", "G4 P0\nG91.1\nG4 P0" ] {
+        let mut exec = Executor::new(Sender::new(program), ExecControl::new());
+
+        let res = exec.step(&Echo, &mut rob, &mut desc, &mut ()).await.unwrap().unwrap();
+        assert_eq!(res.output, [ "G4 P0" ]);
+
+        // The invalid line stops the sender and is not acknowledged
+        let err = exec.step(&Echo, &mut rob, &mut desc, &mut ()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Interpreter);
+        assert_eq!(exec.sender().state(), SenderState::Stopped);
+        assert_eq!(exec.progress().acked, 1);
+        assert!(!exec.modal().relative);
+    }
+}
+
+#[tokio::test]
+async fn executor_breakpoints() {
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::sender::Executor;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut desc = LinearXYDescriptor::new();
+    let log = Log::default();
+
+    let control = ExecControl::new();
+    let mut exec = Executor::new(Sender::new("G4 P0\nM6 T1\nG4 P0\nG4 P1"), control.clone());
+    exec.breakpoints.mcodes.insert(6);
+    exec.breakpoints.lines.insert(3);
+
+    assert_eq!(exec.step(&log, &mut rob, &mut desc, &mut ()).await.unwrap().unwrap().line, 0);
+    assert_eq!(control.state(), ExecState::Running);
+
+    // Both breakpoints pause the execution before their line is executed
+    let (res, _) = tokio::join!(exec.step(&log, &mut rob, &mut desc, &mut ()), resume_paused(&control, &log, 1));
+    assert_eq!(res.unwrap().unwrap().line, 1);
+    assert_eq!(log.len(), 2);
+
+    assert_eq!(exec.step(&log, &mut rob, &mut desc, &mut ()).await.unwrap().unwrap().line, 2);
+    assert_eq!(control.state(), ExecState::Running);
+
+    let (res, _) = tokio::join!(exec.step(&log, &mut rob, &mut desc, &mut ()), resume_paused(&control, &log, 3));
+    assert_eq!(res.unwrap().unwrap().line, 3);
+    assert_eq!(*log.lines.lock().unwrap(), [ "G4 P0", "M6 T1", "G4 P0", "G4 P1" ]);
+
+    assert!(exec.step(&log, &mut rob, &mut desc, &mut ()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn executor_single_step() {
+    use crate::desc::common::LinearXYDescriptor;
+    use crate::sender::Executor;
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut desc = LinearXYDescriptor::new();
+    let log = Log::default();
+
+    let control = ExecControl::new();
+    let mut exec = Executor::new(Sender::new("G4 P0\nG4 P1\nG4 P2"), control.clone());
+    exec.single_step = true;
+
+    // Every line is executed and pauses the execution afterwards
+    assert_eq!(exec.step(&log, &mut rob, &mut desc, &mut ()).await.unwrap().unwrap().line, 0);
+    assert_eq!(control.state(), ExecState::Paused);
+    assert_eq!(exec.sender().state(), SenderState::Paused);
+
+    for i in 1 .. 3 {
+        let (res, _) = tokio::join!(exec.step(&log, &mut rob, &mut desc, &mut ()), resume_paused(&control, &log, i));
+        assert_eq!(res.unwrap().unwrap().line, i);
+        assert_eq!(control.state(), ExecState::Paused);
+    }
+
+    control.resume();
+    assert!(exec.step(&log, &mut rob, &mut desc, &mut ()).await.unwrap().is_none());
+    assert_eq!(log.len(), 3);
+}
+
+#[test]
+fn line_result_json() {
+    use crate::sender::LineResult;
+    use crate::traj::{ProfileSample, VelocityProfile};
+
+    let profile = VelocityProfile { samples: vec![ 
+        ProfileSample { time: 0.0, speed: 0.0 }, 
+        ProfileSample { time: 0.5, speed: 20.0 } 
+    ] };
+    let res = LineResult { line: 3, output: vec![ 1, 2 ], profile: Some(profile) };
+
+    let json = res.to_json().unwrap();
+    assert_eq!(json["line"], 3);
+    assert_eq!(json["output"], serde_json::json!([ 1, 2 ]));
+    assert_eq!(json["profile"]["speed"], serde_json::json!([ 0.0, 20.0 ]));
+
+    let res : LineResult<i32> = LineResult { line: 4, output: Vec::new(), profile: None };
+    assert!(res.to_json().unwrap()["profile"].is_null());
+}
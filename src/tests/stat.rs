@@ -0,0 +1,335 @@
+use crate::stat::{CalendarTime, CronRule, ScheduledJob, ScheduleRule};
+
+#[test]
+fn cron_rules() {
+    // 2024-01-01 22:00 UTC, a monday
+    let now = 1704067200 + 22 * 3600;
+    let time = CalendarTime::from_unix(now);
+    assert_eq!(time, CalendarTime { minute: 0, hour: 22, day: 1, month: 1, weekday: 1 });
+
+    let workdays = CronRule::parse("0 22 * * 1-5").unwrap();
+    assert!(workdays.matches(&time));
+    assert!(!workdays.matches(&CalendarTime::from_unix(now + 6 * 86400)));
+
+    assert!(CronRule::parse("*/15 * * *").is_err());
+    assert!(CronRule::parse("60 * * * *").is_err());
+
+    let mut job = ScheduledJob::new("drawing", ScheduleRule::Cron(workdays));
+    assert!(job.is_due(now + 30));
+
+    job.enabled = false;
+    assert!(!job.is_due(now));
+}
+
+#[test]
+fn robot_cell_addressing() {
+    use crate::stat::RobotCell;
+
+    let mut cell : RobotCell<(), ()> = RobotCell::new();
+    cell.add("left", (), ()).unwrap();
+    cell.add("right", (), ()).unwrap();
+    assert!(cell.add("left", (), ()).is_err());
+
+    assert_eq!(cell.split_addr("right: G1 X10").unwrap(), (1, "G1 X10"));
+    assert_eq!(cell.split_addr("R0:G0 Y5").unwrap(), (0, "G0 Y5"));
+    assert_eq!(cell.split_addr("G1 X10 ; note: slow").unwrap(), (0, "G1 X10 ; note: slow"));
+    assert!(cell.split_addr("R2: G1 X10").is_err());
+}
+
+#[tokio::test]
+async fn sync_waits_for_all() {
+    use crate::stat::sync;
+
+    let done = std::cell::Cell::new(0);
+    let motion = |ms : u64, fail : bool| {
+        let done = &done;
+        async move {
+            tokio::time::sleep(core::time::Duration::from_millis(ms)).await;
+            done.set(done.get() + 1);
+            if fail { Err::<(), crate::Error>("Failed".into()) } else { Ok(()) }
+        }
+    };
+
+    assert!(sync(vec![ motion(20, false), motion(5, true), motion(10, false) ]).await.is_err());
+    assert_eq!(done.get(), 3);
+}
+
+#[tokio::test]
+async fn limit_switch_stop_axis() {
+    use crate::Robot;
+    use crate::stat::{LimitMonitor, LimitReaction, LimitSwitch};
+    use crate::tests::TestXYRobot;
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut monitor = LimitMonitor::new(vec![
+        LimitSwitch::new("y_max", Some(1), LimitReaction::StopAxis),
+        LimitSwitch::new("door", None, LimitReaction::Report)
+    ], core::time::Duration::from_millis(1));
+
+    let res = monitor.guard_move(&mut rob, |input| Ok(input == "y_max"), |_| Box::pin(core::future::pending())).await;
+    assert!(res.is_err());
+    assert_eq!(monitor.triggered().len(), 1);
+    assert!(!monitor.is_error());
+
+    // Only the axis of the switch is masked
+    assert_eq!(rob.vars().masked, [ false, true ]);
+}
+
+#[test]
+fn external_axis_frames() {
+    use glam::Vec3;
+
+    use crate::rcs::{Point, PointRef, Position, WorldObj};
+    use crate::stat::{ExternalAxis, ExternalKind};
+
+    let wobj = WorldObj::zero().add_point_inline("conveyor", PointRef::new(Position::zero()));
+    let mut readings = vec![ 20.0, 10.0 ];
+
+    let mut conveyor = ExternalAxis::new(ExternalKind::Linear { dir: Vec3::X * 2.0 }, "conveyor", 
+        Position::new(0.0, 100.0, 0.0), move || Ok(readings.pop().unwrap()));
+
+    conveyor.update(&wobj).unwrap();
+    assert_eq!(*wobj.global_pose("conveyor").unwrap().pos(), Vec3::new(10.0, 100.0, 0.0));
+
+    conveyor.update(&wobj).unwrap();
+    assert_eq!(conveyor.value(), 20.0);
+
+    // Targets on the tracked frame are resolved where the frame will be, a single reading has no velocity
+    let mut belt = ExternalAxis::new(ExternalKind::Linear { dir: Vec3::X }, "conveyor", Position::new(0.0, 100.0, 0.0), || Ok(20.0));
+    belt.read().unwrap();
+
+    let tracking = belt.tracking();
+    assert_eq!(tracking.frame, "conveyor");
+    assert!((*tracking.track(&Position::new(1.0, 0.0, 0.0), 0.0).pos() - Vec3::new(21.0, 100.0, 0.0)).length() < 1e-3);
+    assert!((*tracking.untrack(&Position::new(21.0, 100.0, 0.0)).pos() - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-3);
+
+    let table = ExternalAxis::new(ExternalKind::Rotary { axis: Vec3::Z }, "table", Position::zero(), || Ok(0.0));
+    let pose = table.pose_at(core::f32::consts::FRAC_PI_2);
+    assert!((pose.to_higher_system(Vec3::X) - Vec3::Y).length() < 1e-5);
+}
+
+#[test]
+fn drift_compensation_limits() {
+    use core::time::Duration;
+
+    use glam::Vec3;
+
+    use crate::stat::DriftCompensation;
+
+    let mut drift = DriftCompensation::new("fixture", Vec3::new(100.0, 0.0, 0.0), Duration::from_secs(600), 0.1, 1.0);
+    assert!(drift.is_due());
+
+    // Limited per step
+    let offset = drift.update(Vec3::new(100.3, 0.0, 0.0)).unwrap();
+    assert!((offset.x - 0.1).abs() < 1e-5);
+    assert!(!drift.is_due());
+
+    drift.update(Vec3::new(100.3, 0.0, 0.0)).unwrap();
+    drift.update(Vec3::new(100.3, 0.0, 0.0)).unwrap();
+    assert!((drift.apply(Vec3::ZERO).x - 0.3).abs() < 1e-4);
+
+    assert!(drift.update(Vec3::new(102.0, 0.0, 0.0)).is_err());
+}
+
+#[test]
+fn tool_rack_config() {
+    use crate::stat::ToolRack;
+
+    let rack : ToolRack = serde_json::from_str(r#"{
+        "slots": [
+            { "tool_id": 0, "pos": [ 200.0, 0.0, 50.0 ], "approach": [ 0.0, 0.0, 30.0 ] },
+            { "tool_id": 2, "pos": [ 250.0, 0.0, 50.0 ], "approach": [ 0.0, 0.0, 30.0 ] }
+        ],
+        "coupling": "tool_lock",
+        "flange": [ 0.0, 0.0, 10.0 ]
+    }"#).unwrap();
+
+    assert_eq!(rack.req_slot(2).unwrap().approach_pos().to_array(), [ 250.0, 0.0, 80.0 ]);
+    assert!(rack.slot(1).is_none());
+    assert!(rack.req_slot(1).is_err());
+}
+
+#[test]
+fn tool_usage_reset() {
+    use crate::stat::{MaintenanceThresholds, ToolUsageTracker};
+
+    let mut tracker = ToolUsageTracker::new();
+    tracker.set_thresholds(0, MaintenanceThresholds { activations: Some(1), ..Default::default() });
+
+    tracker.activate(0);
+    tracker.add_travel(0, -20.0);
+    assert!(tracker.maintenance_due(0));
+
+    // Resetting during an activation only counts the engaged time from the reset on
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    tracker.reset(0);
+    tracker.deactivate(0);
+
+    let usage = tracker.usage(0).unwrap();
+    assert_eq!((usage.activations, usage.travel), (0, 0.0));
+    assert!(usage.engaged_time < 0.05);
+    assert!(!tracker.maintenance_due(0));
+}
+
+#[tokio::test]
+async fn tool_usage_maintenance_events() {
+    use core::time::Duration;
+
+    use crate::{Robot, Station};
+    use crate::events::StateEvent;
+    use crate::stat::{MaintenanceThresholds, ToolUsageTracker};
+    use crate::tests::{TestStation, TestXYRobot};
+
+    let mut rob = TestXYRobot::new_simple();
+    let mut stat = TestStation { usage: Some(ToolUsageTracker::new()), ..Default::default() };
+    stat.usage.as_mut().unwrap().set_thresholds(0, MaintenanceThresholds { travel: Some(100.0), ..Default::default() });
+
+    let mut events = rob.events().subscribe(Duration::ZERO);
+
+    // The usage recorded by the robot is moved into the station
+    rob.tool_usage_mut().unwrap().add_travel(0, 60.0);
+    stat.store_tool_usage(&mut rob);
+    assert!(rob.tool_usage().unwrap().usage(0).is_none());
+    assert_eq!(stat.tool_usage().unwrap().usage(0).unwrap().travel, 60.0);
+
+    // Crossing the threshold is published once
+    rob.tool_usage_mut().unwrap().add_travel(0, 50.0);
+    stat.store_tool_usage(&mut rob);
+    rob.tool_usage_mut().unwrap().add_travel(0, 10.0);
+    stat.store_tool_usage(&mut rob);
+
+    match events.recv().await {
+        Some(StateEvent::Maintenance { tools }) => assert_eq!(tools, [ 0 ]),
+        event => panic!("Unexpected event: {:?}", event)
+    }
+    assert!(tokio::time::timeout(Duration::from_millis(10), events.recv()).await.is_err());
+
+    // Stations without tracker leave the usage in the robot
+    let mut bare = TestStation::default();
+    rob.tool_usage_mut().unwrap().add_travel(1, 5.0);
+    bare.store_tool_usage(&mut rob);
+    assert_eq!(rob.tool_usage().unwrap().usage(1).unwrap().travel, 5.0);
+}
+
+#[tokio::test]
+async fn handover_failures() {
+    use core::time::Duration;
+
+    use glam::Vec3;
+    use syact::{Dismantle, Setup};
+    use syunit::*;
+
+    use crate::{ErrorKind, Robot};
+    use crate::config::AngleConfig;
+    use crate::robs::Tool;
+    use crate::robs::tool::SimpleTool;
+    use crate::stat::{Handover, HandoverState, HandoverStep};
+    use crate::tests::{TestXYRobot, TestXYRobotComponents};
+
+    struct Gripper {
+        active : bool
+    }
+
+    impl Setup for Gripper {
+        fn setup(&mut self) -> Result<(), syact::Error> {
+            Ok(())
+        }
+    }
+
+    impl Dismantle for Gripper {
+        fn dismantle(&mut self) -> Result<(), syact::Error> {
+            Ok(())
+        }
+    }
+
+    impl SimpleTool for Gripper {
+        fn activate(&mut self) {
+            self.active = true;
+        }
+
+        fn deactivate(&mut self) {
+            self.active = false;
+        }
+
+        fn is_active(&self) -> bool {
+            self.active
+        }
+    }
+
+    impl Tool for Gripper {
+        fn simple_tool(&self) -> Option<&dyn SimpleTool> {
+            Some(self)
+        }
+
+        fn simple_tool_mut(&mut self) -> Option<&mut dyn SimpleTool> {
+            Some(self)
+        }
+
+        fn get_json(&self) -> serde_json::Value {
+            serde_json::json!({ "active": self.active })
+        }
+
+        fn vec(&self) -> Vec3 {
+            Vec3::ZERO
+        }
+
+        fn inertia(&self) -> Inertia {
+            Inertia(0.0)
+        }
+
+        fn mass(&self) -> f32 {
+            0.0
+        }
+    }
+
+    fn robot(holding : bool) -> TestXYRobot {
+        let mut rob = TestXYRobot::new([ AngleConfig::EMPTY; 2 ], TestXYRobotComponents::new(), vec![
+            Box::new(Gripper { active: holding })
+        ]);
+        rob.set_tool_id(Some(0));
+        rob
+    }
+
+    fn holds(rob : &TestXYRobot) -> bool {
+        rob.get_tool().unwrap().simple_tool().unwrap().is_active()
+    }
+
+    let pose = [ Phi::ZERO; 2 ];
+
+    // The giver releases once the grip of the receiver has been confirmed
+    let (mut giver, mut receiver) = (robot(true), robot(false));
+    let mut handover = Handover::new(pose, pose, Factor::MAX, Duration::from_millis(200));
+    let mut polls = 0;
+
+    handover.run(&mut giver, &mut receiver, || { polls += 1; polls > 2 }).await.unwrap();
+    assert_eq!(handover.state(), HandoverState::Done);
+    assert!(!holds(&giver) & holds(&receiver));
+
+    // An unconfirmed grip times out, the giver keeps holding the part
+    let (mut giver, mut receiver) = (robot(true), robot(false));
+    let mut handover = Handover::new(pose, pose, Factor::MAX, Duration::from_millis(50));
+
+    let err = handover.run(&mut giver, &mut receiver, || false).await.unwrap_err();
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Safety);
+    assert_eq!(handover.state(), HandoverState::Failed(HandoverStep::Grip));
+    assert!(holds(&giver));
+
+    // Rejected approaches (e.g. by a robot that has not been homed) fail before gripping
+    let (mut giver, mut receiver) = (robot(true), robot(false));
+    receiver.vars_mut().require_homing = true;
+    let mut handover = Handover::new(pose, pose, Factor::MAX, Duration::from_millis(200));
+
+    let err = handover.run(&mut giver, &mut receiver, || true).await.unwrap_err();
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Safety);
+    assert_eq!(handover.state(), HandoverState::Failed(HandoverStep::Approach));
+    assert!(holds(&giver) & !holds(&receiver));
+
+    // A receiver without gripper rejects the grip
+    let (mut giver, mut receiver) = (robot(true), TestXYRobot::new_simple());
+    let mut handover = Handover::new(pose, pose, Factor::MAX, Duration::from_millis(200));
+
+    let err = handover.run(&mut giver, &mut receiver, || true).await.unwrap_err();
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Component);
+    assert_eq!(handover.state(), HandoverState::Failed(HandoverStep::Grip));
+    assert!(holds(&giver));
+}
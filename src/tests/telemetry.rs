@@ -0,0 +1,169 @@
+#[test]
+fn telemetry_router_rates() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::{Duration, Instant};
+
+    use glam::Vec3;
+    use syunit::*;
+
+    use crate::events::StateEvent;
+    use crate::telemetry::{ChannelConfig, TelemetryRouter, TelemetrySink};
+
+    struct Recorder(Rc<RefCell<Vec<String>>>);
+
+    impl TelemetrySink for Recorder {
+        fn send(&mut self, frame : &str) -> Result<(), crate::Error> {
+            self.0.borrow_mut().push(frame.to_owned());
+            Ok(())
+        }
+    }
+
+    let fast = Rc::new(RefCell::new(Vec::new()));
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let mut router : TelemetryRouter<2> = TelemetryRouter::new(Duration::from_millis(10));
+    router.subscribe(Box::new(Recorder(fast.clone())), ChannelConfig { positions: Some(50.0), ..Default::default() });
+    let id = router.subscribe(Box::new(Recorder(events.clone())), ChannelConfig::events_only());
+
+    let now = Instant::now();
+    for ms in [ 0, 10, 20, 30, 40 ] {
+        router.publish_positions(now + Duration::from_millis(ms), &[ Phi::ZERO; 2 ], Vec3::ZERO).unwrap();
+    }
+    router.publish_event(&StateEvent::ToolChange { old: None, new: Some(1) }).unwrap();
+    router.publish_event(&StateEvent::Phis([ Phi::ZERO; 2 ])).unwrap();
+
+    // 50 Hz are every second tick
+    assert_eq!(fast.borrow().len(), 3);
+    assert!(fast.borrow()[0].contains("\"channel\":\"positions\""));
+    assert_eq!(events.borrow().len(), 1);
+    assert!(events.borrow()[0].contains("tool_change"));
+    assert!(!events.borrow()[0].contains("source"));
+
+    // Messages carry the identity of the robot
+    router.identity = Some(crate::Identity::new("arm"));
+    router.publish_event(&StateEvent::ToolChange { old: Some(1), new: None }).unwrap();
+    assert!(events.borrow()[1].contains("\"source\":{\"name\":\"arm\""));
+
+    assert!(router.unsubscribe(id));
+    assert_eq!(router.clients(), 1);
+}
+
+#[tokio::test]
+async fn telemetry_router_buffer() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use glam::Vec3;
+    use syunit::*;
+
+    use crate::desc::{KinElement, Movement, SerialKinematic};
+    use crate::events::{EventBus, StateEvent};
+    use crate::rcs::{PointRef, Position, WorldObj};
+    use crate::telemetry::{TelemetryBuffer, TelemetryRouter};
+
+    let wobj = WorldObj::zero()
+        .add_point_inline("x", PointRef::new(
+            WorldObj::zero().add_point_inline("y", PointRef::new(Position::zero()))
+        ));
+    let kin = SerialKinematic::new([
+        KinElement::new(Movement::Linear(Vec3::X), wobj.point("x").unwrap()),
+        KinElement::new(Movement::Linear(Vec3::Y), wobj.point("x/y").unwrap())
+    ]);
+
+    let buffer = Arc::new(Mutex::new(TelemetryBuffer::new(Duration::from_secs(10))));
+    let mut router : TelemetryRouter<2> = TelemetryRouter::new(Duration::from_millis(10));
+    router.set_buffer(buffer.clone());
+    router.set_load_source(Box::new(|| Some([ Force(2.0), Force(3.0) ])));
+
+    let bus = EventBus::new(16);
+    let events = bus.subscribe(Duration::ZERO);
+
+    // The router records every tick until the bus is dropped
+    let robot = async move {
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        bus.publish(StateEvent::Phis([ Phi(10.0), Phi(5.0) ]));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+    };
+
+    let (res, _) = tokio::join!(router.run(events, kin), robot);
+    res.unwrap();
+
+    let buffer = buffer.lock().unwrap();
+    assert!(buffer.samples().len() >= 3);
+    assert_eq!(buffer.samples()[0].phis, [ 0.0, 0.0 ]);
+    assert_eq!(buffer.samples().back().unwrap().phis, [ 10.0, 5.0 ]);
+    assert_eq!(buffer.samples()[0].forces, Some(vec![ 2.0, 3.0 ]));
+}
+
+#[test]
+fn push_msg_payloads() {
+    use syunit::*;
+
+    use crate::{PushMsg, PushRemote};
+
+    #[derive(Default)]
+    struct Recorder(Vec<(String, String)>);
+
+    impl PushRemote for Recorder {
+        fn push_phis(&mut self, _phis : &[Phi]) -> Result<(), crate::Error> {
+            Ok(())
+        }
+
+        fn push_any(&mut self, msg_type : &str, msg : &[u8]) -> Result<(), crate::Error> {
+            self.0.push((msg_type.to_owned(), String::from_utf8(msg.to_vec())?));
+            Ok(())
+        }
+    }
+
+    let mut remote = Recorder::default();
+    remote.push_other(PushMsg::ToolChange { old: Some(0), new: Some(2) }).unwrap();
+    remote.push_other(PushMsg::error(&crate::SyError::safety("Enclosure open").into())).unwrap();
+
+    assert_eq!(remote.0[0].0, "tool_change");
+    assert_eq!(remote.0[0].1, r#"{"type":"tool_change","old":0,"new":2}"#);
+
+    let msg : PushMsg = serde_json::from_str(&remote.0[1].1).unwrap();
+    assert!(matches!(msg, PushMsg::Error { ref msg, .. } if msg.contains("Enclosure open")));
+}
+
+#[test]
+fn push_identity_namespace() {
+    use syunit::*;
+
+    use crate::{Identity, PushMsg, PushRemote};
+
+    #[derive(Default)]
+    struct Recorder(Vec<(String, String)>);
+
+    impl PushRemote for Recorder {
+        fn push_phis(&mut self, _phis : &[Phi]) -> Result<(), crate::Error> {
+            Ok(())
+        }
+
+        fn push_any(&mut self, msg_type : &str, msg : &[u8]) -> Result<(), crate::Error> {
+            self.0.push((msg_type.to_owned(), String::from_utf8(msg.to_vec())?));
+            Ok(())
+        }
+    }
+
+    let identity = Identity::new("arm").serial("0042");
+    assert_eq!(identity.topic("tool_change"), "arm/0042/tool_change");
+    assert_eq!(Identity::new("arm").namespace(), "arm");
+
+    let mut remote = Recorder::default();
+    remote.push_other_from(Some(&identity), PushMsg::ToolChange { old: None, new: Some(1) }).unwrap();
+    remote.push_phis_from(Some(&identity), &[ Phi(1.0), Phi(2.0) ]).unwrap();
+    remote.push_any_from(None, "raw", b"data").unwrap();
+
+    assert_eq!(remote.0[0].0, "arm/0042/tool_change");
+    let value : serde_json::Value = serde_json::from_str(&remote.0[0].1).unwrap();
+    assert_eq!(value["source"]["serial"], "0042");
+
+    // The identity does not break deserialization
+    let msg : PushMsg = serde_json::from_str(&remote.0[0].1).unwrap();
+    assert_eq!(msg, PushMsg::ToolChange { old: None, new: Some(1) });
+
+    assert_eq!(remote.0[1].0, "arm/0042/phis");
+    assert_eq!(remote.0[2].0, "raw");
+}
@@ -0,0 +1,138 @@
+use syunit::*;
+
+use crate::traj::{plan_traj, AxisLimits};
+
+#[test]
+fn plan_trapezoid() {
+    let limits = [ AxisLimits::new(2.0, 4.0), AxisLimits::new(1.0, 1.0) ];
+    let traj = plan_traj(&[ [ Phi(0.0), Phi(0.0) ], [ Phi(4.0), Phi(0.5) ] ], &limits, 0.01).unwrap();
+
+    // Axis 0: 0.5s accelerating, 1.5s constant velocity, 0.5s decelerating
+    assert!((traj.duration() - 2.5).abs() < 1e-3, "Duration: {}", traj.duration());
+
+    let last = traj.points.last().unwrap().phis;
+    assert_eq!((last[0].0, last[1].0), (4.0, 0.5));
+
+    for pair in traj.points.windows(2) {
+        let dt = pair[1].time - pair[0].time;
+        if dt > 0.0 {
+            assert!((pair[1].phis[0].0 - pair[0].phis[0].0) / dt <= 2.0 + 1e-2);
+        }
+    }
+}
+
+#[test]
+fn lookahead_blends_straight_segments() {
+    use crate::traj::Lookahead;
+
+    let mut queue = Lookahead::new([ Phi(0.0) ], 4, 10.0, 10.0, 0.05);
+    let mut points = Vec::new();
+
+    for i in 1 ..= 3 {
+        if let Some(traj) = queue.push([ Phi(i as f32 * 10.0) ], 0.01) {
+            points.extend(traj.points);
+        }
+    }
+    points.extend(queue.flush(0.01).points);
+
+    for p in points.windows(2) {
+        assert!(p[1].phis[0].0 >= p[0].phis[0].0);
+    }
+    assert_eq!(points.last().unwrap().phis[0].0, 30.0);
+    // Straight continuation without stops at the waypoints: 30 units at 10 units/s plus one second of acceleration
+    assert!((points.last().unwrap().time - 4.0).abs() < 0.05, "Duration: {}", points.last().unwrap().time);
+}
+
+#[test]
+fn compare_offset() {
+    use crate::traj::Trajectory;
+
+    let mut reference = Trajectory::new();
+    reference.push(0.0, [ Phi(0.0) ]).unwrap();
+    reference.push(1.0, [ Phi(1.0) ]).unwrap();
+
+    let mut traj = Trajectory::new();
+    traj.push(0.0, [ Phi(0.1) ]).unwrap();
+    traj.push(1.5, [ Phi(1.1) ]).unwrap();
+
+    let report = traj.compare(&reference, 0.1, None).unwrap();
+    assert!((report.duration_delta - 0.5).abs() < 1e-6);
+    assert!(report.max_error[0] > 0.1);
+    assert!(report.peak_force_delta.is_empty());
+    assert!(!report.within(0.05, 1.0));
+}
+
+#[test]
+fn tcp_speed_limit() {
+    use crate::Descriptor;
+    use crate::desc::common::LinearXYZDescriptor;
+    use crate::traj::plan_traj_tcp;
+
+    let mut desc = LinearXYZDescriptor::new();
+    let limits = [ AxisLimits::new(100.0, 1000.0); 3 ];
+    let waypoints = [ [ Phi(0.0); 3 ], [ Phi(30.0), Phi(40.0), Phi(0.0) ] ];
+
+    let traj = plan_traj_tcp(&waypoints, &limits, 0.01, desc.kinematic_mut(), 10.0).unwrap();
+
+    // 50 units of TCP travel at 10 units per second at most
+    assert!(traj.duration() >= 5.0 - 1e-3, "Duration: {}", traj.duration());
+}
+
+#[test]
+fn trigger_schedule_segments() {
+    use crate::traj::{segment_times, TriggerAction, TriggerSchedule};
+
+    let limits = [ AxisLimits::new(1.0, 1.0) ];
+    let times = segment_times(&[ [ Phi(0.0) ], [ Phi(2.0) ], [ Phi(4.0) ] ], &limits);
+    assert_eq!(times.len(), 2);
+    assert!((times[1].0 - 3.0).abs() < 1e-4, "Times: {:?}", times);
+
+    let action = TriggerAction::Output { name: "camera".to_owned(), state: true };
+    let mut schedule = TriggerSchedule::new().segment(times[1], &[ 0.3, 0.6, 0.9 ], action);
+
+    assert_eq!(schedule.due(3.5).len(), 0);
+    assert_eq!(schedule.due(4.9).len(), 2);
+    assert_eq!(schedule.due(10.0).len(), 1);
+    assert_eq!(schedule.due(10.0).len(), 0);
+}
+
+#[test]
+fn velocity_profile_linear() {
+    use crate::Descriptor;
+    use crate::desc::common::LinearXYZDescriptor;
+    use crate::traj::{Trajectory, VelocityProfile};
+
+    let mut desc = LinearXYZDescriptor::new();
+    let mut traj = Trajectory::new();
+    traj.push(0.0, [ Phi(0.0), Phi(0.0), Phi(0.0) ]).unwrap();
+    traj.push(2.0, [ Phi(10.0), Phi(0.0), Phi(0.0) ]).unwrap();
+
+    let profile = VelocityProfile::from_traj(&traj, desc.kinematic_mut(), 0.1).unwrap();
+    assert!((profile.duration() - 2.0).abs() < 1e-3);
+    assert!((profile.max_speed() - 5.0).abs() < 1e-2);
+    assert_eq!(profile.to_json()["speed"].as_array().unwrap().len(), profile.samples.len());
+}
+
+#[test]
+fn optimize_segment_order() {
+    use crate::traj::{apply_order, optimize_order, rapid_time, travel_cost, Placed};
+
+    let p = |x : f32| [ Phi(x), Phi(0.0) ];
+    let segments = vec![ 
+        vec![ p(10.0), p(11.0) ], 
+        vec![ p(3.0), p(2.0) ], 
+        vec![ p(6.0), p(5.0) ]
+    ];
+
+    let limits = [ AxisLimits::new(1.0, 10.0); 2 ];
+    let cost = rapid_time(&limits);
+    let start = p(0.0);
+
+    let given : Vec<Placed> = (0 .. 3).map(|index| Placed { index, reversed: false }).collect();
+    let order = optimize_order(&segments, &start, true, &cost);
+
+    assert!(travel_cost(&segments, &order, &start, &cost) < travel_cost(&segments, &given, &start, &cost));
+
+    let ordered = apply_order(&segments, &order);
+    assert_eq!(ordered.iter().map(|s| s[0][0].0).collect::<Vec<_>>(), vec![ 2.0, 5.0, 10.0 ]);
+}
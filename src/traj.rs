@@ -0,0 +1,116 @@
+use syunit::*;
+
+use crate::SyError;
+
+// Submodules
+    mod compare;
+    pub use compare::TrajReport;
+
+    mod export;
+
+    mod feed;
+    pub use feed::{AxisDynamics, FeedForwardSink, FeedSample};
+
+    mod lookahead;
+    pub use lookahead::Lookahead;
+
+    mod optimize;
+    pub use optimize::{apply_order, optimize_order, rapid_time, travel_cost, Placed};
+
+    mod planner;
+    pub use planner::{plan_traj, plan_traj_tcp, segment_times, AxisLimits};
+
+    mod profile;
+    pub use profile::{ProfileSample, VelocityProfile};
+
+    mod shaping;
+    pub use shaping::{InputShaper, ShaperKind};
+
+    mod stream;
+    pub use stream::{stream, StreamReceiver, StreamSender};
+
+    mod triggers;
+    pub use triggers::{Trigger, TriggerAction, TriggerSchedule};
+// 
+
+/// A single point of a `Trajectory`
+#[derive(Clone, Copy, Debug)]
+pub struct TrajPoint<const C : usize> {
+    /// Time of the point in seconds, relative to the start of the trajectory
+    pub time : f32,
+    /// The `Phi` values at the given time
+    pub phis : [Phi; C]
+}
+
+/// A time-parameterized joint-space trajectory, with points sorted by their time
+#[derive(Clone, Debug, Default)]
+pub struct Trajectory<const C : usize> {
+    /// The points of the trajectory
+    pub points : Vec<TrajPoint<C>>
+}
+
+impl<const C : usize> Trajectory<C> {
+    /// Creates a new empty trajectory
+    pub fn new() -> Self {
+        Self { points: Vec::new() }
+    }
+
+    /// Adds a point to the trajectory, the `time` must not be smaller than the time of the last point
+    pub fn push(&mut self, time : f32, phis : [Phi; C]) -> Result<(), crate::Error> {
+        if let Some(last) = self.points.last() {
+            if time < last.time {
+                return Err(SyError::kinematics(format!("Trajectory points must be sorted by time! (Last: {}, New: {})", 
+                    last.time, time)).into());
+            }
+        }
+
+        self.points.push(TrajPoint { time, phis });
+        Ok(())
+    }
+
+    /// Returns the duration of the trajectory in seconds
+    pub fn duration(&self) -> f32 {
+        self.points.last().map_or(0.0, |p| p.time)
+    }
+
+    /// Samples the trajectory at the given time, interpolating linearly between points. Times outside of the 
+    /// trajectory return the first or last point, `None` if the trajectory is empty
+    pub fn sample(&self, time : f32) -> Option<[Phi; C]> {
+        let index = self.points.partition_point(|p| p.time <= time);
+
+        if index == 0 {
+            return self.points.first().map(|p| p.phis);
+        }
+
+        if index >= self.points.len() {
+            return self.points.last().map(|p| p.phis);
+        }
+
+        let p0 = &self.points[index - 1];
+        let p1 = &self.points[index];
+        let t = if p1.time > p0.time { (time - p0.time) / (p1.time - p0.time) } else { 1.0 };
+
+        let mut phis = [Phi::ZERO; C];
+        for i in 0 .. C {
+            phis[i] = Phi(p0.phis[i].0 + (p1.phis[i].0 - p0.phis[i].0) * t);
+        }
+
+        Some(phis)
+    }
+
+    /// Resamples the trajectory with a constant time step `dt`
+    pub fn resample(&self, dt : f32) -> Self {
+        let mut traj = Self::new();
+        let n = (self.duration() / dt).ceil() as usize;
+
+        for i in 0 ..= n {
+            let time = (i as f32 * dt).min(self.duration());
+
+            if let Some(phis) = self.sample(time) {
+                traj.points.push(TrajPoint { time, phis });
+            }
+        }
+
+        traj
+    }
+}
@@ -0,0 +1,96 @@
+use serde::Serialize;
+
+use crate::SyError;
+use crate::config::AngleConfig;
+use crate::traj::{AxisDynamics, Trajectory};
+
+/// Structured report of the comparison of two trajectories
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TrajReport {
+    /// Duration of the compared trajectory minus the duration of the reference in seconds
+    pub duration_delta : f32,
+    /// Maximum position error of each axis
+    pub max_error : Vec<f32>,
+    /// Root mean square position error of each axis
+    pub rms_error : Vec<f32>,
+    /// The largest position error of all axes over time, as (time, error) pairs
+    pub error_over_time : Vec<(f32, f32)>,
+    /// Peak torque of the compared trajectory minus the peak torque of the reference for each axis, 
+    /// empty if no dynamics have been given
+    pub peak_force_delta : Vec<f32>
+}
+
+impl TrajReport {
+    /// Checks the report against tolerances for the position error (per axis) and the duration delta
+    pub fn within(&self, max_error : f32, max_duration_delta : f32) -> bool {
+        self.max_error.iter().all(|e| *e <= max_error) & (self.duration_delta.abs() <= max_duration_delta)
+    }
+
+    /// Returns the report as JSON
+    pub fn to_json(&self) -> Result<String, crate::Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl<const C : usize> Trajectory<C> {
+    /// Compares this trajectory with a `reference` (e.g. a golden recording), sampling both with the time step `dt`. 
+    /// Peak forces are compared if the dynamics of the axes are given
+    pub fn compare(&self, reference : &Trajectory<C>, dt : f32, dynamics : Option<(&[AngleConfig; C], &[AxisDynamics; C])>) 
+    -> Result<TrajReport, crate::Error> {
+        if dt <= 0.0 {
+            return Err(SyError::config("The time step of a comparison must be positive!").into());
+        }
+
+        let duration = self.duration().max(reference.duration());
+        let steps = (duration / dt).ceil() as usize;
+
+        let mut max_error = vec![0.0f32; C];
+        let mut sq_error = vec![0.0f32; C];
+        let mut error_over_time = Vec::with_capacity(steps + 1);
+
+        for n in 0 ..= steps {
+            let time = (n as f32 * dt).min(duration);
+            let (Some(a), Some(b)) = (self.sample(time), reference.sample(time)) else {
+                break;
+            };
+
+            let mut worst = 0.0f32;
+            for i in 0 .. C {
+                let err = (a[i].0 - b[i].0).abs();
+                max_error[i] = max_error[i].max(err);
+                sq_error[i] += err * err;
+                worst = worst.max(err);
+            }
+
+            error_over_time.push((time, worst));
+        }
+
+        let count = error_over_time.len().max(1) as f32;
+        let rms_error = sq_error.iter().map(|s| (s / count).sqrt()).collect();
+
+        let peak_force_delta = match dynamics {
+            Some((ang_confs, dynamics)) => {
+                let peaks = |traj : &Trajectory<C>| -> Vec<f32> {
+                    let mut peaks = vec![0.0f32; C];
+                    for sample in traj.feed_forward(ang_confs, dynamics) {
+                        for i in 0 .. C {
+                            peaks[i] = peaks[i].max(sample.torques[i].0.abs());
+                        }
+                    }
+                    peaks
+                };
+
+                peaks(self).iter().zip(peaks(reference)).map(|(a, b)| a - b).collect()
+            },
+            None => Vec::new()
+        };
+
+        Ok(TrajReport {
+            duration_delta: self.duration() - reference.duration(),
+            max_error,
+            rms_error,
+            error_over_time,
+            peak_force_delta
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::Write;
+
+use crate::config::AngleConfig;
+use crate::desc::Kinematic;
+use crate::rcs::Point;
+use crate::traj::Trajectory;
+
+impl<const C : usize> Trajectory<C> {
+    /// Returns the gamma values, velocities and accelerations of each point of the trajectory as tuples of 
+    /// (gammas, omegas, alphas), the derivatives are calculated by finite differences
+    pub fn gamma_derivatives(&self, ang_confs : &[AngleConfig; C]) -> Vec<([f32; C], [f32; C], [f32; C])> {
+        let gammas : Vec<[f32; C]> = self.points.iter().map(|p| {
+            let mut g = [0.0; C];
+            for i in 0 .. C {
+                g[i] = ang_confs[i].gamma_from_phi(p.phis[i]).0;
+            }
+            g
+        }).collect();
+
+        let derive = |values : &Vec<[f32; C]>| -> Vec<[f32; C]> {
+            (0 .. values.len()).map(|n| {
+                let mut d = [0.0; C];
+                if values.len() < 2 {
+                    return d;
+                }
+
+                let (a, b) = if n == 0 { (0, 1) } else { (n - 1, n) };
+                let dt = self.points[b].time - self.points[a].time;
+
+                if dt > 0.0 {
+                    for i in 0 .. C {
+                        d[i] = (values[b][i] - values[a][i]) / dt;
+                    }
+                }
+                d
+            }).collect()
+        };
+
+        let omegas = derive(&gammas);
+        let alphas = derive(&omegas);
+
+        gammas.into_iter().zip(omegas).zip(alphas).map(|((g, o), a)| (g, o, a)).collect()
+    }
+
+    /// Writes the trajectory as CSV table (time, per-axis gamma/omega/alpha and the TCP position if a kinematic 
+    /// is given) for analysis in external tools
+    pub fn write_csv<K : Kinematic<C>>(&self, mut writer : impl Write, ang_confs : &[AngleConfig; C], mut kin : Option<&mut K>) 
+        -> Result<(), crate::Error> 
+    {
+        let mut header = String::from("time");
+        for name in [ "gamma", "omega", "alpha" ] {
+            for i in 0 .. C {
+                header.push_str(&format!(",{}_{}", name, i));
+            }
+        }
+        if kin.is_some() {
+            header.push_str(",tcp_x,tcp_y,tcp_z");
+        }
+        writeln!(writer, "{}", header)?;
+
+        for (point, (gammas, omegas, alphas)) in self.points.iter().zip(self.gamma_derivatives(ang_confs)) {
+            let mut line = format!("{}", point.time);
+
+            for values in [ gammas, omegas, alphas ] {
+                for value in values {
+                    line.push_str(&format!(",{}", value));
+                }
+            }
+
+            if let Some(kin) = kin.as_mut() {
+                kin.update(&point.phis)?;
+                let tcp = kin.calculate_end();
+                line.push_str(&format!(",{},{},{}", tcp.x(), tcp.y(), tcp.z()));
+            }
+
+            writeln!(writer, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
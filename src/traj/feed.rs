@@ -0,0 +1,67 @@
+use syunit::*;
+
+use crate::config::AngleConfig;
+use crate::traj::Trajectory;
+
+/// Simple rigid-body model of a single axis, in component units (the gamma side of the axis)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AxisDynamics {
+    /// The inertia moved by the axis
+    pub inertia : Inertia,
+    /// Viscous friction, torque per unit of velocity
+    pub viscous : f32,
+    /// Coulomb friction, constant torque against the direction of movement
+    pub coulomb : f32,
+    /// Constant load torque (e.g. gravity) acting on the axis
+    pub load : Force,
+    /// The torque constant of the motor (torque per ampere), used to convert torques into currents
+    pub torque_const : Option<f32>
+}
+
+impl AxisDynamics {
+    /// Returns the torque required to move the axis with the given velocity and acceleration
+    pub fn torque(&self, omega : f32, alpha : f32) -> Force {
+        let friction = if omega == 0.0 { 0.0 } else { self.coulomb * omega.signum() };
+        Force(self.inertia.0 * alpha + self.viscous * omega + friction + self.load.0)
+    }
+
+    /// Converts a torque into a motor current, `None` if no torque constant is given
+    pub fn current(&self, torque : Force) -> Option<f32> {
+        self.torque_const.filter(|k| *k != 0.0).map(|k| torque.0 / k)
+    }
+}
+
+/// Feed-forward values for a single sample of a trajectory
+#[derive(Clone, Copy, Debug)]
+pub struct FeedSample<const C : usize> {
+    /// Time of the sample in seconds
+    pub time : f32,
+    /// Torques required on each axis
+    pub torques : [Force; C],
+    /// Currents required on each axis, `None` for axes without a torque constant
+    pub currents : [Option<f32>; C]
+}
+
+/// A component backend accepting feed-forward values, e.g. a servo driver with a torque input
+pub trait FeedForwardSink<const C : usize> {
+    /// Applies the feed-forward values of a sample, called in sync with the trajectory
+    fn apply_feed_forward(&mut self, sample : &FeedSample<C>) -> Result<(), crate::Error>;
+}
+
+impl<const C : usize> Trajectory<C> {
+    /// Calculates the feed-forward torques and currents for each point of the trajectory using the given dynamics 
+    /// model of each axis
+    pub fn feed_forward(&self, ang_confs : &[AngleConfig; C], dynamics : &[AxisDynamics; C]) -> Vec<FeedSample<C>> {
+        self.points.iter().zip(self.gamma_derivatives(ang_confs)).map(|(point, (_, omegas, alphas))| {
+            let mut torques = [Force(0.0); C];
+            let mut currents = [None; C];
+
+            for i in 0 .. C {
+                torques[i] = dynamics[i].torque(omegas[i], alphas[i]);
+                currents[i] = dynamics[i].current(torques[i]);
+            }
+
+            FeedSample { time: point.time, torques, currents }
+        }).collect()
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+
+use syunit::*;
+
+use crate::traj::{TrajPoint, Trajectory};
+
+/// A single linear joint-space segment of a `Lookahead` queue
+#[derive(Clone, Copy, Debug)]
+struct Segment<const C : usize> {
+    start : [Phi; C],
+    target : [Phi; C],
+    len : f32,
+    dir : [f32; C],
+
+    /// Maximum velocity at the junction to the previous segment
+    v_junction : f32,
+    v_entry : f32,
+    v_exit : f32
+}
+
+/// Motion queue with lookahead, consecutive joint movements are blended with the corner velocities computed from the 
+/// angle between the segments (junction deviation), like the planners of CNC controllers
+/// 
+/// Velocities and accelerations are given along the path in joint space (euclidean norm of the `Phi` deltas). Streamed 
+/// movements are blended by passing the queue to `StreamReceiver::with_lookahead()`
+#[derive(Clone, Debug)]
+pub struct Lookahead<const C : usize> {
+    /// Maximum number of segments planned ahead, the oldest segment is released once the queue is full
+    pub depth : usize,
+    /// Maximum path velocity
+    pub vel : f32,
+    /// Maximum path acceleration
+    pub accel : f32,
+    /// Junction deviation, larger values allow higher corner velocities
+    pub deviation : f32,
+
+    segments : VecDeque<Segment<C>>,
+    last : [Phi; C],
+    /// Velocity at the end of the segments already released
+    v_released : f32,
+    time : f32
+}
+
+impl<const C : usize> Lookahead<C> {
+    /// Creates a new queue starting at rest at the given position
+    pub fn new(start : [Phi; C], depth : usize, vel : f32, accel : f32, deviation : f32) -> Self {
+        Self {
+            depth: depth.max(1),
+            vel,
+            accel,
+            deviation,
+
+            segments: VecDeque::new(),
+            last: start,
+            v_released: 0.0,
+            time: 0.0
+        }
+    }
+
+    /// Returns the number of segments queued
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns wheiter the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Adds a movement to the given position. If the queue is full, the oldest segment is planned and returned as 
+    /// trajectory sampled with the time step `dt`
+    pub fn push(&mut self, target : [Phi; C], dt : f32) -> Option<Trajectory<C>> {
+        let start = self.last;
+        let mut dir = [0.0; C];
+        let len = (0 .. C).map(|i| (target[i].0 - start[i].0).powi(2)).sum::<f32>().sqrt();
+
+        if len <= f32::EPSILON {
+            return None;
+        }
+
+        for i in 0 .. C {
+            dir[i] = (target[i].0 - start[i].0) / len;
+        }
+
+        let v_junction = match self.segments.back() {
+            Some(prev) => self.junction_vel(&prev.dir, &dir),
+            // The first segment starts with the velocity the released segments ended with
+            None => self.v_released
+        };
+
+        self.segments.push_back(Segment { start, target, len, dir, v_junction, v_entry: 0.0, v_exit: 0.0 });
+        self.last = target;
+
+        if self.segments.len() > self.depth {
+            Some(self.release(dt))
+        } else {
+            None
+        }
+    }
+
+    /// Plans all the remaining segments, coming to a stop at the last one
+    pub fn flush(&mut self, dt : f32) -> Trajectory<C> {
+        let mut traj = Trajectory::new();
+
+        while !self.segments.is_empty() {
+            traj.points.extend(self.release(dt).points);
+        }
+
+        traj
+    }
+
+    fn junction_vel(&self, prev : &[f32; C], dir : &[f32; C]) -> f32 {
+        let cos = -(0 .. C).map(|i| prev[i] * dir[i]).sum::<f32>();
+
+        if cos > 0.999999 {
+            // Full reversal
+            return 0.0;
+        }
+
+        if cos < -0.999999 {
+            // Straight continuation
+            return self.vel;
+        }
+
+        let sin_half = ((1.0 - cos) / 2.0).sqrt();
+        (self.accel * self.deviation * sin_half / (1.0 - sin_half)).sqrt().min(self.vel)
+    }
+
+    fn recalculate(&mut self) {
+        let accel = self.accel;
+        let n = self.segments.len();
+
+        // Backward pass, the robot must be able to stop at the end of the last segment
+        let mut v_next = 0.0;
+        for seg in self.segments.iter_mut().rev() {
+            seg.v_exit = v_next;
+            seg.v_entry = seg.v_junction.min((v_next * v_next + 2.0 * accel * seg.len).sqrt());
+            v_next = seg.v_entry;
+        }
+
+        // Forward pass, the velocities must be reachable from the start
+        let mut v_prev = self.v_released;
+        for seg in self.segments.iter_mut() {
+            seg.v_entry = seg.v_entry.min(v_prev);
+            seg.v_exit = seg.v_exit.min((seg.v_entry * seg.v_entry + 2.0 * accel * seg.len).sqrt());
+            v_prev = seg.v_exit;
+        }
+
+        // Keep entry and exit velocities of neighbours consistent
+        for i in 1 .. n {
+            let v = self.segments[i - 1].v_exit.min(self.segments[i].v_entry);
+            self.segments[i - 1].v_exit = v;
+            self.segments[i].v_entry = v;
+        }
+    }
+
+    fn release(&mut self, dt : f32) -> Trajectory<C> {
+        self.recalculate();
+
+        let seg = self.segments.pop_front().expect("Release called on an empty queue");
+        let traj = self.sample_segment(&seg, dt);
+
+        self.v_released = seg.v_exit;
+        if let Some(next) = self.segments.front_mut() {
+            next.v_junction = next.v_junction.min(seg.v_exit);
+        }
+
+        traj
+    }
+
+    fn sample_segment(&mut self, seg : &Segment<C>, dt : f32) -> Trajectory<C> {
+        let a = self.accel;
+        let (v0, v1) = (seg.v_entry, seg.v_exit);
+
+        // Peak velocity, limited if the segment is too short to reach the maximum
+        let v_peak = self.vel.min(((2.0 * a * seg.len + v0 * v0 + v1 * v1) / 2.0).sqrt()).max(v0.max(v1));
+
+        let t_acc = (v_peak - v0) / a;
+        let t_dec = (v_peak - v1) / a;
+        let d_acc = (v0 + v_peak) / 2.0 * t_acc;
+        let d_dec = (v1 + v_peak) / 2.0 * t_dec;
+        let t_cruise = ((seg.len - d_acc - d_dec) / v_peak.max(f32::EPSILON)).max(0.0);
+        let duration = t_acc + t_cruise + t_dec;
+
+        let dist = |t : f32| -> f32 {
+            if t < t_acc {
+                v0 * t + a * t * t / 2.0
+            } else if t < t_acc + t_cruise {
+                d_acc + v_peak * (t - t_acc)
+            } else {
+                let r = (t - t_acc - t_cruise).min(t_dec);
+                d_acc + v_peak * t_cruise + v_peak * r - a * r * r / 2.0
+            }
+        };
+
+        let mut traj = Trajectory::new();
+        let steps = ((duration / dt).ceil() as usize).max(1);
+
+        for n in 1 ..= steps {
+            let t = (n as f32 * dt).min(duration);
+            let mut phis = seg.target;
+
+            // The last sample is placed exactly on the target
+            if n < steps {
+                let s = dist(t).min(seg.len);
+                for i in 0 .. C {
+                    phis[i] = Phi(seg.start[i].0 + seg.dir[i] * s);
+                }
+            }
+
+            traj.points.push(TrajPoint { time: self.time + t, phis });
+        }
+
+        self.time += duration;
+        traj
+    }
+}
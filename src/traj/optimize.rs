@@ -0,0 +1,122 @@
+use syunit::*;
+
+use crate::traj::{segment_times, AxisLimits};
+
+/// A segment placed by `optimize_order`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placed {
+    /// Index of the segment in the original order
+    pub index : usize,
+    /// Wheiter the segment is run backwards
+    pub reversed : bool
+}
+
+/// Returns the estimated time of a rapid movement between two joint poses, as planned by `plan_traj` with the limits
+/// given. Used as cost function for `optimize_order`
+pub fn rapid_time<const C : usize>(limits : &[AxisLimits; C]) -> impl Fn(&[Phi; C], &[Phi; C]) -> f32 + '_ {
+    move |a, b| segment_times(&[ *a, *b ], limits).first().map_or(0.0, |(start, end)| end - start)
+}
+
+fn ends<P>(seg : &[P], reversed : bool) -> Option<(&P, &P)> {
+    let (first, last) = (seg.first()?, seg.last()?);
+    Some(if reversed { (last, first) } else { (first, last) })
+}
+
+/// Returns the total rapid travel cost of the order, starting at `start`
+pub fn travel_cost<P, F>(segments : &[Vec<P>], order : &[Placed], start : &P, cost : &F) -> f32
+where
+    F : Fn(&P, &P) -> f32
+{
+    let mut pos = start;
+    let mut total = 0.0;
+
+    for placed in order {
+        if let Some((first, last)) = ends(&segments[placed.index], placed.reversed) {
+            total += cost(pos, first);
+            pos = last;
+        }
+    }
+
+    total
+}
+
+/// Reorders independent path segments (e.g. disconnected strokes or drill points) to minimize the rapid travel
+/// between them, starting at `start`. `cost` estimates the time of a rapid movement between two points, e.g.
+/// `rapid_time`. Segments may be run backwards if `reversible` is set
+///
+/// The order is built greedily by always choosing the closest segment next and improved by 2-opt afterwards
+pub fn optimize_order<P, F>(segments : &[Vec<P>], start : &P, reversible : bool, cost : F) -> Vec<Placed>
+where
+    F : Fn(&P, &P) -> f32
+{
+    let mut order : Vec<Placed> = Vec::with_capacity(segments.len());
+    let mut open : Vec<usize> = (0 .. segments.len()).filter(|i| !segments[*i].is_empty()).collect();
+    let mut pos = start;
+
+    // Nearest neighbour
+    while !open.is_empty() {
+        let mut best = (f32::INFINITY, 0, false);
+
+        for (o, &index) in open.iter().enumerate() {
+            for reversed in [ false, true ] {
+                if reversed & !reversible {
+                    continue;
+                }
+
+                if let Some((first, _)) = ends(&segments[index], reversed) {
+                    let c = cost(pos, first);
+                    if c < best.0 {
+                        best = (c, o, reversed);
+                    }
+                }
+            }
+        }
+
+        let index = open.swap_remove(best.1);
+        order.push(Placed { index, reversed: best.2 });
+        pos = ends(&segments[index], best.2).map_or(pos, |(_, last)| last);
+    }
+
+    // 2-opt, reversing a section of the order reverses the direction of its segments as well
+    if reversible {
+        let mut improved = true;
+        let mut best_cost = travel_cost(segments, &order, start, &cost);
+
+        while improved {
+            improved = false;
+
+            for i in 0 .. order.len() {
+                for j in (i + 1) .. order.len() {
+                    let mut candidate = order.clone();
+                    candidate[i ..= j].reverse();
+
+                    for placed in &mut candidate[i ..= j] {
+                        placed.reversed = !placed.reversed;
+                    }
+
+                    let c = travel_cost(segments, &candidate, start, &cost);
+                    if c < best_cost - 1e-6 {
+                        order = candidate;
+                        best_cost = c;
+                        improved = true;
+                    }
+                }
+            }
+        }
+    }
+
+    order
+}
+
+/// Applies the order returned by `optimize_order`, reversing the segments run backwards
+pub fn apply_order<P : Clone>(segments : &[Vec<P>], order : &[Placed]) -> Vec<Vec<P>> {
+    order.iter().map(|placed| {
+        let mut seg = segments[placed.index].clone();
+
+        if placed.reversed {
+            seg.reverse();
+        }
+
+        seg
+    }).collect()
+}
@@ -0,0 +1,276 @@
+use core::time::Duration;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+
+use crate::{Robot, SyError};
+use crate::desc::Kinematic;
+use crate::rcs::Point;
+use crate::robs::{AxisTuning, SpeedOverride};
+use crate::traj::Trajectory;
+
+/// Number of samples used to smooth trapezoidal profiles into S-curves
+const SMOOTH_SAMPLES : usize = 16;
+
+/// Motion limits of a single axis, given in `Phi` units per second
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AxisLimits {
+    /// Maximum velocity
+    pub vel : f32,
+    /// Maximum acceleration
+    pub accel : f32,
+    /// Maximum jerk, `None` for trapezoidal profiles
+    pub jerk : Option<f32>
+}
+
+impl AxisLimits {
+    /// Creates new limits for trapezoidal profiles
+    pub fn new(vel : f32, accel : f32) -> Self {
+        Self { vel, accel, jerk: None }
+    }
+
+    /// Creates new limits out of a tuning profile, fails if the profile has no acceleration limit
+    pub fn from_tuning(tuning : &AxisTuning) -> Result<Self, crate::Error> {
+        Ok(Self {
+            vel: tuning.omega_max,
+            accel: tuning.alpha_max.ok_or_else(|| SyError::config("The tuning profile has no acceleration limit!"))?,
+            jerk: tuning.jerk_max
+        })
+    }
+
+    /// Returns the ramp time added by the jerk limit
+    fn jerk_time(&self) -> f32 {
+        self.jerk.filter(|j| *j > 0.0).map_or(0.0, |j| self.accel / j)
+    }
+}
+
+/// A trapezoidal velocity profile normalized to a distance of `1.0`
+#[derive(Clone, Copy, Debug)]
+struct Profile {
+    t_acc : f32,
+    t_total : f32,
+    t_jerk : f32
+}
+
+impl Profile {
+    /// The fastest profile covering `dist` within the limits
+    fn fastest(dist : f32, limits : &AxisLimits) -> Self {
+        let dist = dist.abs();
+
+        let (t_acc, t_total) = if dist == 0.0 {
+            (0.0, 0.0)
+        } else if dist < limits.vel * limits.vel / limits.accel {
+            // Triangular, the maximum velocity is never reached
+            let t_acc = (dist / limits.accel).sqrt();
+            (t_acc, 2.0 * t_acc)
+        } else {
+            let t_acc = limits.vel / limits.accel;
+            (t_acc, dist / limits.vel + t_acc)
+        };
+
+        Self { t_acc, t_total, t_jerk: limits.jerk_time() }
+    }
+
+    /// The profile of a segment, the slowest axis defines the profile, all other axes follow it scaled
+    fn segment<const C : usize>(p0 : &[Phi; C], p1 : &[Phi; C], limits : &[AxisLimits; C]) -> Self {
+        (0 .. C)
+            .map(|i| Self::fastest(p1[i].0 - p0[i].0, &limits[i]))
+            .max_by(|a, b| a.duration().total_cmp(&b.duration()))
+            .unwrap_or(Self { t_acc: 0.0, t_total: 0.0, t_jerk: 0.0 })
+    }
+
+    fn duration(&self) -> f32 {
+        self.t_total + self.t_jerk
+    }
+
+    /// Normalized position of the trapezoid
+    fn trapezoid(&self, t : f32) -> f32 {
+        if self.t_total <= 0.0 {
+            return 1.0;
+        }
+
+        let t = t.clamp(0.0, self.t_total);
+        // Peak velocity for a distance of 1
+        let v = 1.0 / (self.t_total - self.t_acc);
+        let a = v / self.t_acc.max(f32::EPSILON);
+
+        if t < self.t_acc {
+            a * t * t / 2.0
+        } else if t <= self.t_total - self.t_acc {
+            v * (t - self.t_acc / 2.0)
+        } else {
+            let r = self.t_total - t;
+            1.0 - a * r * r / 2.0
+        }
+    }
+
+    /// Normalized position at the time given, the trapezoid is averaged over the jerk time to limit the jerk (S-curve)
+    fn position(&self, t : f32) -> f32 {
+        if self.t_jerk <= 0.0 {
+            return self.trapezoid(t);
+        }
+
+        (0 .. SMOOTH_SAMPLES).map(|i| {
+            self.trapezoid(t - self.t_jerk * (i as f32 + 0.5) / SMOOTH_SAMPLES as f32)
+        }).sum::<f32>() / SMOOTH_SAMPLES as f32
+    }
+}
+
+/// Plans a time-parameterized trajectory through the given joint waypoints, respecting the velocity, acceleration and 
+/// jerk limits of each axis. All axes of a segment are synchronized to the slowest one, the robot stops at each 
+/// waypoint. The trajectory is sampled with the time step `dt`
+pub fn plan_traj<const C : usize>(waypoints : &[[Phi; C]], limits : &[AxisLimits; C], dt : f32) -> Result<Trajectory<C>, crate::Error> {
+    if dt <= 0.0 {
+        return Err(SyError::config("The time step of a trajectory must be positive!").into());
+    }
+
+    for (i, l) in limits.iter().enumerate() {
+        if (l.vel <= 0.0) | (l.accel <= 0.0) {
+            return Err(SyError::config(format!("The limits of axis {} must be positive! (Velocity: {}, Acceleration: {})", 
+                i, l.vel, l.accel)).into());
+        }
+    }
+
+    let mut traj = Trajectory::new();
+    let mut time = 0.0;
+
+    let Some(first) = waypoints.first() else {
+        return Ok(traj);
+    };
+    traj.push(0.0, *first)?;
+
+    for pair in waypoints.windows(2) {
+        let (p0, p1) = (pair[0], pair[1]);
+        let profile = Profile::segment(&p0, &p1, limits);
+
+        let duration = profile.duration();
+        let steps = (duration / dt).ceil() as usize;
+
+        for n in 1 ..= steps {
+            let t = (n as f32 * dt).min(duration);
+            let s = profile.position(t);
+
+            let mut phis = p1;
+            for i in 0 .. C {
+                phis[i] = Phi(p0[i].0 + (p1[i].0 - p0[i].0) * s);
+            }
+
+            traj.push(time + t, phis)?;
+        }
+
+        time += duration;
+    }
+
+    Ok(traj)
+}
+
+/// Returns the start and end time of each segment between the waypoints in a trajectory planned by `plan_traj` 
+/// with the same limits
+pub fn segment_times<const C : usize>(waypoints : &[[Phi; C]], limits : &[AxisLimits; C]) -> Vec<(f32, f32)> {
+    let mut time = 0.0;
+
+    waypoints.windows(2).map(|pair| {
+        let start = time;
+        time += Profile::segment(&pair[0], &pair[1], limits).duration();
+        (start, time)
+    }).collect()
+}
+
+/// Plans a trajectory like `plan_traj`, additionally limiting the cartesian speed of the TCP to `tcp_max`, see 
+/// `Trajectory::limit_tcp_speed`
+pub fn plan_traj_tcp<K : Kinematic<C>, const C : usize>(waypoints : &[[Phi; C]], limits : &[AxisLimits; C], dt : f32, 
+    kin : &mut K, tcp_max : f32) -> Result<Trajectory<C>, crate::Error> 
+{
+    plan_traj(waypoints, limits, dt)?.limit_tcp_speed(kin, tcp_max)
+}
+
+impl<const C : usize> Trajectory<C> {
+    /// Returns the trajectory time-scaled so the cartesian speed of the TCP never exceeds `tcp_max`, independent of the 
+    /// joint speeds. Segments too fast are stretched, all others are kept. The kinematic is restored afterwards
+    pub fn limit_tcp_speed<K : Kinematic<C>>(&self, kin : &mut K, tcp_max : f32) -> Result<Trajectory<C>, crate::Error> {
+        if tcp_max <= 0.0 {
+            return Err(SyError::config(format!("The maximum TCP speed must be positive! (Speed: {})", tcp_max)).into());
+        }
+
+        let phis_0 = kin.phis();
+        let mut tcps = Vec::with_capacity(self.points.len());
+
+        for point in &self.points {
+            kin.update(&point.phis)?;
+            tcps.push(*kin.calculate_end().pos());
+        }
+
+        kin.update(&phis_0)?;
+
+        let mut traj = Trajectory::new();
+        let mut time = 0.0;
+
+        for (i, point) in self.points.iter().enumerate() {
+            if i > 0 {
+                let dt = point.time - self.points[i - 1].time;
+                time += dt.max(tcps[i].distance(tcps[i - 1]) / tcp_max);
+            } else {
+                time = point.time;
+            }
+
+            traj.push(time, point.phis)?;
+        }
+
+        Ok(traj)
+    }
+
+    /// Executes the trajectory, driving the robot to the sample of the trajectory every control `period` with the 
+    /// velocity required to reach it within the period (see `Robot::speed_f_for_time()`)
+    pub async fn execute<R, G, T>(&self, rob : &mut R, period : Duration) -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        self.execute_scaled(rob, period, &SpeedOverride::new()).await
+    }
+
+    /// Executes the trajectory like `execute`, the time of the trajectory is scaled by the `SpeedOverride` given, 
+    /// changes of the override apply immediately. An override of `0.0` holds the robot at its current sample
+    pub async fn execute_scaled<R, G, T>(&self, rob : &mut R, period : Duration, ovr : &SpeedOverride) -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        self.execute_with(rob, period, ovr, |_, _| Ok(())).await
+    }
+
+    /// Executes the trajectory like `execute_scaled`, calling `after` with the time of the trajectory reached after 
+    /// every sample driven
+    pub(crate) async fn execute_with<R, G, T, F>(&self, rob : &mut R, period : Duration, ovr : &SpeedOverride, mut after : F) 
+        -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static,
+        F : FnMut(&mut R, f32) -> Result<(), crate::Error>
+    {
+        let mut interval = tokio::time::interval(period);
+        let mut time = 0.0;
+
+        while let Some(phis) = self.sample(time) {
+            interval.tick().await;
+
+            // Every sample is driven with the speed planned for it, reaching it within one period
+            let deltas = sub_unit_arrays(rob.gammas_from_phis(phis), rob.gammas());
+            let speed_f = rob.speed_f_for_time(&deltas, period.as_secs_f32());
+            rob.drive_j(deltas, speed_f).await?;
+            after(rob, time)?;
+
+            if time >= self.duration() {
+                break;
+            }
+
+            time = (time + period.as_secs_f32() * ovr.get()).min(self.duration());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Serialize, Deserialize};
+
+use crate::desc::Kinematic;
+use crate::rcs::Point;
+use crate::traj::Trajectory;
+
+/// A sample of a `VelocityProfile`
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSample {
+    /// Time in seconds, relative to the start of the movement
+    pub time : f32,
+    /// The TCP speed at the given time
+    pub speed : f32
+}
+
+/// The planned TCP speed over time of a movement, e.g. of a single gcode line, for front ends to plot the feed
+/// behavior of the planner
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VelocityProfile {
+    /// The samples, sorted by time
+    pub samples : Vec<ProfileSample>
+}
+
+impl VelocityProfile {
+    /// Samples the TCP speed of the trajectory every `dt` seconds using the given kinematic, which is restored
+    /// afterwards. The speed of a sample is the average speed of the step leading to it
+    pub fn from_traj<K : Kinematic<C>, const C : usize>(traj : &Trajectory<C>, kin : &mut K, dt : f32)
+        -> Result<Self, crate::Error>
+    {
+        if dt <= 0.0 {
+            return Err(SyError::config(format!("The sample time of a velocity profile must be positive! (Time: {})", dt)).into());
+        }
+
+        let phis_0 = kin.phis();
+        let points = traj.resample(dt).points;
+        let mut samples = Vec::with_capacity(points.len());
+        let mut last = None;
+
+        for point in &points {
+            kin.update(&point.phis)?;
+            let tcp = *kin.calculate_end().pos();
+
+            let speed = match last {
+                Some((time, pos)) if point.time > time => tcp.distance(pos) / (point.time - time),
+                _ => 0.0
+            };
+
+            samples.push(ProfileSample { time: point.time, speed });
+            last = Some((point.time, tcp));
+        }
+
+        kin.update(&phis_0)?;
+        Ok(Self { samples })
+    }
+
+    /// Returns the duration of the profile
+    pub fn duration(&self) -> f32 {
+        self.samples.last().map_or(0.0, |s| s.time)
+    }
+
+    /// Returns the maximum speed of the profile
+    pub fn max_speed(&self) -> f32 {
+        self.samples.iter().map(|s| s.speed).fold(0.0, f32::max)
+    }
+
+    /// Returns the profile as JSON, `{ "time": [..], "speed": [..] }` for easy plotting
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "time": self.samples.iter().map(|s| s.time).collect::<Vec<_>>(),
+            "speed": self.samples.iter().map(|s| s.speed).collect::<Vec<_>>()
+        })
+    }
+}
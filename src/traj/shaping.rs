@@ -0,0 +1,86 @@
+use core::f32::consts::PI;
+
+use syunit::*;
+
+use crate::traj::{TrajPoint, Trajectory};
+
+/// The type of input shaper
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShaperKind {
+    /// Zero-Vibration shaper, two impulses
+    ZV,
+    /// Zero-Vibration-Derivative shaper, three impulses, more robust against errors in the resonance frequency
+    ZVD
+}
+
+/// An input shaper for suppressing the vibration at a resonance frequency of an axis
+#[derive(Clone, Copy, Debug)]
+pub struct InputShaper {
+    /// Type of the shaper
+    pub kind : ShaperKind,
+    /// Resonance frequency in Hz
+    pub freq : f32,
+    /// Damping ratio (`0.0` to `1.0`)
+    pub damping : f32
+}
+
+impl InputShaper {
+    /// Creates a new input shaper
+    pub fn new(kind : ShaperKind, freq : f32, damping : f32) -> Self {
+        Self { kind, freq, damping }
+    }
+
+    /// Returns the impulses of the shaper as tuples of (time, amplitude), the amplitudes sum up to `1.0`
+    pub fn impulses(&self) -> Vec<(f32, f32)> {
+        let damp_sqrt = (1.0 - self.damping.powi(2)).sqrt();
+        let k = (-self.damping * PI / damp_sqrt).exp();
+        let t_d = 1.0 / (self.freq * damp_sqrt);
+
+        match self.kind {
+            ShaperKind::ZV => {
+                let norm = 1.0 + k;
+                vec![ (0.0, 1.0 / norm), (t_d / 2.0, k / norm) ]
+            },
+            ShaperKind::ZVD => {
+                let norm = (1.0 + k).powi(2);
+                vec![ (0.0, 1.0 / norm), (t_d / 2.0, 2.0 * k / norm), (t_d, k.powi(2) / norm) ]
+            }
+        }
+    }
+
+    /// Returns the time the shaper delays the end of a movement
+    pub fn delay(&self) -> f32 {
+        self.impulses().last().map_or(0.0, |i| i.0)
+    }
+
+    /// Applies the shapers given for each axis (`None` for no shaping) to the trajectory, sampling with the time step `dt`
+    /// 
+    /// The shaped trajectory is longer than the original one by the largest delay of all shapers
+    pub fn apply<const C : usize>(traj : &Trajectory<C>, shapers : &[Option<InputShaper>; C], dt : f32) -> Trajectory<C> {
+        let impulses : Vec<Option<Vec<(f32, f32)>>> = shapers.iter().map(|s| s.map(|s| s.impulses())).collect();
+        let delay = shapers.iter().flatten().map(|s| s.delay()).fold(0.0, f32::max);
+
+        let mut shaped = Trajectory::new();
+        let duration = traj.duration() + delay;
+        let n = (duration / dt).ceil() as usize;
+
+        for i in 0 ..= n {
+            let time = (i as f32 * dt).min(duration);
+            let mut phis = [Phi::ZERO; C];
+
+            for a in 0 .. C {
+                phis[a] = if let Some(imps) = &impulses[a] {
+                    Phi(imps.iter().map(|(t_i, amp)| {
+                        amp * traj.sample(time - t_i).map_or(0.0, |p| p[a].0)
+                    }).sum())
+                } else {
+                    traj.sample(time).map_or(Phi::ZERO, |p| p[a])
+                };
+            }
+
+            shaped.points.push(TrajPoint { time, phis });
+        }
+
+        shaped
+    }
+}
@@ -0,0 +1,143 @@
+use core::time::Duration;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+use syunit::*;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::{Robot, SyError};
+use crate::traj::{Lookahead, TrajPoint, Trajectory};
+
+/// Creates a new trajectory stream buffering up to `buffer` points, used to drive the robot by external planners
+pub fn stream<const C : usize>(buffer : usize) -> (StreamSender<C>, StreamReceiver<C>) {
+    let (tx, rx) = mpsc::channel(buffer);
+    ( StreamSender { tx }, StreamReceiver { rx, lookahead: None, shift: 0.0 } )
+}
+
+/// The producer side of a trajectory stream
+#[derive(Clone)]
+pub struct StreamSender<const C : usize> {
+    tx : mpsc::Sender<TrajPoint<C>>
+}
+
+impl<const C : usize> StreamSender<C> {
+    /// Sends a timestamped point, the time is relative to the first point sent. Waits if the buffer is full
+    pub async fn send(&self, point : TrajPoint<C>) -> Result<(), crate::Error> {
+        self.tx.send(point).await.map_err(|_| SyError::remote("The trajectory stream has been closed!").into())
+    }
+}
+
+/// The consumer side of a trajectory stream
+pub struct StreamReceiver<const C : usize> {
+    rx : mpsc::Receiver<TrajPoint<C>>,
+    lookahead : Option<Lookahead<C>>,
+    /// Time the points released by the lookahead queue are delayed by, increased if the stream starves
+    shift : f32
+}
+
+impl<const C : usize> StreamReceiver<C> {
+    /// Blends the streamed points with the given lookahead queue, the points are executed as consecutive linear moves
+    /// with the corner velocities planned by the queue and their times are ignored. The queue must start at the 
+    /// current position of the robot
+    pub fn with_lookahead(mut self, lookahead : Lookahead<C>) -> Self {
+        self.lookahead = Some(lookahead);
+        self
+    }
+
+    /// Adds a received point to the buffer, either directly or through the lookahead queue
+    fn receive(&mut self, buffer : &mut Trajectory<C>, point : TrajPoint<C>, t_0 : f32, dt : f32, time : f32) 
+    -> Result<(), crate::Error> {
+        match self.lookahead.as_mut() {
+            Some(queue) => match queue.push(point.phis, dt) {
+                Some(traj) => append(buffer, traj, &mut self.shift, time),
+                None => Ok(())
+            },
+            None => buffer.push(point.time - t_0, point.phis)
+        }
+    }
+
+    /// Executes the stream on the robot, interpolating the points received at the given control `period`. Finishes when 
+    /// all the senders have been dropped and all points have been executed (see `with_lookahead()` for blended streams)
+    pub async fn run<R, G, T>(mut self, rob : &mut R, period : Duration, speed_f : Factor) -> Result<(), crate::Error> 
+    where
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        let mut buffer = Trajectory::<C>::new();
+        let mut closed = false;
+        let dt = period.as_secs_f32();
+
+        // Wait for the first point to set the time base
+        let Some(first) = self.rx.recv().await else {
+            return Ok(());
+        };
+        let t_0 = first.time;
+
+        // Blended streams start at the current position, the first point is already a movement
+        if self.lookahead.is_some() {
+            buffer.push(0.0, rob.phis())?;
+        }
+
+        self.receive(&mut buffer, first, t_0, dt, 0.0)?;
+
+        let start = Instant::now();
+        let mut interval = tokio::time::interval(period);
+
+        loop {
+            interval.tick().await;
+
+            let time = start.elapsed().as_secs_f32();
+
+            // Fetch all the points available
+            while !closed {
+                match self.rx.try_recv() {
+                    Ok(point) => self.receive(&mut buffer, point, t_0, dt, time)?,
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => {
+                        closed = true;
+
+                        // The remaining segments come to a stop at the last point
+                        if let Some(queue) = self.lookahead.as_mut() {
+                            append(&mut buffer, queue.flush(dt), &mut self.shift, time)?;
+                        }
+                    }
+                }
+            }
+
+            if let Some(phis) = buffer.sample(time) {
+                rob.move_abs_j(phis, speed_f).await?;
+            }
+
+            if time >= buffer.duration() {
+                if closed {
+                    return Ok(());
+                }
+            } else {
+                // Drop all points that are no longer required for interpolation
+                let index = buffer.points.partition_point(|p| p.time <= time);
+                if index > 1 {
+                    buffer.points.drain(.. index - 1);
+                }
+            }
+        }
+    }
+}
+
+/// Appends the points released by a lookahead queue, if the buffer has already run out at the given `time`, the points 
+/// are delayed to continue from there instead of jumping ahead
+fn append<const C : usize>(buffer : &mut Trajectory<C>, traj : Trajectory<C>, shift : &mut f32, time : f32) 
+-> Result<(), crate::Error> {
+    if let Some(first) = traj.points.first() {
+        if buffer.duration() < time {
+            *shift = shift.max(time - first.time);
+        }
+    }
+
+    for point in traj.points {
+        buffer.push(point.time + *shift, point.phis)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use core::time::Duration;
+
+use syact::math::movements::DefinedActuator;
+use syact::{SyncActuator, SyncActuatorGroup};
+
+use crate::{Robot, Station};
+use crate::robs::SpeedOverride;
+use crate::traj::Trajectory;
+
+/// The action performed by a `Trigger`
+#[derive(Clone, Debug, PartialEq)]
+pub enum TriggerAction {
+    /// Pushes a message to all the remotes of the robot, see `Robot::push_bytes`
+    Push { msg_type : String, msg : Vec<u8> },
+    /// Sets a named output of the station
+    Output { name : String, state : bool }
+}
+
+/// An action fired when the trajectory reaches the given time
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trigger {
+    /// Time of the trajectory in seconds
+    pub time : f32,
+    /// The action to perform
+    pub action : TriggerAction
+}
+
+/// A set of triggers attached to a trajectory, sorted by their time
+#[derive(Clone, Debug, Default)]
+pub struct TriggerSchedule {
+    triggers : Vec<Trigger>,
+    next : usize
+}
+
+impl TriggerSchedule {
+    /// Creates a new empty schedule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a trigger at the given time of the trajectory
+    pub fn at(mut self, time : f32, action : TriggerAction) -> Self {
+        let index = self.triggers.partition_point(|t| t.time <= time);
+        self.triggers.insert(index, Trigger { time, action });
+        self
+    }
+
+    /// Adds the action at each fraction (`0.0` to `1.0`) of the segment given by its start and end time, see
+    /// `segment_times`. E.g. fractions of `[0.3, 0.6, 0.9]` to fire a camera at 30%, 60% and 90% of the segment
+    pub fn segment(mut self, (start, end) : (f32, f32), fractions : &[f32], action : TriggerAction) -> Self {
+        for f in fractions {
+            self = self.at(start + (end - start) * f.clamp(0.0, 1.0), action.clone());
+        }
+        self
+    }
+
+    /// Returns all the triggers
+    pub fn triggers(&self) -> &[Trigger] {
+        &self.triggers
+    }
+
+    /// Resets the schedule, so all triggers fire again
+    pub fn reset(&mut self) {
+        self.next = 0;
+    }
+
+    /// Returns the triggers that are due at the given time and have not been fired yet
+    pub fn due(&mut self, time : f32) -> &[Trigger] {
+        let start = self.next;
+
+        while self.triggers.get(self.next).map_or(false, |t| t.time <= time) {
+            self.next += 1;
+        }
+
+        &self.triggers[start .. self.next]
+    }
+}
+
+impl<const C : usize> Trajectory<C> {
+    /// Executes the trajectory like `execute_scaled`, firing the triggers of the schedule as soon as the robot reaches
+    /// their time. The precision of the triggers is limited by the control `period`
+    pub async fn execute_triggered<S, R, G, T>(&self, rob : &mut R, stat : &mut S, period : Duration, ovr : &SpeedOverride,
+        schedule : &mut TriggerSchedule) -> Result<(), crate::Error>
+    where
+        S : Station<G, T, C, Robot = R>,
+        R : Robot<G, T, C>,
+        G : SyncActuatorGroup<T, C>,
+        T : SyncActuator + DefinedActuator + ?Sized + 'static
+    {
+        schedule.reset();
+
+        self.execute_with(rob, period, ovr, |rob, time| {
+            for trigger in schedule.due(time) {
+                match &trigger.action {
+                    TriggerAction::Push { msg_type, msg } => rob.push_bytes(msg_type, msg)?,
+                    TriggerAction::Output { name, state } => stat.set_output(name, *state)?
+                }
+            }
+
+            Ok(())
+        }).await
+    }
+}